@@ -0,0 +1,88 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use uinput_rs::{
+    BufferedDevice, Device, SYN_REPORT,
+    key_codes::{REL_X, REL_Y},
+    key_events::{BTN_LEFT_EVENT, REL_X_EVENT, REL_Y_EVENT},
+    key_types::{EV_REL, EV_SYN},
+};
+
+fn device() -> Option<Device> {
+    match Device::new(&[BTN_LEFT_EVENT, REL_X_EVENT, REL_Y_EVENT]) {
+        Ok(device) => Some(device),
+        Err(err) => {
+            eprintln!("Skipping emit benches, can't create a device: {err}");
+            None
+        }
+    }
+}
+
+fn emit(c: &mut Criterion) {
+    let Some(device) = device() else { return };
+
+    c.bench_function("emit", |b| {
+        b.iter(|| device.emit(black_box(EV_REL), black_box(REL_X), black_box(0)))
+    });
+
+    c.bench_function("emit frame", |b| {
+        b.iter(|| {
+            device.emit(EV_REL, REL_X, black_box(0)).unwrap();
+            device.emit(EV_REL, REL_Y, black_box(0)).unwrap();
+            device.sync()
+        })
+    });
+}
+
+fn emit_batch(c: &mut Criterion) {
+    let Some(device) = device() else { return };
+    let frame = [
+        uinput_rs::input_event {
+            time: uinput_rs::timeval {
+                tv_sec: 0,
+                tv_usec: 0,
+            },
+            type_: EV_REL,
+            code: REL_X,
+            value: 0,
+        },
+        uinput_rs::input_event {
+            time: uinput_rs::timeval {
+                tv_sec: 0,
+                tv_usec: 0,
+            },
+            type_: EV_REL,
+            code: REL_Y,
+            value: 0,
+        },
+        uinput_rs::input_event {
+            time: uinput_rs::timeval {
+                tv_sec: 0,
+                tv_usec: 0,
+            },
+            type_: EV_SYN,
+            code: SYN_REPORT,
+            value: 0,
+        },
+    ];
+
+    c.bench_function("emit_batch frame", |b| {
+        b.iter(|| device.emit_batch(black_box(&frame)))
+    });
+}
+
+fn buffered(c: &mut Criterion) {
+    let Some(device) = device() else { return };
+    let mut buffered = BufferedDevice::new(device);
+
+    c.bench_function("buffered frame", |b| {
+        b.iter(|| {
+            buffered.emit(EV_REL, REL_X, black_box(0));
+            buffered.emit(EV_REL, REL_Y, black_box(0));
+            buffered.sync()
+        })
+    });
+}
+
+criterion_group!(benches, emit, emit_batch, buffered);
+criterion_main!(benches);
@@ -0,0 +1,200 @@
+// With the `system-codes` feature, regenerates the key_codes tables from the
+// installed linux/input-event-codes.h so codes newer than this release are
+// usable, and makes names resolve the new codes. The pregenerated tables in
+// src/key_codes/ are the base; codes the header adds are appended to them,
+// so nothing existing ever changes value or disappears. Without the feature
+// this does nothing.
+
+use std::{collections::HashMap, env, fs, path::Path};
+
+const DEFAULT_HEADER: &str = "/usr/include/linux/input-event-codes.h";
+
+// (file under src/key_codes/, name prefix, event type)
+const MODULES: &[(&str, &str, u16)] = &[
+    ("keys.rs", "KEY_", 0x01),
+    ("buttons.rs", "BTN_", 0x01),
+    ("rel.rs", "REL_", 0x02),
+    ("abs.rs", "ABS_", 0x03),
+    ("switches.rs", "SW_", 0x05),
+    ("leds.rs", "LED_", 0x11),
+    ("sounds.rs", "SND_", 0x12),
+    ("misc.rs", "MSC_", 0x04),
+];
+
+// Range markers kept out of the ALL lists, same as the pregenerated ones.
+const MARKERS: &[&str] = &[
+    "KEY_RESERVED",
+    "KEY_MIN_INTERESTING",
+    "BTN_MISC",
+    "BTN_MOUSE",
+    "BTN_JOYSTICK",
+    "BTN_GAMEPAD",
+    "BTN_DIGI",
+    "BTN_WHEEL",
+    "BTN_TRIGGER_HAPPY",
+    "REL_RESERVED",
+    "ABS_RESERVED",
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_SYSTEM_CODES").is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-changed=src/key_codes");
+    println!("cargo:rerun-if-env-changed=UINPUT_EVENT_CODES_H");
+
+    let header_path = env::var("UINPUT_EVENT_CODES_H").unwrap_or_else(|_| DEFAULT_HEADER.into());
+    println!("cargo:rerun-if-changed={header_path}");
+    let header = match fs::read_to_string(&header_path) {
+        Ok(header) => parse_header(&header),
+        Err(e) => {
+            println!(
+                "cargo:warning=can't read {header_path} ({e}), using the pregenerated key codes"
+            );
+            Vec::new()
+        }
+    };
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("key_codes");
+    fs::create_dir_all(&out).unwrap();
+    let mut events = String::new();
+    let mut names: Vec<(u16, String)> = Vec::new();
+    for &(file, prefix, event_type) in MODULES {
+        let table = fs::read_to_string(Path::new("src/key_codes").join(file)).unwrap();
+        let (generated, extra) = merge(&table, prefix, &header);
+        fs::write(out.join(file), generated).unwrap();
+        for (name, value) in extra {
+            events.push_str(&format!(
+                "pub const {name}_EVENT: (u64, u64) = ({event_type:#04x}, {value:#04x});\n"
+            ));
+            if !name.ends_with("_MAX") {
+                names.push((event_type, format!("(\"{name}\", {value:#x}),")));
+            }
+        }
+    }
+    fs::write(out.join("key_events.rs"), events).unwrap();
+
+    let mut table = String::from(
+        "fn system_table(event_type: u16) -> &'static [(&'static str, u16)] {\n    match event_type {\n",
+    );
+    for &(_, _, event_type) in MODULES {
+        if table.contains(&format!("{event_type:#04x} =>")) {
+            continue;
+        }
+        table.push_str(&format!("        {event_type:#04x} => &["));
+        for (_, entry) in names.iter().filter(|(t, _)| *t == event_type) {
+            table.push_str(entry);
+        }
+        table.push_str("],\n");
+    }
+    table.push_str("        _ => &[],\n    }\n}\n");
+    fs::write(out.join("names.rs"), table).unwrap();
+}
+
+// Every `#define NAME value` in the header that resolves to a number,
+// following aliases like `#define BTN_A BTN_SOUTH`. Expressions such as the
+// `(KEY_MAX+1)` of the *_CNT entries are skipped.
+fn parse_header(header: &str) -> Vec<(String, u16)> {
+    let mut raw = Vec::new();
+    for line in header.lines() {
+        let line = line.split("/*").next().unwrap();
+        let mut words = line.split_whitespace();
+        if words.next() != Some("#define") {
+            continue;
+        }
+        if let (Some(name), Some(value), None) = (words.next(), words.next(), words.next()) {
+            raw.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    let mut values: HashMap<&str, u16> = HashMap::new();
+    // Aliases only ever point backwards, but loop until nothing changes so
+    // the order of the header doesn't matter.
+    loop {
+        let before = values.len();
+        for (name, value) in &raw {
+            if values.contains_key(name.as_str()) {
+                continue;
+            }
+            let resolved = match value.strip_prefix("0x") {
+                Some(hex) => u16::from_str_radix(hex, 16).ok(),
+                None => value
+                    .parse()
+                    .ok()
+                    .or_else(|| values.get(value.as_str()).copied()),
+            };
+            if let Some(resolved) = resolved {
+                values.insert(name, resolved);
+            }
+        }
+        if values.len() == before {
+            break;
+        }
+    }
+
+    raw.iter()
+        .filter_map(|(name, _)| Some((name.clone(), *values.get(name.as_str())?)))
+        .collect()
+}
+
+fn parse_literal(value: &str) -> u16 {
+    match value.strip_prefix("0x") {
+        Some(hex) => u16::from_str_radix(hex, 16).unwrap(),
+        None => value.parse().unwrap(),
+    }
+}
+
+// Appends the header's codes that `table` doesn't have to both its constants
+// and its ALL list. Returns the new table and the codes it added.
+fn merge(table: &str, prefix: &str, header: &[(String, u16)]) -> (String, Vec<(String, u16)>) {
+    let mut consts = Vec::new();
+    let mut values = HashMap::new();
+    for line in table.lines() {
+        let Some(rest) = line.strip_prefix("pub const ") else {
+            continue;
+        };
+        let Some((name, value)) = rest.split_once(": u16 = ") else {
+            continue;
+        };
+        let value = parse_literal(value.trim_end_matches(';'));
+        consts.push(line.to_string());
+        values.insert(name.to_string(), value);
+    }
+
+    let all_start = table.find("pub const ALL: &[u16] = &[").unwrap();
+    let all_body = &table[all_start..];
+    let all_body = &all_body[all_body.find('[').unwrap() + 1..];
+    let all_body = &all_body[all_body.find('[').unwrap() + 1..all_body.rfind(']').unwrap()];
+    let mut all: Vec<String> = all_body
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect();
+    let mut listed: Vec<u16> = all.iter().map(|name| values[name]).collect();
+
+    let mut extra = Vec::new();
+    for (name, value) in header {
+        if !name.starts_with(prefix) || values.contains_key(name) {
+            continue;
+        }
+        consts.push(format!("pub const {name}: u16 = {value:#x};"));
+        values.insert(name.clone(), *value);
+        extra.push((name.clone(), *value));
+        let marker = MARKERS.contains(&name.as_str()) || name.ends_with("_MAX");
+        if !marker && !listed.contains(value) {
+            all.push(name.clone());
+            listed.push(*value);
+        }
+    }
+
+    let mut generated = consts.join("\n");
+    generated.push_str("\n\n/// Every distinct code, without aliases, KEY_RESERVED or *_MAX.\n");
+    generated.push_str("pub const ALL: &[u16] = &[\n");
+    for name in &all {
+        generated.push_str(&format!("    {name},\n"));
+    }
+    generated.push_str("];\n");
+    (generated, extra)
+}
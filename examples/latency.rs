@@ -0,0 +1,96 @@
+//! Measures how long injected frames take to show up on the device's evdev node.
+//!
+//! Needs root (or access to /dev/uinput and /dev/input/event*).
+//! Run with: cargo run --release --example latency
+
+use std::{
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use uinput_rs::{
+    BufferedDevice, Device,
+    evdev::EvdevReader,
+    key_codes::REL_X,
+    key_events::{BTN_LEFT_EVENT, REL_X_EVENT, REL_Y_EVENT},
+    key_types::EV_REL,
+};
+
+const SAMPLES: usize = 2000;
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn main() -> std::io::Result<()> {
+    let device = Device::new(&[BTN_LEFT_EVENT, REL_X_EVENT, REL_Y_EVENT])?;
+
+    // Wait for the event node to show up.
+    let node = loop {
+        match device.event_node() {
+            Ok(node) => break node,
+            Err(_) => sleep(Duration::from_millis(10)),
+        }
+    };
+    let mut reader = EvdevReader::open(&node)?;
+    println!("Reading back from {}", node.display());
+
+    // Alternate directions so the pointer doesn't wander off.
+    for events_per_frame in [1, 4, 16] {
+        let samples = measure(&mut reader, |i| {
+            for _ in 0..events_per_frame {
+                device.emit(EV_REL, REL_X, if i % 2 == 0 { 1 } else { -1 })?;
+            }
+            device.sync()
+        })?;
+        report(&format!("emit, {events_per_frame} per frame"), samples);
+    }
+
+    let mut buffered = BufferedDevice::new(device);
+    for events_per_frame in [1, 4, 16] {
+        let samples = measure(&mut reader, |i| {
+            for _ in 0..events_per_frame {
+                buffered.emit(EV_REL, REL_X, if i % 2 == 0 { 1 } else { -1 });
+            }
+            buffered.sync()
+        })?;
+        report(&format!("buffered, {events_per_frame} per frame"), samples);
+    }
+
+    Ok(())
+}
+
+fn measure(
+    reader: &mut EvdevReader,
+    mut send_frame: impl FnMut(usize) -> std::io::Result<()>,
+) -> std::io::Result<Vec<Duration>> {
+    let mut samples = Vec::with_capacity(SAMPLES);
+    for i in 0..SAMPLES {
+        let start = Instant::now();
+        send_frame(i)?;
+        if reader.read_frame(TIMEOUT)?.is_none() {
+            eprintln!("Frame {i} never arrived");
+            continue;
+        }
+        samples.push(start.elapsed());
+    }
+    Ok(samples)
+}
+
+fn report(label: &str, mut samples: Vec<Duration>) {
+    if samples.is_empty() {
+        println!("{label}: no samples");
+        return;
+    }
+    samples.sort();
+
+    let percentile = |p: f64| samples[((samples.len() - 1) as f64 * p) as usize];
+    let micros: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1e6).collect();
+    let mean = micros.iter().sum::<f64>() / micros.len() as f64;
+    let jitter =
+        (micros.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / micros.len() as f64).sqrt();
+
+    println!(
+        "{label:>24}: p50 {:>8.1?} p99 {:>8.1?} max {:>8.1?} jitter {jitter:.1}µs",
+        percentile(0.50),
+        percentile(0.99),
+        samples[samples.len() - 1],
+    );
+}
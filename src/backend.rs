@@ -0,0 +1,631 @@
+use std::{
+    fmt::Debug,
+    fs::{File, OpenOptions},
+    io::{self, Read, Result, Write},
+    os::fd::{AsRawFd, RawFd},
+    sync::{Arc, Mutex},
+};
+
+use libc::{ff_effect, input_event, uinput_ff_erase, uinput_ff_upload, uinput_user_dev};
+
+use crate::{
+    DisplayEvent, IntoKeyName, SYN_REPORT, UI_BEGIN_FF_ERASE, UI_BEGIN_FF_UPLOAD, UI_DEV_CREATE,
+    UI_DEV_DESTROY, UI_END_FF_ERASE, UI_END_FF_UPLOAD, UI_SET_ABSBIT, UI_SET_EVBIT, UI_SET_FFBIT,
+    UI_SET_KEYBIT, UI_SET_LEDBIT, UI_SET_MSCBIT, UI_SET_PHYS, UI_SET_PROPBIT, UI_SET_RELBIT,
+    UI_SET_SNDBIT, UI_SET_SWBIT, event_type_name,
+    key_codes::{UI_FF_ERASE, UI_FF_UPLOAD},
+    key_types::*,
+};
+
+/// The three things a device needs from uinput: a handle, ioctls and writes.
+///
+/// Device talks to the kernel only through this, so swapping the backend
+/// lets you run code built on this crate without root or /dev/uinput.
+pub trait UinputBackend: Debug + Send + Sync {
+    /// Open a fresh handle.
+    fn open() -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Perform an ioctl. `arg` is either a plain value or a pointer cast to u64,
+    /// depending on the request.
+    ///
+    /// # Safety
+    /// For requests that take a pointer, `arg` has to point at a live value
+    /// of the type the request is for, writable when the kernel fills it
+    /// in: a uinput_abs_setup for UI_ABS_SETUP, a buffer of the encoded
+    /// length for UI_GET_SYSNAME, a uinput_ff_upload or uinput_ff_erase
+    /// with its request_id set for UI_BEGIN_FF_* and UI_END_FF_*, and a
+    /// nul-terminated string for UI_SET_PHYS. Implementations may read and
+    /// write through it on that basis, so a made up address can't get past
+    /// the compiler:
+    /// ```rust,compile_fail,E0133
+    /// use uinput_rs::{MockBackend, UI_SET_PHYS, UinputBackend};
+    ///
+    /// MockBackend::new().ioctl(UI_SET_PHYS, 0x10).unwrap();
+    /// ```
+    unsafe fn ioctl(&self, request: u64, arg: u64) -> Result<()>;
+
+    /// Write raw bytes (a uinput_user_dev or input_events).
+    fn write(&self, buf: &[u8]) -> Result<usize>;
+
+    /// Read input_events the kernel sent back (LED changes, force feedback
+    /// requests) without blocking. Ok(0) when nothing is pending, which is
+    /// all backends that can't receive anything ever return.
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let _ = buf;
+        Ok(0)
+    }
+
+    /// The uinput fd, for handing the device to another process. None for
+    /// backends that don't have one.
+    fn raw_fd(&self) -> Option<RawFd> {
+        None
+    }
+}
+
+pub(crate) const UINPUT_PATHS: [&str; 2] = ["/dev/uinput", "/dev/input/uinput"];
+
+/// EACCES with SELinux enforcing, on a node the usual permission bits would
+/// let us open read/write.
+pub(crate) fn selinux_denied(path: &str, err: &io::Error) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    if err.raw_os_error() != Some(libc::EACCES) {
+        return false;
+    }
+    let enforcing = std::fs::read_to_string("/sys/fs/selinux/enforce")
+        .is_ok_and(|enforce| enforce.trim() == "1");
+    let Ok(meta) = std::fs::metadata(path) else {
+        return false;
+    };
+    let (uid, gid) = unsafe { (libc::geteuid(), libc::getegid()) };
+    let mode = meta.mode();
+    let allowed = uid == 0
+        || mode & 0o006 == 0o006
+        || (meta.uid() == uid && mode & 0o600 == 0o600)
+        || (meta.gid() == gid && mode & 0o060 == 0o060);
+    enforcing && allowed
+}
+
+/// The real thing: /dev/uinput.
+/// With the `nix` feature the syscalls go through nix's checked wrappers.
+#[derive(Debug)]
+pub struct KernelBackend {
+    file: File,
+}
+
+impl UinputBackend for KernelBackend {
+    /// Tries /dev/uinput and then /dev/input/uinput, where some Android
+    /// and older systems keep it.
+    fn open() -> Result<Self> {
+        let mut last = None;
+        for path in UINPUT_PATHS {
+            match OpenOptions::new().read(true).write(true).open(path) {
+                Ok(file) => return Ok(Self { file }),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => last = Some(err),
+                Err(err) if selinux_denied(path, &err) => {
+                    return Err(crate::Error::SelinuxDenied {
+                        path: path.to_owned(),
+                    }
+                    .into());
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Err(last.unwrap_or_else(|| io::ErrorKind::NotFound.into()))
+    }
+
+    #[cfg(not(feature = "nix"))]
+    unsafe fn ioctl(&self, request: u64, arg: u64) -> Result<()> {
+        // The request parameter is a c_int on Android and musl.
+        let ret = unsafe { libc::ioctl(self.file.as_raw_fd(), request as _, arg) };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[cfg(not(feature = "nix"))]
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        (&self.file).write(buf)
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let mut pollfd = libc::pollfd {
+            fd: self.file.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let ready = unsafe { libc::poll(&mut pollfd, 1, 0) };
+        if ready < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if ready == 0 {
+            return Ok(0);
+        }
+        (&self.file).read(buf)
+    }
+
+    #[cfg(feature = "nix")]
+    unsafe fn ioctl(&self, request: u64, arg: u64) -> Result<()> {
+        unsafe { crate::nix_sys::ioctl(&self.file, request, arg) }
+    }
+
+    #[cfg(feature = "nix")]
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        crate::nix_sys::write(&self.file, buf)
+    }
+
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.file.as_raw_fd())
+    }
+}
+
+impl KernelBackend {
+    /// Use an already opened uinput handle, e.g. one received from a broker.
+    pub(crate) fn from_file(file: File) -> Self {
+        Self { file }
+    }
+}
+
+impl AsRawFd for KernelBackend {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
+
+/// Event type a UI_SET_*BIT request enables codes for.
+fn enabled_type(request: u64) -> Option<u16> {
+    Some(match request {
+        UI_SET_KEYBIT => EV_KEY,
+        UI_SET_RELBIT => EV_REL,
+        UI_SET_ABSBIT => EV_ABS,
+        UI_SET_MSCBIT => EV_MSC,
+        UI_SET_LEDBIT => EV_LED,
+        UI_SET_SNDBIT => EV_SND,
+        UI_SET_SWBIT => EV_SW,
+        UI_SET_FFBIT => EV_FF,
+        _ => return None,
+    })
+}
+
+/// Decodes a write of whole input_events.
+fn decode_events(bytes: &[u8]) -> impl Iterator<Item = input_event> + '_ {
+    bytes
+        .chunks_exact(std::mem::size_of::<input_event>())
+        .map(|bytes| unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const input_event) })
+}
+
+/// An ioctl call recorded by MockBackend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ioctl {
+    /// The request number, like UI_SET_EVBIT.
+    pub request: u64,
+    /// The argument as passed in.
+    pub arg: u64,
+}
+
+/// ff_effect has no Debug, this shows the ID.
+#[derive(Clone, Copy)]
+struct RawEffect(ff_effect);
+
+impl Debug for RawEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RawEffect({})", self.0.id)
+    }
+}
+
+/// A force feedback request waiting for its UI_BEGIN_FF_* ioctl.
+#[derive(Debug)]
+enum PendingFf {
+    Upload(RawEffect),
+    Erase(u32),
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+    ioctls: Vec<Ioctl>,
+    writes: Vec<Vec<u8>>,
+    incoming: std::collections::VecDeque<input_event>,
+    ff_pending: Vec<(u32, PendingFf)>,
+    ff_uploaded: Vec<RawEffect>,
+    ff_results: Vec<i32>,
+    ff_next_request: u32,
+    phys: Option<String>,
+}
+
+/// In-memory backend that accepts everything and remembers it.
+///
+/// Clones share the same recording, so keep a clone around to inspect what
+/// the device did.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{Device, MockBackend, UInputUserDevice, UI_DEV_CREATE, key_events::KEY_A_EVENT};
+///
+/// let mock = MockBackend::new();
+/// let device = Device::with_backend(mock.clone(), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap();
+/// device.emit_key_code(KEY_A_EVENT, 1).unwrap();
+///
+/// assert!(mock.ioctls().iter().any(|call| call.request == UI_DEV_CREATE));
+/// // The device description and the key press.
+/// assert_eq!(mock.writes().len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockBackend {
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl MockBackend {
+    /// Empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn recording(&self) -> std::sync::MutexGuard<'_, Recording> {
+        // A panic while holding the lock can't leave the Vecs half updated.
+        self.recording.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Queue an event for the device to read back, as if the kernel sent
+    /// it. Use it to fake LED changes or force feedback requests.
+    pub fn push_incoming(&self, event_type: u16, code: u16, value: i32) {
+        self.recording()
+            .incoming
+            .push_back(crate::device::new_event(event_type, code, value));
+    }
+
+    /// Queue a force feedback upload, as if an application called EVIOCSFF
+    /// on the device. The kernel has already picked `effect.id` by the time
+    /// uinput sees it, so set it. The previous upload to the same ID is
+    /// handed out as the old effect.
+    pub fn push_ff_upload(&self, effect: ff_effect) {
+        self.push_ff(PendingFf::Upload(RawEffect(effect)));
+    }
+
+    /// Queue a force feedback erase of an effect ID.
+    pub fn push_ff_erase(&self, effect_id: i16) {
+        self.push_ff(PendingFf::Erase(effect_id as u32));
+    }
+
+    fn push_ff(&self, pending: PendingFf) {
+        let mut recording = self.recording();
+        let request_id = recording.ff_next_request;
+        recording.ff_next_request += 1;
+        let code = match pending {
+            PendingFf::Upload(_) => UI_FF_UPLOAD,
+            PendingFf::Erase(_) => UI_FF_ERASE,
+        };
+        recording.ff_pending.push((request_id, pending));
+        recording
+            .incoming
+            .push_back(crate::device::new_event(EV_UINPUT, code, request_id as i32));
+    }
+
+    /// The retval of every finished force feedback upload and erase, in
+    /// order. 0 for accepted, minus an errno for refused.
+    pub fn ff_results(&self) -> Vec<i32> {
+        self.recording().ff_results.clone()
+    }
+
+    /// The phys the device was given with UI_SET_PHYS, if any.
+    pub fn phys(&self) -> Option<String> {
+        self.recording().phys.clone()
+    }
+
+    /// Every ioctl so far, in order.
+    pub fn ioctls(&self) -> Vec<Ioctl> {
+        self.recording().ioctls.clone()
+    }
+
+    /// Every write so far, in order.
+    pub fn writes(&self) -> Vec<Vec<u8>> {
+        self.recording().writes.clone()
+    }
+
+    /// The (TYPE, CODE) pairs enabled on the device, in the order they were enabled.
+    pub fn enabled(&self) -> Vec<(u16, u16)> {
+        self.recording()
+            .ioctls
+            .iter()
+            .filter_map(|call| Some((enabled_type(call.request)?, call.arg as u16)))
+            .collect()
+    }
+
+    /// Panics unless both the event type and the code were enabled.
+    #[track_caller]
+    pub fn assert_enabled(&self, event_type: u16, code: u16) {
+        let type_enabled = self
+            .ioctls()
+            .iter()
+            .any(|call| call.request == UI_SET_EVBIT && call.arg == event_type as u64);
+        assert!(type_enabled, "event type {event_type:#x} was never enabled");
+        assert!(
+            self.enabled().contains(&(event_type, code)),
+            "code {code:#x} of type {event_type:#x} was never enabled"
+        );
+    }
+
+    /// UI_DEV_CREATE was called.
+    pub fn created(&self) -> bool {
+        self.ioctls()
+            .iter()
+            .any(|call| call.request == UI_DEV_CREATE)
+    }
+
+    /// UI_DEV_DESTROY was called.
+    pub fn destroyed(&self) -> bool {
+        self.ioctls()
+            .iter()
+            .any(|call| call.request == UI_DEV_DESTROY)
+    }
+
+    /// The device description written during setup.
+    pub fn user_dev(&self) -> Option<uinput_user_dev> {
+        let recording = self.recording();
+        let bytes = recording
+            .writes
+            .iter()
+            .find(|write| write.len() == std::mem::size_of::<uinput_user_dev>())?;
+        Some(unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const uinput_user_dev) })
+    }
+
+    /// Every event written after the device was created, in order.
+    pub fn events(&self) -> Vec<input_event> {
+        self.recording()
+            .writes
+            .iter()
+            .filter(|write| write.len() != std::mem::size_of::<uinput_user_dev>())
+            .flat_map(|write| decode_events(write))
+            .collect()
+    }
+
+    /// Written events split into frames at each SYN_REPORT.
+    /// The SYN_REPORTs themselves are left out, and so are events after the
+    /// last one since they were never synced.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     key_codes::KEY_A,
+    ///     key_events::KEY_A_EVENT,
+    ///     key_types::EV_KEY,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let device = Device::with_backend(mock.clone(), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap();
+    /// mock.assert_enabled(EV_KEY, KEY_A);
+    ///
+    /// device.emit_key_code(KEY_A_EVENT, 1).unwrap();
+    /// device.sync().unwrap();
+    /// device.emit_key_code(KEY_A_EVENT, 0).unwrap();
+    /// device.sync().unwrap();
+    ///
+    /// let frames = mock.frames();
+    /// assert_eq!(frames.len(), 2);
+    /// assert_eq!((frames[0][0].code, frames[0][0].value), (KEY_A, 1));
+    /// assert_eq!((frames[1][0].code, frames[1][0].value), (KEY_A, 0));
+    /// ```
+    pub fn frames(&self) -> Vec<Vec<input_event>> {
+        let mut frames = Vec::new();
+        let mut frame = Vec::new();
+        for event in self.events() {
+            if event.type_ == EV_SYN && event.code == SYN_REPORT {
+                frames.push(std::mem::take(&mut frame));
+            } else {
+                frame.push(event);
+            }
+        }
+        frames
+    }
+
+    /// Forget everything recorded so far.
+    pub fn clear(&self) {
+        let mut recording = self.recording();
+        recording.ioctls.clear();
+        recording.writes.clear();
+    }
+}
+
+impl UinputBackend for MockBackend {
+    fn open() -> Result<Self> {
+        Ok(Self::new())
+    }
+
+    unsafe fn ioctl(&self, request: u64, arg: u64) -> Result<()> {
+        let mut recording = self.recording();
+        recording.ioctls.push(Ioctl { request, arg });
+        match request {
+            UI_BEGIN_FF_UPLOAD | UI_BEGIN_FF_ERASE => {
+                // Both structs start with the request ID, and the caller
+                // promised one of them is behind arg.
+                let request_id = unsafe { *(arg as *const u32) };
+                let index = recording
+                    .ff_pending
+                    .iter()
+                    .position(|(id, _)| *id == request_id)
+                    .ok_or_else(|| io::Error::from_raw_os_error(libc::EINVAL))?;
+                let (_, pending) = recording.ff_pending.remove(index);
+                match pending {
+                    PendingFf::Upload(RawEffect(effect)) => {
+                        let upload = unsafe { &mut *(arg as *mut uinput_ff_upload) };
+                        upload.effect = effect;
+                        upload.old = match recording
+                            .ff_uploaded
+                            .iter()
+                            .find(|old| old.0.id == effect.id)
+                        {
+                            Some(old) => old.0,
+                            None => unsafe { std::mem::zeroed() },
+                        };
+                        recording.ff_uploaded.retain(|old| old.0.id != effect.id);
+                        recording.ff_uploaded.push(RawEffect(effect));
+                    }
+                    PendingFf::Erase(effect_id) => {
+                        let erase = unsafe { &mut *(arg as *mut uinput_ff_erase) };
+                        erase.effect_id = effect_id;
+                        recording
+                            .ff_uploaded
+                            .retain(|old| old.0.id as u32 != effect_id);
+                    }
+                }
+            }
+            UI_SET_PHYS => {
+                // A nul-terminated string, per the trait's contract.
+                let phys = unsafe { std::ffi::CStr::from_ptr(arg as *const libc::c_char) };
+                recording.phys = Some(phys.to_string_lossy().into_owned());
+            }
+            UI_END_FF_UPLOAD | UI_END_FF_ERASE => {
+                // retval follows the request ID in both.
+                let retval = unsafe { *(arg as *const i32).add(1) };
+                recording.ff_results.push(retval);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        self.recording().writes.push(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let size = std::mem::size_of::<input_event>();
+        let mut recording = self.recording();
+        let mut len = 0;
+        while len + size <= buf.len() {
+            let Some(event) = recording.incoming.pop_front() else {
+                break;
+            };
+            let bytes = unsafe {
+                std::slice::from_raw_parts(&event as *const input_event as *const u8, size)
+            };
+            buf[len..len + size].copy_from_slice(bytes);
+            len += size;
+        }
+        Ok(len)
+    }
+}
+
+/// Backend that describes what would happen instead of doing it.
+///
+/// Nothing touches the kernel. Every step is written as a line of text
+/// (to stderr by default), so an automation script can be reviewed before
+/// it runs for real.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{Device, DryRunBackend, UInputUserDevice, key_events::KEY_A_EVENT};
+///
+/// let out = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+/// # struct Shared(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+/// # impl std::io::Write for Shared {
+/// #     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> { self.0.lock().unwrap().write(buf) }
+/// #     fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+/// # }
+/// let backend = DryRunBackend::with_writer(Shared(out.clone()));
+/// let device = Device::with_backend(backend, &[KEY_A_EVENT], &UInputUserDevice::with_name("script")).unwrap();
+/// device.emit_key_code(KEY_A_EVENT, 1).unwrap();
+/// device.sync().unwrap();
+///
+/// let out = String::from_utf8(out.lock().unwrap().clone()).unwrap();
+/// assert!(out.contains("would emit EV_KEY KEY_A 1"));
+/// ```
+pub struct DryRunBackend {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Debug for DryRunBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DryRunBackend").finish_non_exhaustive()
+    }
+}
+
+impl Default for DryRunBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DryRunBackend {
+    /// Describe everything on stderr.
+    pub fn new() -> Self {
+        Self::with_writer(io::stderr())
+    }
+
+    /// Describe everything to out instead.
+    pub fn with_writer(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Mutex::new(Box::new(out)),
+        }
+    }
+
+    fn line(&self, args: std::fmt::Arguments<'_>) -> Result<()> {
+        let mut out = self.out.lock().unwrap_or_else(|err| err.into_inner());
+        out.write_fmt(args)?;
+        out.write_all(b"\n")
+    }
+}
+
+impl UinputBackend for DryRunBackend {
+    fn open() -> Result<Self> {
+        Ok(Self::new())
+    }
+
+    unsafe fn ioctl(&self, request: u64, arg: u64) -> Result<()> {
+        if let Some(event_type) = enabled_type(request) {
+            let code = arg as u16;
+            let name = match event_type {
+                EV_KEY => code.into_key_name(),
+                _ => "",
+            };
+            return match name {
+                "" => self.line(format_args!(
+                    "would enable {} {code:#x}",
+                    event_type_name(event_type)
+                )),
+                name => self.line(format_args!(
+                    "would enable {} {name}",
+                    event_type_name(event_type)
+                )),
+            };
+        }
+        match request {
+            UI_SET_PROPBIT => self.line(format_args!("would set input property {arg:#x}")),
+            UI_SET_PHYS => {
+                // A nul-terminated string, per the trait's contract.
+                let phys = unsafe { std::ffi::CStr::from_ptr(arg as *const libc::c_char) };
+                self.line(format_args!("would set phys {:?}", phys.to_string_lossy()))
+            }
+            UI_DEV_CREATE => self.line(format_args!("would create the device")),
+            UI_DEV_DESTROY => self.line(format_args!("would destroy the device")),
+            _ => Ok(()),
+        }
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        if buf.len() == std::mem::size_of::<uinput_user_dev>() {
+            let device =
+                unsafe { std::ptr::read_unaligned(buf.as_ptr() as *const uinput_user_dev) };
+            let name: Vec<u8> = device
+                .name
+                .iter()
+                .take_while(|&&b| b != 0)
+                .map(|&b| b as u8)
+                .collect();
+            self.line(format_args!(
+                "would describe the device as \"{}\"",
+                String::from_utf8_lossy(&name)
+            ))?;
+        } else {
+            for event in decode_events(buf) {
+                self.line(format_args!("would emit {}", DisplayEvent(&event)))?;
+            }
+        }
+        Ok(buf.len())
+    }
+}
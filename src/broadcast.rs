@@ -0,0 +1,84 @@
+use std::io::Result;
+
+use libc::input_event;
+
+use crate::Device;
+
+/// Sends the same frames to several devices.
+/// Handy for multi-seat testing or mirroring input to more than one
+/// virtual device.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::{Broadcast, Device, input_event, timeval, SYN_REPORT, key_codes::REL_X,
+///     key_events::REL_X_EVENT, key_types::{EV_REL, EV_SYN}};
+///
+/// let devices = vec![Device::new(&[REL_X_EVENT]).unwrap(), Device::new(&[REL_X_EVENT]).unwrap()];
+/// let broadcast = Broadcast::new(devices);
+///
+/// let time = timeval { tv_sec: 0, tv_usec: 0 };
+/// broadcast.emit_frame(&[
+///     input_event { time, type_: EV_REL, code: REL_X, value: 10 },
+///     input_event { time, type_: EV_SYN, code: SYN_REPORT, value: 0 },
+/// ]).unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct Broadcast {
+    devices: Vec<Device>,
+}
+
+impl Broadcast {
+    /// Broadcast to the given devices.
+    pub fn new(devices: Vec<Device>) -> Self {
+        Self { devices }
+    }
+
+    /// Add another device.
+    pub fn push(&mut self, device: Device) {
+        self.devices.push(device);
+    }
+
+    /// Write the events to every device, one write per device.
+    /// Every device gets the frame even if an earlier one fails.
+    /// Returns the first error.
+    pub fn emit_frame(&self, events: &[input_event]) -> Result<()> {
+        let mut result = Ok(());
+        for device in &self.devices {
+            let written = device.emit_batch(events);
+            if result.is_ok() {
+                result = written;
+            }
+        }
+        result
+    }
+
+    /// Same as emit_frame but reports every device's result separately.
+    pub fn emit_frame_each(&self, events: &[input_event]) -> Vec<Result<()>> {
+        self.devices
+            .iter()
+            .map(|device| device.emit_batch(events))
+            .collect()
+    }
+
+    /// Send a SYN_REPORT to every device.
+    pub fn sync(&self) -> Result<()> {
+        let mut result = Ok(());
+        for device in &self.devices {
+            let written = device.sync();
+            if result.is_ok() {
+                result = written;
+            }
+        }
+        result
+    }
+
+    /// The devices in this broadcast.
+    pub fn devices(&self) -> &[Device] {
+        &self.devices
+    }
+
+    /// Give the devices back.
+    pub fn into_inner(self) -> Vec<Device> {
+        self.devices
+    }
+}
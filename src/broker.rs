@@ -0,0 +1,451 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read, Result, Write},
+    mem::size_of,
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+        unix::net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use libc::uinput_user_dev;
+
+use crate::{Device, KernelBackend, UInputUserDevice, UinputBackend};
+
+/// More than any real device enables. Keeps a hostile client from making
+/// the broker allocate without bound.
+const MAX_EVENTS: u32 = 4096;
+
+/// The longest request read_request accepts: MAX_EVENTS pairs, the
+/// description, absres, properties and a phys under 1024 bytes.
+const MAX_REQUEST_LEN: usize =
+    4 + MAX_EVENTS as usize * 16 + size_of::<uinput_user_dev>() + 64 * 4 + 4 + 4 + 1024;
+
+/// How long a client gets to send its request before it's dropped, so one
+/// that connects and goes quiet can't hold up the ones behind it.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Owns access to /dev/uinput and hands created devices to unprivileged
+/// processes.
+///
+/// A client sends the events and the UInputUserDevice it wants. The broker
+/// validates them, opens /dev/uinput, does the setup ioctls, creates the
+/// device and passes the fd back over SCM_RIGHTS. The client writes events
+/// itself, the broker isn't involved after that and the device lives as
+/// long as the client keeps it.
+///
+/// Run the broker as a small root (or `input` group) service, or fork it
+/// off before dropping privileges and talk over a socketpair with
+/// broker::handle.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::{UInputUserDevice, broker::{self, Broker}, key_events::KEY_A_EVENT};
+///
+/// // In the privileged helper:
+/// let broker = Broker::bind("/run/uinput-broker.sock").unwrap().allow_uid(1000);
+/// std::thread::spawn(move || broker.serve());
+///
+/// // In the unprivileged process:
+/// let device = broker::connect(
+///     "/run/uinput-broker.sock",
+///     &[KEY_A_EVENT],
+///     &UInputUserDevice::with_name("brokered keyboard"),
+/// )
+/// .unwrap();
+/// device.emit_key_code(KEY_A_EVENT, 1).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Broker {
+    listener: UnixListener,
+    path: Option<PathBuf>,
+    allowed_uids: Vec<u32>,
+}
+
+impl Broker {
+    /// Listen at path, replacing a stale socket. Only the broker's own uid
+    /// is served until allow_uid adds others.
+    pub fn bind(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        match fs::remove_file(&path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        let listener = UnixListener::bind(&path)?;
+        Ok(Self {
+            listener,
+            path: Some(path),
+            allowed_uids: Vec::new(),
+        })
+    }
+
+    /// Serve on an already bound listener, e.g. from socket activation.
+    pub fn from_listener(listener: UnixListener) -> Self {
+        Self {
+            listener,
+            path: None,
+            allowed_uids: Vec::new(),
+        }
+    }
+
+    /// Also serve peers running as this uid. Can be called more than once.
+    /// Without it only peers with the broker's own uid are served.
+    pub fn allow_uid(mut self, uid: u32) -> Self {
+        self.allowed_uids.push(uid);
+        self
+    }
+
+    /// Accept and serve clients one at a time, forever. A client gets a
+    /// few seconds to send its request before it's dropped.
+    /// Returns only on listener errors; failed requests are reported to
+    /// the client and skipped.
+    pub fn serve(&self) -> Result<()> {
+        loop {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if let Err(_err) = self.serve_client(&stream) {
+                #[cfg(feature = "log")]
+                log::warn!("broker: {_err}");
+            }
+        }
+    }
+
+    fn serve_client(&self, stream: &UnixStream) -> Result<()> {
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+        let uid = peer_uid(stream)?;
+        if uid != unsafe { libc::geteuid() } && !self.allowed_uids.contains(&uid) {
+            let err = io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("uid {uid} is not allowed to create devices"),
+            );
+            return respond(stream, Err(err));
+        }
+        handle(stream)
+    }
+}
+
+impl Drop for Broker {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Serve one request on a connected stream. This is the broker half
+/// without the listener, for a socketpair shared with a forked child.
+///
+/// Example:
+/// ```rust
+/// use std::os::unix::net::UnixStream;
+///
+/// use uinput_rs::{UInputUserDevice, broker, key_events::KEY_A_EVENT};
+///
+/// let (client, server) = UnixStream::pair().unwrap();
+/// std::thread::spawn(move || broker::handle(&server));
+///
+/// // Empty names are rejected before the broker touches /dev/uinput.
+/// let mut info = UInputUserDevice::default();
+/// info.name = [0; 80];
+/// let err = broker::request(&client, &[KEY_A_EVENT], &info).unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+/// ```
+pub fn handle(stream: &UnixStream) -> Result<()> {
+    let created = read_request(stream).and_then(|(events, info)| {
+        info.validate(&events)?;
+        let backend = KernelBackend::open()?;
+        Device::setup(&backend, &events, &info)?;
+        Ok(backend)
+    });
+    respond(stream, created)
+}
+
+/// Ask the broker on stream for a device. The returned device is as good
+/// as one from Device::new_custom.
+pub fn request(
+    stream: &UnixStream,
+    events: &[(u64, u64)],
+    device: &UInputUserDevice,
+) -> Result<Device> {
+    (&*stream).write_all(&encode(events, device))?;
+
+    let mut header = [0u8; 8];
+    let fd = recv_with_fd(stream, &mut header)?;
+    let status = i32::from_ne_bytes(header[..4].try_into().unwrap());
+    let len = u32::from_ne_bytes(header[4..].try_into().unwrap());
+    let mut message = vec![0u8; len as usize];
+    (&*stream).read_exact(&mut message)?;
+
+    if status != 0 {
+        let message = String::from_utf8_lossy(&message);
+        let kind = io::Error::from_raw_os_error(status).kind();
+        return Err(io::Error::new(kind, format!("broker: {message}")));
+    }
+    let fd = fd.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "broker replied without an fd")
+    })?;
+    let backend = KernelBackend::from_file(File::from(fd));
+    Ok(Device::adopt(Box::new(backend), events, device))
+}
+
+/// Hand a device to the process on the other end of stream, which picks
+/// it up with receive_device_fd. Both ends then share the device and
+/// events written by either get through. Dropping either Device destroys
+/// it for both, so keep the sending side around for as long as the other
+/// needs it. Only devices on /dev/uinput have an fd to send.
+///
+/// Example:
+/// ```rust,no_run
+/// use std::os::unix::net::UnixStream;
+/// use uinput_rs::{broker, devices, key_codes::KEY_A, key_types::EV_KEY};
+///
+/// let (parent, child) = UnixStream::pair().unwrap();
+/// let keyboard = devices::keyboard("Shared keyboard").unwrap();
+/// broker::send_device_fd(&parent, &keyboard).unwrap();
+///
+/// // In the container or sandboxed child:
+/// let received = broker::receive_device_fd(&child).unwrap();
+/// received.emit(EV_KEY, KEY_A, 1).unwrap();
+/// received.sync().unwrap();
+/// ```
+pub fn send_device_fd(stream: &UnixStream, device: &Device) -> Result<()> {
+    let fd = device.raw_fd().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the device's backend has no fd to send",
+        )
+    })?;
+    let (events, info) = device.description();
+    let description = encode(&events, &info);
+    send_with_fd(stream, &(description.len() as u32).to_ne_bytes(), Some(fd))?;
+    (&*stream).write_all(&description)
+}
+
+/// Pick up a device sent with send_device_fd.
+pub fn receive_device_fd(stream: &UnixStream) -> Result<Device> {
+    let mut len = [0u8; 4];
+    let fd = recv_with_fd(stream, &mut len)?;
+    let fd =
+        fd.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "message came without an fd"))?;
+    let len = u32::from_ne_bytes(len) as usize;
+    if len > MAX_REQUEST_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{len} byte device description is more than any device needs"),
+        ));
+    }
+    let mut description = vec![0u8; len];
+    (&*stream).read_exact(&mut description)?;
+    let (events, info) = read_request(description.as_slice())?;
+    let backend = KernelBackend::from_file(File::from(fd));
+    Ok(Device::adopt(Box::new(backend), &events, &info))
+}
+
+/// Connect to a broker listening at path and ask it for a device.
+pub fn connect(
+    path: impl AsRef<Path>,
+    events: &[(u64, u64)],
+    device: &UInputUserDevice,
+) -> Result<Device> {
+    request(&UnixStream::connect(path)?, events, device)
+}
+
+/// Events and description in the request format.
+fn encode(events: &[(u64, u64)], device: &UInputUserDevice) -> Vec<u8> {
+    let mut request = Vec::new();
+    request.extend((events.len() as u32).to_ne_bytes());
+    for (event_type, code) in events {
+        request.extend(event_type.to_ne_bytes());
+        request.extend(code.to_ne_bytes());
+    }
+    let info = device.as_uinput_user_dev();
+    request.extend_from_slice(unsafe {
+        std::slice::from_raw_parts(
+            &info as *const uinput_user_dev as *const u8,
+            size_of::<uinput_user_dev>(),
+        )
+    });
+    for res in device.absres {
+        request.extend(res.to_ne_bytes());
+    }
+    request.extend(device.properties.to_ne_bytes());
+    request.extend((device.phys.len() as u32).to_ne_bytes());
+    request.extend(device.phys.as_bytes());
+    request
+}
+
+fn read_request(mut stream: impl Read) -> Result<(Vec<(u64, u64)>, UInputUserDevice)> {
+    let mut count = [0u8; 4];
+    stream.read_exact(&mut count)?;
+    let count = u32::from_ne_bytes(count);
+    if count > MAX_EVENTS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{count} events is more than the broker allows"),
+        ));
+    }
+
+    let mut events = Vec::with_capacity(count as usize);
+    let mut pair = [0u8; 16];
+    for _ in 0..count {
+        stream.read_exact(&mut pair)?;
+        let event_type = u64::from_ne_bytes(pair[..8].try_into().unwrap());
+        let code = u64::from_ne_bytes(pair[8..].try_into().unwrap());
+        events.push((event_type, code));
+    }
+
+    let mut info = [0u8; size_of::<uinput_user_dev>()];
+    stream.read_exact(&mut info)?;
+    let info = unsafe { std::ptr::read_unaligned(info.as_ptr() as *const uinput_user_dev) };
+    let mut absres = [0i32; 64];
+    let mut res = [0u8; 4];
+    for value in absres.iter_mut() {
+        stream.read_exact(&mut res)?;
+        *value = i32::from_ne_bytes(res);
+    }
+    let mut properties = [0u8; 4];
+    stream.read_exact(&mut properties)?;
+    let mut phys_len = [0u8; 4];
+    stream.read_exact(&mut phys_len)?;
+    let phys_len = u32::from_ne_bytes(phys_len);
+    if phys_len >= 1024 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{phys_len} byte phys is more than the kernel takes"),
+        ));
+    }
+    let mut phys = vec![0u8; phys_len as usize];
+    stream.read_exact(&mut phys)?;
+    let phys = String::from_utf8(phys)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "phys is not UTF-8"))?;
+    let info = UInputUserDevice {
+        name: info.name,
+        id: info.id,
+        ff_effects_max: info.ff_effects_max,
+        absmax: info.absmax,
+        absmin: info.absmin,
+        absfuzz: info.absfuzz,
+        absflat: info.absflat,
+        absres,
+        properties: u32::from_ne_bytes(properties),
+        phys,
+    };
+    Ok((events, info))
+}
+
+/// Status, message length and message. The fd rides along on success.
+fn respond(stream: &UnixStream, created: Result<KernelBackend>) -> Result<()> {
+    let (status, message, fd) = match &created {
+        Ok(backend) => (0, String::new(), Some(backend.as_raw_fd())),
+        Err(err) => (
+            err.raw_os_error().unwrap_or(match err.kind() {
+                io::ErrorKind::PermissionDenied => libc::EPERM,
+                _ => libc::EINVAL,
+            }),
+            err.to_string(),
+            None,
+        ),
+    };
+    let mut header = [0u8; 8];
+    header[..4].copy_from_slice(&i32::to_ne_bytes(status));
+    header[4..].copy_from_slice(&(message.len() as u32).to_ne_bytes());
+    send_with_fd(stream, &header, fd)?;
+    (&*stream).write_all(message.as_bytes())?;
+    created.map(drop)
+}
+
+fn peer_uid(stream: &UnixStream) -> Result<u32> {
+    let mut cred: libc::ucred = unsafe { std::mem::zeroed() };
+    let mut len = size_of::<libc::ucred>() as libc::socklen_t;
+    let ret = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut cred as *mut libc::ucred as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(cred.uid)
+}
+
+/// Room for one SCM_RIGHTS fd, aligned like cmsghdr wants.
+type ControlBuf = [u64; 4];
+
+fn send_with_fd(stream: &UnixStream, bytes: &[u8], fd: Option<RawFd>) -> Result<()> {
+    let mut iov = libc::iovec {
+        iov_base: bytes.as_ptr() as *mut libc::c_void,
+        iov_len: bytes.len(),
+    };
+    let mut control: ControlBuf = [0; 4];
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+
+    if let Some(fd) = fd {
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = unsafe { libc::CMSG_SPACE(size_of::<RawFd>() as u32) } as _;
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = libc::SOL_SOCKET;
+            (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<RawFd>() as u32) as _;
+            std::ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut RawFd, fd);
+        }
+    }
+
+    let sent = unsafe { libc::sendmsg(stream.as_raw_fd(), &msg, libc::MSG_NOSIGNAL) };
+    if sent < 0 {
+        Err(io::Error::last_os_error())
+    } else if sent as usize != bytes.len() {
+        Err(io::ErrorKind::WriteZero.into())
+    } else {
+        Ok(())
+    }
+}
+
+fn recv_with_fd(stream: &UnixStream, buf: &mut [u8]) -> Result<Option<OwnedFd>> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    let mut control: ControlBuf = [0; 4];
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = size_of::<ControlBuf>() as _;
+
+    let received = unsafe { libc::recvmsg(stream.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC) };
+    if received < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut fd = None;
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_RIGHTS {
+                let raw = std::ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const RawFd);
+                fd = Some(OwnedFd::from_raw_fd(raw));
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    let received = received as usize;
+    if received < buf.len() {
+        // The fd only comes with the first bytes, the rest is plain data.
+        (&*stream).read_exact(&mut buf[received..])?;
+    }
+    Ok(fd)
+}
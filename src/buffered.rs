@@ -0,0 +1,143 @@
+use std::io::Result;
+
+use libc::input_event;
+
+use crate::{
+    Device, SYN_REPORT,
+    device::new_event,
+    key_types::{EV_REL, EV_SYN},
+};
+
+/// Wraps a device and keeps emitted events in a userspace buffer until
+/// flush() or sync() is called.
+/// Everything in the buffer is written to the kernel with one write, so a
+/// whole frame (or several) shows up to consumers at once.
+///
+/// The buffer is flushed when this is dropped, like BufWriter does.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::{BufferedDevice, Device, key_events::{REL_X_EVENT, REL_Y_EVENT}};
+///
+/// let device = Device::new(&[REL_X_EVENT, REL_Y_EVENT]).unwrap();
+/// let mut buffered = BufferedDevice::new(device);
+///
+/// buffered.emit_key_code(REL_X_EVENT, 5);
+/// buffered.emit_key_code(REL_Y_EVENT, -5);
+/// // Both events and the SYN_REPORT go out in a single write.
+/// buffered.sync().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct BufferedDevice {
+    device: Option<Device>,
+    buffer: Vec<input_event>,
+    /// Index of the first event after the last queued SYN_REPORT.
+    frame_start: usize,
+    coalesce_rel: bool,
+}
+
+impl BufferedDevice {
+    /// Wrap a device with a default sized buffer.
+    pub fn new(device: Device) -> Self {
+        Self::with_capacity(device, 64)
+    }
+
+    /// Wrap a device with room for `capacity` events before the buffer has to grow.
+    pub fn with_capacity(device: Device, capacity: usize) -> Self {
+        Self {
+            device: Some(device),
+            buffer: Vec::with_capacity(capacity),
+            frame_start: 0,
+            coalesce_rel: false,
+        }
+    }
+
+    /// Merge relative events with the same code inside one frame into a single
+    /// accumulated delta.
+    /// Useful when the source (network packets for example) produces motion
+    /// faster than it's worth sending. Off by default.
+    ///
+    /// Three REL_X 1 events followed by sync() turn into one REL_X 3.
+    pub fn set_coalesce_rel(&mut self, coalesce: bool) {
+        self.coalesce_rel = coalesce;
+    }
+
+    /// Queue a single event. Nothing is written until flush or sync.
+    pub fn emit(&mut self, event_type: u16, code: u16, value: i32) {
+        self.emit_custom(new_event(event_type, code, value));
+    }
+
+    /// Same as emit but using the key code format.
+    pub fn emit_key_code(&mut self, key_code: (u64, u64), value: i32) {
+        self.emit(key_code.0 as u16, key_code.1 as u16, value)
+    }
+
+    /// Queue a custom event.
+    pub fn emit_custom(&mut self, event: input_event) {
+        if self.coalesce_rel && event.type_ == EV_REL {
+            let frame = &mut self.buffer[self.frame_start..];
+            if let Some(queued) = frame
+                .iter_mut()
+                .find(|queued| queued.type_ == EV_REL && queued.code == event.code)
+            {
+                queued.value = queued.value.saturating_add(event.value);
+                return;
+            }
+        }
+
+        self.buffer.push(event);
+        if event.type_ == EV_SYN && event.code == SYN_REPORT {
+            self.frame_start = self.buffer.len();
+        }
+    }
+
+    /// Write every queued event to the kernel with one write.
+    /// The buffer is cleared even if the write fails so a broken frame isn't
+    /// sent twice.
+    pub fn flush(&mut self) -> Result<()> {
+        let result = self.device().emit_batch(&self.buffer);
+        self.discard();
+        result
+    }
+
+    /// Queue a SYN_REPORT and flush.
+    pub fn sync(&mut self) -> Result<()> {
+        self.emit(EV_SYN, SYN_REPORT, 0);
+        self.flush()
+    }
+
+    /// Events currently waiting in the buffer.
+    pub fn pending(&self) -> &[input_event] {
+        &self.buffer
+    }
+
+    /// Drop queued events without writing them.
+    pub fn discard(&mut self) {
+        self.buffer.clear();
+        self.frame_start = 0;
+    }
+
+    /// The wrapped device.
+    pub fn device(&self) -> &Device {
+        self.device
+            .as_ref()
+            .expect("device is only taken in into_inner")
+    }
+
+    /// Flush and give back the wrapped device.
+    pub fn into_inner(mut self) -> Result<Device> {
+        self.flush()?;
+        Ok(self
+            .device
+            .take()
+            .expect("device is only taken in into_inner"))
+    }
+}
+
+impl Drop for BufferedDevice {
+    fn drop(&mut self) {
+        if self.device.is_some() {
+            let _ = self.flush();
+        }
+    }
+}
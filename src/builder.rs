@@ -0,0 +1,231 @@
+use std::{io::Result, time::Duration};
+
+use libc::input_id;
+
+use crate::{
+    Device, NamePolicy, UInputUserDevice,
+    key_types::{EV_ABS, EV_KEY, EV_REL},
+    name_from_str_with,
+};
+
+/// Builds a device step by step instead of filling in UInputUserDevice and
+/// the event list by hand.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::{
+///     DeviceBuilder,
+///     key_codes::{ABS_X, ABS_Y, BTN_LEFT, KEY_A},
+/// };
+///
+/// let device = DeviceBuilder::new()
+///     .name("my-device")
+///     .key(KEY_A)
+///     .key(BTN_LEFT)
+///     .abs(ABS_X, 0, 4096)
+///     .abs(ABS_Y, 0, 4096)
+///     .build()
+///     .unwrap();
+/// ```
+pub struct DeviceBuilder {
+    name: Option<String>,
+    name_policy: NamePolicy,
+    device: UInputUserDevice,
+    events: Vec<(u64, u64)>,
+    wait_registered: Option<Duration>,
+}
+
+impl Default for DeviceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceBuilder {
+    /// Starts from UInputUserDevice::default() with no events enabled.
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            name_policy: NamePolicy::Strict,
+            device: UInputUserDevice::default(),
+            events: Vec::new(),
+            wait_registered: None,
+        }
+    }
+
+    /// Human readable name of the device.
+    /// An invalid name makes build() fail, unless name_policy says otherwise.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    /// What to do with names that are too long or contain a null byte.
+    /// The default is NamePolicy::Strict.
+    pub fn name_policy(mut self, policy: NamePolicy) -> Self {
+        self.name_policy = policy;
+        self
+    }
+
+    /// Bus type, vendor, product and version.
+    pub fn id(mut self, id: input_id) -> Self {
+        self.device.id = id;
+        self
+    }
+
+    /// Physical path, see the phys module for realistic ones.
+    pub fn phys(mut self, phys: &str) -> Self {
+        self.device.phys = phys.to_owned();
+        self
+    }
+
+    /// Maximum number of simultaneous force feedback effects.
+    pub fn ff_effects_max(mut self, max: u32) -> Self {
+        self.device.ff_effects_max = max;
+        self
+    }
+
+    /// Enable a single event in the (TYPE, CODE) format.
+    pub fn event(mut self, event: (u64, u64)) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Enable several events in the (TYPE, CODE) format, for example one of
+    /// the tables in the devices module.
+    pub fn events(mut self, events: &[(u64, u64)]) -> Self {
+        self.events.extend_from_slice(events);
+        self
+    }
+
+    /// Enable a key or button.
+    pub fn key(self, code: u16) -> Self {
+        self.event((EV_KEY as u64, code as u64))
+    }
+
+    /// Enable a relative axis.
+    pub fn rel(self, code: u16) -> Self {
+        self.event((EV_REL as u64, code as u64))
+    }
+
+    /// Enable an absolute axis with the given range.
+    pub fn abs(self, code: u16, min: i32, max: i32) -> Self {
+        self.absinfo(code, min, max, 0, 0)
+    }
+
+    /// Enable an absolute axis with the full absinfo.
+    /// Read UInputUserDevice's fields to learn what these mean.
+    pub fn absinfo(mut self, code: u16, min: i32, max: i32, fuzz: i32, flat: i32) -> Self {
+        let event = (EV_ABS as u64, code as u64);
+        self.device.absinfo(event, min, max, fuzz, flat);
+        self.event(event)
+    }
+
+    /// Set an input property, INPUT_PROP_* from key_codes.
+    pub fn property(mut self, prop: u16) -> Self {
+        self.device.property(prop);
+        self
+    }
+
+    /// Make build() wait up to timeout for the kernel to register the device.
+    /// See Device::wait_until_registered.
+    pub fn wait_registered(mut self, timeout: Duration) -> Self {
+        self.wait_registered = Some(timeout);
+        self
+    }
+
+    /// Check the configuration with UInputUserDevice::validate and create
+    /// the device.
+    pub fn build(self) -> Result<Device> {
+        let mut device = self.device;
+        if let Some(name) = self.name {
+            device.name = name_from_str_with(&name, self.name_policy)?;
+        }
+        device.validate(&self.events)?;
+        let device = Device::new_custom(&self.events, &device)?;
+        if let Some(timeout) = self.wait_registered {
+            device.wait_until_registered(timeout)?;
+        }
+        Ok(device)
+    }
+}
+
+/// Declares a device and creates it through DeviceBuilder.
+/// Evaluates to `std::io::Result<Device>`.
+///
+/// Codes are the constant names from the key_codes module, so a typo is a
+/// compile error instead of a silently missing capability.
+/// Codes are also checked against KEY_MAX/REL_MAX/ABS_MAX, and abs ranges
+/// (min..=max, both ends included like absinfo) are checked to have
+/// min <= max, all at compile time.
+///
+/// Every section after name is optional, but they have to be in this order.
+///
+/// Example:
+/// ```rust,no_run
+/// let device = uinput_rs::device! {
+///     name: "my-tablet",
+///     keys: [BTN_TOUCH, BTN_STYLUS],
+///     rel: [REL_WHEEL],
+///     abs: { ABS_X: 0..=4096, ABS_Y: 0..=4096, ABS_PRESSURE: 0..=255 },
+/// }
+/// .unwrap();
+/// ```
+///
+/// This doesn't compile because the range is backwards:
+/// ```rust,compile_fail
+/// let device = uinput_rs::device! {
+///     name: "broken",
+///     abs: { ABS_X: 4096..=0 },
+/// };
+/// ```
+///
+/// And neither does this, ABS_MT_SLOT is way past REL_MAX:
+/// ```rust,compile_fail
+/// let device = uinput_rs::device! {
+///     name: "broken",
+///     rel: [ABS_MT_SLOT],
+/// };
+/// ```
+#[macro_export]
+macro_rules! device {
+    (
+        name: $name:expr
+        $(, keys: [$($key:ident),* $(,)?])?
+        $(, rel: [$($rel:ident),* $(,)?])?
+        $(, abs: { $($abs:ident : $min:literal ..= $max:literal),* $(,)? })?
+        $(,)?
+    ) => {{
+        #[allow(unused_mut)]
+        let mut builder = $crate::DeviceBuilder::new().name($name);
+        $($(
+            const {
+                assert!(
+                    $crate::key_codes::$key <= $crate::key_codes::KEY_MAX,
+                    concat!(stringify!($key), " is not a key code"),
+                )
+            };
+            builder = builder.key($crate::key_codes::$key);
+        )*)?
+        $($(
+            const {
+                assert!(
+                    $crate::key_codes::$rel <= $crate::key_codes::REL_MAX,
+                    concat!(stringify!($rel), " is not a relative axis"),
+                )
+            };
+            builder = builder.rel($crate::key_codes::$rel);
+        )*)?
+        $($(
+            const {
+                assert!(
+                    $crate::key_codes::$abs <= $crate::key_codes::ABS_MAX,
+                    concat!(stringify!($abs), " is not an absolute axis"),
+                );
+                assert!($min <= $max, concat!("empty range for ", stringify!($abs)));
+            };
+            builder = builder.abs($crate::key_codes::$abs, $min, $max);
+        )*)?
+        builder.build()
+    }};
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+
+/// A 2x3 affine matrix over normalized coordinates, laid out like
+/// libinput's LIBINPUT_CALIBRATION_MATRIX: `[a, b, c, d, e, f]` maps
+/// (x, y) in 0.0..=1.0 to (a·x + b·y + c, d·x + e·y + f).
+///
+/// The wrappers run the coordinates you give them through it before
+/// emitting, so if the compositor applies a matrix to the device, inject
+/// through its inverse to land where you meant.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::calibration::Calibration;
+///
+/// // Panel mounted upside down and only covering the left half of the output.
+/// let calibration = Calibration::rotate_180().then(Calibration::scale(0.5, 1.0));
+/// assert_eq!(calibration.apply(0.0, 0.0), (0.5, 1.0));
+/// assert_eq!(calibration.apply(1.0, 1.0), (0.0, 0.0));
+///
+/// let undo = calibration.inverse().unwrap();
+/// assert_eq!(undo.apply(0.5, 1.0), (0.0, 0.0));
+/// assert_eq!(calibration.to_string(), "-0.5 0 0.5 0 -1 1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    matrix: [f32; 6],
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Calibration {
+    /// Leaves coordinates alone.
+    pub const IDENTITY: Self = Self {
+        matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+    };
+
+    /// From the six numbers of a libinput calibration matrix.
+    pub fn from_matrix(matrix: [f32; 6]) -> Self {
+        Self { matrix }
+    }
+
+    /// Parse "a b c d e f", the format of the LIBINPUT_CALIBRATION_MATRIX
+    /// udev property.
+    pub fn parse(matrix: &str) -> Option<Self> {
+        let mut numbers = matrix.split_whitespace().map(str::parse::<f32>);
+        let mut out = [0.0; 6];
+        for slot in &mut out {
+            *slot = numbers.next()?.ok()?;
+        }
+        numbers.next().is_none().then_some(Self::from_matrix(out))
+    }
+
+    /// The six numbers.
+    pub fn matrix(&self) -> [f32; 6] {
+        self.matrix
+    }
+
+    /// Quarter turn clockwise.
+    pub fn rotate_90() -> Self {
+        Self::from_matrix([0.0, -1.0, 1.0, 1.0, 0.0, 0.0])
+    }
+
+    /// Half turn.
+    pub fn rotate_180() -> Self {
+        Self::from_matrix([-1.0, 0.0, 1.0, 0.0, -1.0, 1.0])
+    }
+
+    /// Quarter turn counterclockwise.
+    pub fn rotate_270() -> Self {
+        Self::from_matrix([0.0, 1.0, 0.0, -1.0, 0.0, 1.0])
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal() -> Self {
+        Self::from_matrix([-1.0, 0.0, 1.0, 0.0, 1.0, 0.0])
+    }
+
+    /// Mirror top to bottom.
+    pub fn flip_vertical() -> Self {
+        Self::from_matrix([1.0, 0.0, 0.0, 0.0, -1.0, 1.0])
+    }
+
+    /// Scale towards the top left corner.
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self::from_matrix([x, 0.0, 0.0, 0.0, y, 0.0])
+    }
+
+    /// Shift by a fraction of the range, for letterboxing.
+    pub fn offset(x: f32, y: f32) -> Self {
+        Self::from_matrix([1.0, 0.0, x, 0.0, 1.0, y])
+    }
+
+    /// This, followed by `next`.
+    pub fn then(self, next: Self) -> Self {
+        let [a, b, c, d, e, f] = self.matrix;
+        let [na, nb, nc, nd, ne, nf] = next.matrix;
+        Self::from_matrix([
+            na * a + nb * d,
+            na * b + nb * e,
+            na * c + nb * f + nc,
+            nd * a + ne * d,
+            nd * b + ne * e,
+            nd * c + ne * f + nf,
+        ])
+    }
+
+    /// The matrix undoing this one, None when it squashes everything onto
+    /// a line.
+    pub fn inverse(&self) -> Option<Self> {
+        let [a, b, c, d, e, f] = self.matrix;
+        let det = a * e - b * d;
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+        Some(Self::from_matrix([
+            e / det,
+            -b / det,
+            (b * f - c * e) / det,
+            -d / det,
+            a / det,
+            (c * d - a * f) / det,
+        ]))
+    }
+
+    /// Transform normalized coordinates.
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        let [a, b, c, d, e, f] = self.matrix;
+        (a * x + b * y + c, d * x + e * y + f)
+    }
+
+    /// Transform coordinates in an axis range, clamping the result to it.
+    pub fn apply_in(&self, x: i32, y: i32, x_range: (i32, i32), y_range: (i32, i32)) -> (i32, i32) {
+        let normalize = |value: i32, (min, max): (i32, i32)| match max - min {
+            0 => 0.0,
+            span => (value - min) as f32 / span as f32,
+        };
+        let scale = |value: f32, (min, max): (i32, i32)| {
+            (min as f32 + value * (max - min) as f32)
+                .round()
+                .clamp(min as f32, max as f32) as i32
+        };
+        let (nx, ny) = self.apply(normalize(x, x_range), normalize(y, y_range));
+        (scale(nx, x_range), scale(ny, y_range))
+    }
+}
+
+impl fmt::Display for Calibration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.matrix.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            // -0.0 prints as "-0", which udev rules never use.
+            write!(f, "{}", value + 0.0)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    io::{self, Result},
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::timing::{Clock, Pacing};
+
+/// How often waits look at the token.
+const POLL: Duration = Duration::from_millis(10);
+
+/// A shared stop switch. Clone it into whatever runs for a while
+/// (Keyboard typing, Mouse moves, TouchScreen gestures, Playback, a
+/// Plan) and cancel it from a Stop button, a signal thread or
+/// anything else. Whatever checks it stops between frames, lets go of
+/// everything it was holding and fails with Error::Cancelled.
+///
+/// Example:
+/// ```rust
+/// use std::{thread, time::Duration};
+/// use uinput_rs::{Error, cancel::CancelToken};
+///
+/// let token = CancelToken::new();
+/// let sleeper = token.clone();
+/// let worker = thread::spawn(move || sleeper.sleep(Duration::from_secs(60)));
+/// token.cancel();
+/// let err = worker.join().unwrap().unwrap_err();
+/// assert_eq!(Error::from_io(&err), Some(&Error::Cancelled));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl CancelToken {
+    /// A token that isn't cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn set(&self, cancelled: bool) {
+        let (lock, changed) = &*self.inner;
+        *lock.lock().unwrap_or_else(|err| err.into_inner()) = cancelled;
+        changed.notify_all();
+    }
+
+    /// Stop everything holding a clone.
+    pub fn cancel(&self) {
+        self.set(true);
+    }
+
+    /// Un-cancel, to run the same thing again.
+    pub fn reset(&self) {
+        self.set(false);
+    }
+
+    /// Whether it was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.inner.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Ok, or Error::Cancelled once cancelled.
+    pub fn check(&self) -> Result<()> {
+        match self.is_cancelled() {
+            true => Err(cancelled()),
+            false => Ok(()),
+        }
+    }
+
+    /// Sleep for `duration`, waking up early with Error::Cancelled if
+    /// cancelled.
+    pub fn sleep(&self, duration: Duration) -> Result<()> {
+        let deadline = Instant::now() + duration;
+        let (lock, changed) = &*self.inner;
+        let mut cancelled = lock.lock().unwrap_or_else(|err| err.into_inner());
+        while !*cancelled {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Ok(());
+            }
+            cancelled = changed
+                .wait_timeout(cancelled, left)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+        }
+        Err(self::cancelled())
+    }
+}
+
+pub(crate) fn cancelled() -> io::Error {
+    crate::Error::Cancelled.into()
+}
+
+/// clock.wait_until, a little at a time so cancelling doesn't have to wait
+/// for the whole thing.
+pub(crate) fn wait_until(
+    clock: &impl Clock,
+    offset: Duration,
+    pacing: Pacing,
+    token: Option<&CancelToken>,
+) -> Result<()> {
+    let Some(token) = token else {
+        return clock.wait_until(offset, pacing);
+    };
+    loop {
+        token.check()?;
+        let now = clock.elapsed();
+        if now >= offset {
+            return Ok(());
+        }
+        clock.wait_until(offset.min(now + POLL), pacing)?;
+    }
+}
@@ -0,0 +1,85 @@
+/// Number of u64 words needed for the biggest code range (KEY_CNT = 0x300).
+const WORDS: usize = 0x300 / 64;
+/// EV_MAX + 1
+const TYPES: usize = 0x20;
+
+/// Set of (TYPE, CODE) pairs a device was created with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    bits: Box<[[u64; WORDS]; TYPES]>,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            bits: Box::new([[0; WORDS]; TYPES]),
+        }
+    }
+}
+
+impl std::fmt::Debug for Capabilities {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Capabilities {
+    /// Empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the set from events in the (TYPE, CODE) format.
+    /// Pairs that are out of range are ignored.
+    pub fn from_events(events: &[(u64, u64)]) -> Self {
+        let mut capabilities = Self::new();
+        for &(event_type, code) in events {
+            capabilities.insert(event_type as u16, code as u16);
+        }
+        capabilities
+    }
+
+    /// Add a pair. Returns false if it's out of range.
+    pub fn insert(&mut self, event_type: u16, code: u16) -> bool {
+        let (event_type, code) = (event_type as usize, code as usize);
+        if event_type >= TYPES || code >= WORDS * 64 {
+            return false;
+        }
+        self.bits[event_type][code / 64] |= 1 << (code % 64);
+        true
+    }
+
+    /// The pair was enabled.
+    pub fn contains(&self, event_type: u16, code: u16) -> bool {
+        let (event_type, code) = (event_type as usize, code as usize);
+        event_type < TYPES
+            && code < WORDS * 64
+            && self.bits[event_type][code / 64] & (1 << (code % 64)) != 0
+    }
+
+    /// At least one code of this type was enabled.
+    pub fn has_type(&self, event_type: u16) -> bool {
+        (event_type as usize) < TYPES && self.bits[event_type as usize].iter().any(|&w| w != 0)
+    }
+
+    /// Every enabled pair, sorted by type and then code.
+    pub fn iter(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.bits
+            .iter()
+            .enumerate()
+            .flat_map(|(event_type, words)| {
+                words.iter().enumerate().flat_map(move |(i, &word)| {
+                    (0..64)
+                        .filter(move |bit| word & (1 << bit) != 0)
+                        .map(move |bit| (event_type as u16, (i * 64 + bit) as u16))
+                })
+            })
+    }
+
+    /// Enabled codes of one type, in order.
+    pub fn codes(&self, event_type: u16) -> impl Iterator<Item = u16> + '_ {
+        self.iter()
+            .filter(move |&(t, _)| t == event_type)
+            .map(|(_, code)| code)
+    }
+}
@@ -0,0 +1,158 @@
+use std::{io::Result, sync::Mutex, thread::sleep, time::Duration};
+
+use libc::{input_event, uinput_user_dev};
+
+use crate::{SYN_REPORT, UinputBackend, key_types::EV_SYN};
+
+/// How often ChaosBackend messes with events. Probabilities are 0.0..=1.0
+/// and all default to 0, which passes everything through untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChaosConfig {
+    /// Same seed, same chaos.
+    pub seed: u64,
+    /// Chance of dropping an event.
+    pub drop: f64,
+    /// Chance of sending an event twice.
+    pub duplicate: f64,
+    /// Chance of shuffling the events of a frame.
+    pub reorder: f64,
+    /// Chance of sleeping before a write.
+    pub delay: f64,
+    /// Longest sleep when a write is delayed.
+    pub max_delay: Duration,
+}
+
+/// Wraps a backend and randomly drops, duplicates, reorders within a frame
+/// or delays events. Use it to check how software consuming the device
+/// copes with an imperfect one.
+///
+/// SYN_REPORTs are never dropped or moved, so frames stay frames.
+/// Device setup goes through untouched.
+///
+/// Only available with the `chaos` feature.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{ChaosBackend, ChaosConfig, Device, MockBackend, UInputUserDevice, key_events::KEY_A_EVENT};
+///
+/// let mock = MockBackend::new();
+/// let config = ChaosConfig { seed: 7, drop: 1.0, ..Default::default() };
+/// let device = Device::with_backend(ChaosBackend::new(mock.clone(), config), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap();
+///
+/// device.emit_key_code(KEY_A_EVENT, 1).unwrap();
+/// device.sync().unwrap();
+/// assert_eq!(mock.frames().len(), 1);
+/// assert!(mock.frames()[0].is_empty());
+/// ```
+#[derive(Debug)]
+pub struct ChaosBackend<B> {
+    inner: B,
+    config: ChaosConfig,
+    rng: Mutex<u64>,
+}
+
+impl<B: UinputBackend> ChaosBackend<B> {
+    /// Wrap a backend.
+    pub fn new(inner: B, config: ChaosConfig) -> Self {
+        Self {
+            inner,
+            config,
+            // xorshift gets stuck on 0.
+            rng: Mutex::new(config.seed | 1),
+        }
+    }
+
+    /// The wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Uniform in 0.0..1.0, from xorshift64.
+    fn next(&self) -> f64 {
+        let mut state = self.rng.lock().unwrap_or_else(|err| err.into_inner());
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        (*state >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn chance(&self, probability: f64) -> bool {
+        probability > 0.0 && self.next() < probability
+    }
+
+    fn mangle(&self, events: &[input_event]) -> Vec<input_event> {
+        let mut out = Vec::with_capacity(events.len());
+        let mut frame = Vec::new();
+        for &event in events {
+            if event.type_ == EV_SYN && event.code == SYN_REPORT {
+                self.mangle_frame(&mut frame, &mut out);
+                out.push(event);
+            } else {
+                frame.push(event);
+            }
+        }
+        self.mangle_frame(&mut frame, &mut out);
+        out
+    }
+
+    fn mangle_frame(&self, frame: &mut Vec<input_event>, out: &mut Vec<input_event>) {
+        if frame.len() > 1 && self.chance(self.config.reorder) {
+            // Fisher-Yates
+            for i in (1..frame.len()).rev() {
+                let j = (self.next() * (i + 1) as f64) as usize;
+                frame.swap(i, j.min(i));
+            }
+        }
+        for event in frame.drain(..) {
+            if self.chance(self.config.drop) {
+                continue;
+            }
+            out.push(event);
+            if self.chance(self.config.duplicate) {
+                out.push(event);
+            }
+        }
+    }
+}
+
+impl<B: UinputBackend> UinputBackend for ChaosBackend<B> {
+    fn open() -> Result<Self> {
+        Ok(Self::new(B::open()?, ChaosConfig::default()))
+    }
+
+    unsafe fn ioctl(&self, request: u64, arg: u64) -> Result<()> {
+        unsafe { self.inner.ioctl(request, arg) }
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.inner.read(buf)
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let size = std::mem::size_of::<input_event>();
+        if buf.len() == std::mem::size_of::<uinput_user_dev>() || !buf.len().is_multiple_of(size) {
+            return self.inner.write(buf);
+        }
+
+        let events: Vec<input_event> = buf
+            .chunks_exact(size)
+            .map(|bytes| unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const input_event) })
+            .collect();
+        let events = self.mangle(&events);
+
+        if self.chance(self.config.delay) {
+            sleep(self.config.max_delay.mul_f64(self.next()));
+        }
+        if !events.is_empty() {
+            let bytes = unsafe {
+                std::slice::from_raw_parts(
+                    events.as_ptr() as *const u8,
+                    std::mem::size_of_val(events.as_slice()),
+                )
+            };
+            self.inner.write(bytes)?;
+        }
+        // The caller asked for buf to be written, as far as it knows it was.
+        Ok(buf.len())
+    }
+}
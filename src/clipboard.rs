@@ -0,0 +1,34 @@
+use std::{
+    io::{self, Result, Write},
+    process::{Command, Stdio},
+};
+
+/// Put text into the clipboard of the running session: wl-copy when
+/// WAYLAND_DISPLAY is set, xclip when DISPLAY is. Both stay in the
+/// background serving the text, so it can be pasted once this returns.
+pub fn copy(text: &str) -> Result<()> {
+    let (program, args): (&str, &[&str]) = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-copy", &[])
+    } else if std::env::var_os("DISPLAY").is_some() {
+        ("xclip", &["-selection", "clipboard", "-in"])
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no WAYLAND_DISPLAY or DISPLAY to copy to",
+        ));
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|err| io::Error::new(err.kind(), format!("{program}: {err}")))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{program} failed: {status}")));
+    }
+    Ok(())
+}
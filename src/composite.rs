@@ -0,0 +1,179 @@
+use std::io::Result;
+
+use libc::input_id;
+
+use crate::{
+    Device, KernelBackend, UInputUserDevice, UinputBackend, key_codes::BUS_USB, name_from_str, phys,
+};
+
+struct Node {
+    suffix: String,
+    events: Vec<(u64, u64)>,
+    info: UInputUserDevice,
+}
+
+/// Builds a CompositeDevice node by node.
+pub struct CompositeBuilder {
+    name: String,
+    id: input_id,
+    controller: String,
+    port: String,
+    nodes: Vec<Node>,
+}
+
+impl CompositeBuilder {
+    /// Add a node named "<name> <suffix>", like "Keyboard", "Mouse" or
+    /// "Consumer Control" the way the kernel's HID driver names them. An
+    /// empty suffix gives the node the plain name.
+    pub fn node(self, suffix: &str, events: &[(u64, u64)]) -> Self {
+        self.node_with(suffix, events, UInputUserDevice::default())
+    }
+
+    /// Same as node, with axis ranges and the like from `info`. Its name,
+    /// id and phys are replaced with the composite's.
+    pub fn node_with(
+        mut self,
+        suffix: &str,
+        events: &[(u64, u64)],
+        info: UInputUserDevice,
+    ) -> Self {
+        self.nodes.push(Node {
+            suffix: suffix.to_owned(),
+            events: events.to_vec(),
+            info,
+        });
+        self
+    }
+
+    /// USB controller and hub port the nodes' phys strings point at, by
+    /// default "0000:00:14.0" and "1". Node n gets interface n.
+    pub fn usb_port(mut self, controller: &str, port: &str) -> Self {
+        self.controller = controller.to_owned();
+        self.port = port.to_owned();
+        self
+    }
+
+    /// Create every node through /dev/uinput.
+    pub fn build(self) -> Result<CompositeDevice> {
+        self.build_with(KernelBackend::open)
+    }
+
+    /// Create every node on a backend from `backend`. If one fails, the
+    /// ones already created are destroyed again.
+    pub fn build_with<B: UinputBackend + 'static>(
+        self,
+        mut backend: impl FnMut() -> Result<B>,
+    ) -> Result<CompositeDevice> {
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        for (interface, node) in self.nodes.into_iter().enumerate() {
+            let mut info = node.info;
+            info.name = match node.suffix.as_str() {
+                "" => name_from_str(&self.name)?,
+                suffix => name_from_str(&format!("{} {suffix}", self.name))?,
+            };
+            info.id = self.id;
+            info.phys = match self.id.bustype {
+                BUS_USB => phys::usb(&self.controller, &self.port, interface as u8),
+                bustype => phys::for_bus(bustype, interface as u8),
+            };
+            info.validate(&node.events)?;
+            let device = Device::with_backend(backend()?, &node.events, &info)?;
+            nodes.push((node.suffix, device));
+        }
+        Ok(CompositeDevice { nodes })
+    }
+}
+
+/// Several uinput nodes sharing one vendor, product and version with
+/// related phys strings, like a wireless receiver that shows up as a
+/// keyboard, a mouse and a consumer control device. The nodes are created
+/// together and destroyed together.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     MockBackend, input_id,
+///     composite::CompositeDevice,
+///     devices::MOUSE_EVENTS,
+///     key_codes::{BUS_USB, KEY_A, KEY_VOLUMEUP},
+///     key_events::{KEY_A_EVENT, KEY_VOLUMEUP_EVENT},
+/// };
+///
+/// let id = input_id { bustype: BUS_USB, vendor: 0x046d, product: 0xc52b, version: 0x0111 };
+/// let mock = MockBackend::new();
+/// let receiver = CompositeDevice::builder("Logitech USB Receiver", id)
+///     .usb_port("0000:00:14.0", "2")
+///     .node("", &[KEY_A_EVENT])
+///     .node("Mouse", &MOUSE_EVENTS)
+///     .node("Consumer Control", &[KEY_VOLUMEUP_EVENT])
+///     .build_with(|| Ok(mock.clone()))
+///     .unwrap();
+///
+/// assert_eq!(receiver.len(), 3);
+/// receiver.node("Consumer Control").unwrap().emit(1, KEY_VOLUMEUP, 1).unwrap();
+/// receiver.node("").unwrap().emit(1, KEY_A, 1).unwrap();
+/// receiver.release_all().unwrap();
+/// assert!(receiver.nodes().all(|(_, device)| device.pressed_keys().is_empty()));
+/// assert_eq!(mock.phys().unwrap(), "usb-0000:00:14.0-2/input2");
+/// ```
+#[derive(Debug)]
+pub struct CompositeDevice {
+    nodes: Vec<(String, Device)>,
+}
+
+impl CompositeDevice {
+    /// Start a composite device with a base name and the identity every
+    /// node shares.
+    pub fn builder(name: &str, id: input_id) -> CompositeBuilder {
+        CompositeBuilder {
+            name: name.to_owned(),
+            id,
+            controller: "0000:00:14.0".to_owned(),
+            port: "1".to_owned(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// A node by its suffix.
+    pub fn node(&self, suffix: &str) -> Option<&Device> {
+        self.nodes
+            .iter()
+            .find(|(name, _)| name == suffix)
+            .map(|(_, device)| device)
+    }
+
+    /// Every node with its suffix, in the order they were added.
+    pub fn nodes(&self) -> impl Iterator<Item = (&str, &Device)> {
+        self.nodes
+            .iter()
+            .map(|(suffix, device)| (suffix.as_str(), device))
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether there are no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Release every held key and button on every node. Keeps going past a
+    /// failing node and returns the first error.
+    pub fn release_all(&self) -> Result<()> {
+        let mut result = Ok(());
+        for (_, device) in &self.nodes {
+            let released = device.release_all();
+            if result.is_ok() {
+                result = released;
+            }
+        }
+        result
+    }
+
+    /// Take the nodes apart.
+    pub fn into_nodes(self) -> Vec<(String, Device)> {
+        self.nodes
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    fmt::{self, Write as _},
+    io::{self, BufRead, BufReader, Read, Result, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{Device, DeviceStats};
+
+/// How long a scraper gets to send its request or take the response.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Source = Box<dyn Fn() -> DeviceStats + Send + Sync>;
+
+/// Name, help text and value of a per-device counter.
+type Counter = (&'static str, &'static str, fn(&DeviceStats) -> u64);
+
+/// Per-device counters and service gauges in the Prometheus text format.
+///
+/// Example:
+/// ```rust
+/// use std::sync::Arc;
+/// use uinput_rs::{Device, MockBackend, UInputUserDevice, daemon::metrics::Metrics, key_codes::KEY_A, key_events::KEY_A_EVENT, key_types::EV_KEY};
+///
+/// let device = Arc::new(Device::with_backend(MockBackend::new(), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap());
+/// let metrics = Metrics::new();
+/// metrics.add_device("keyboard", device.clone());
+/// metrics.set_clients(2);
+///
+/// device.emit(EV_KEY, KEY_A, 1).unwrap();
+/// device.sync().unwrap();
+/// let text = metrics.render();
+/// assert!(text.contains("uinput_events_total{device=\"keyboard\"} 2\n"));
+/// assert!(text.contains("uinput_connected_clients 2\n"));
+/// ```
+#[derive(Default)]
+pub struct Metrics {
+    sources: Mutex<Vec<(String, Source)>>,
+    clients: AtomicU64,
+    queue_depth: AtomicU64,
+    /// Events of each device at the previous render, for events/sec.
+    last: Mutex<Vec<(Instant, u64)>>,
+}
+
+impl fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = self
+            .sources
+            .lock()
+            .map(|sources| sources.iter().map(|(name, _)| name.clone()).collect())
+            .unwrap_or_default();
+        f.debug_struct("Metrics")
+            .field("devices", &names)
+            .field("clients", &self.clients)
+            .field("queue_depth", &self.queue_depth)
+            .finish()
+    }
+}
+
+impl Metrics {
+    /// Nothing to report yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report a device under `name`.
+    pub fn add_device(&self, name: &str, device: Arc<Device>) {
+        self.add_source(name, move || device.stats());
+    }
+
+    /// Report stats from somewhere else, like a device owned by a
+    /// ydotool::Server.
+    pub fn add_source(&self, name: &str, stats: impl Fn() -> DeviceStats + Send + Sync + 'static) {
+        self.sources
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push((name.to_owned(), Box::new(stats)));
+    }
+
+    /// Set the number of connected clients.
+    pub fn set_clients(&self, clients: u64) {
+        self.clients.store(clients, Ordering::Relaxed);
+    }
+
+    /// Set how many events wait to be written.
+    pub fn set_queue_depth(&self, depth: u64) {
+        self.queue_depth.store(depth, Ordering::Relaxed);
+    }
+
+    /// Everything in the Prometheus text format. Events per second are
+    /// measured since the previous render.
+    pub fn render(&self) -> String {
+        let sources = self.sources.lock().unwrap_or_else(|err| err.into_inner());
+        let mut last = self.last.lock().unwrap_or_else(|err| err.into_inner());
+        let now = Instant::now();
+        let stats: Vec<(String, DeviceStats)> = sources
+            .iter()
+            .map(|(name, stats)| (escape(name), stats()))
+            .collect();
+        let rates: Vec<f64> = stats
+            .iter()
+            .enumerate()
+            .map(|(i, (_, stats))| match last.get(i) {
+                Some(&(at, events)) if now > at => {
+                    stats.events.saturating_sub(events) as f64 / (now - at).as_secs_f64()
+                }
+                _ => 0.0,
+            })
+            .collect();
+        *last = stats.iter().map(|(_, stats)| (now, stats.events)).collect();
+
+        let mut out = String::new();
+        let counters: [Counter; 5] = [
+            (
+                "uinput_events_total",
+                "Events written, SYN included.",
+                |s| s.events,
+            ),
+            ("uinput_frames_total", "SYN_REPORT frames written.", |s| {
+                s.frames
+            }),
+            ("uinput_bytes_total", "Bytes written to uinput.", |s| {
+                s.bytes
+            }),
+            ("uinput_write_errors_total", "Failed writes.", |s| {
+                s.write_errors
+            }),
+            (
+                "uinput_dropped_total",
+                "Events dropped by the retry policy.",
+                |s| s.dropped,
+            ),
+        ];
+        for (metric, help, value) in counters {
+            let _ = writeln!(out, "# HELP {metric} {help}\n# TYPE {metric} counter");
+            for (name, stats) in &stats {
+                let _ = writeln!(out, "{metric}{{device=\"{name}\"}} {}", value(stats));
+            }
+        }
+        let _ = writeln!(
+            out,
+            "# HELP uinput_events_per_second Events written per second since the last scrape.\n# TYPE uinput_events_per_second gauge"
+        );
+        for ((name, _), rate) in stats.iter().zip(rates) {
+            let _ = writeln!(out, "uinput_events_per_second{{device=\"{name}\"}} {rate}");
+        }
+        let gauges = [
+            (
+                "uinput_connected_clients",
+                "Clients connected to the daemon.",
+                &self.clients,
+            ),
+            (
+                "uinput_queue_depth",
+                "Events waiting to be written.",
+                &self.queue_depth,
+            ),
+        ];
+        for (metric, help, value) in gauges {
+            let _ = writeln!(
+                out,
+                "# HELP {metric} {help}\n# TYPE {metric} gauge\n{metric} {}",
+                value.load(Ordering::Relaxed)
+            );
+        }
+        out
+    }
+}
+
+/// Label values escape backslashes, quotes and newlines.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// A tiny HTTP server answering GET /metrics.
+///
+/// Example:
+/// ```rust
+/// use std::{io::{Read, Write}, net::TcpStream, sync::Arc};
+/// use uinput_rs::daemon::metrics::{Metrics, MetricsServer};
+///
+/// let metrics = Arc::new(Metrics::new());
+/// metrics.set_queue_depth(7);
+/// let server = MetricsServer::bind("127.0.0.1:0", metrics).unwrap();
+/// let addr = server.local_addr().unwrap();
+/// let handle = std::thread::spawn(move || server.serve_once());
+///
+/// let mut client = TcpStream::connect(addr).unwrap();
+/// client.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+/// let mut response = String::new();
+/// client.read_to_string(&mut response).unwrap();
+/// handle.join().unwrap().unwrap();
+/// assert!(response.starts_with("HTTP/1.1 200 OK"));
+/// assert!(response.contains("uinput_queue_depth 7"));
+/// ```
+#[derive(Debug)]
+pub struct MetricsServer {
+    listener: TcpListener,
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsServer {
+    /// Listen on `addr`, like "127.0.0.1:9464".
+    pub fn bind(addr: impl ToSocketAddrs, metrics: Arc<Metrics>) -> Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            metrics,
+        })
+    }
+
+    /// Where it listens, handy after binding port 0.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Answer one connection.
+    pub fn serve_once(&self) -> Result<()> {
+        let (stream, _) = self.listener.accept()?;
+        self.respond(stream)
+    }
+
+    /// Answer connections forever, one at a time. Returns only on accept
+    /// errors, a misbehaving scraper is logged (with the log feature) and
+    /// skipped. One that goes quiet is dropped after a few seconds.
+    pub fn serve(&self) -> Result<()> {
+        loop {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if let Err(_err) = self.respond(stream) {
+                #[cfg(feature = "log")]
+                log::warn!("metrics: {_err}");
+            }
+        }
+    }
+
+    fn respond(&self, mut stream: TcpStream) -> Result<()> {
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?.take(8192));
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // Skip the headers, nothing in them matters here.
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 2 {
+            line.clear();
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let (status, body) = match (parts.next(), parts.next()) {
+            (Some("GET"), Some("/metrics")) => ("200 OK", self.metrics.render()),
+            (Some("GET"), _) => ("404 Not Found", "not found\n".to_owned()),
+            _ => ("405 Method Not Allowed", "only GET\n".to_owned()),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+}
@@ -0,0 +1,8 @@
+//! Building blocks for running an input injection daemon.
+
+/// Prometheus /metrics endpoint for device stats
+pub mod metrics;
+/// systemd socket activation, readiness and watchdog
+pub mod systemd;
+/// ydotoold compatible socket server
+pub mod ydotool;
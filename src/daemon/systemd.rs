@@ -0,0 +1,132 @@
+use std::{
+    env,
+    io::{self, Result},
+    os::{
+        fd::{FromRawFd, OwnedFd},
+        linux::net::SocketAddrExt,
+        unix::net::{SocketAddr, UnixDatagram},
+    },
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+/// First fd systemd passes, SD_LISTEN_FDS_START.
+const LISTEN_FDS_START: i32 = 3;
+
+/// Set once listen_fds has handed the fds out.
+static LISTEN_FDS_TAKEN: AtomicBool = AtomicBool::new(false);
+
+fn for_this_process(pid_var: &str) -> bool {
+    env::var(pid_var)
+        .ok()
+        .and_then(|pid| pid.parse::<u32>().ok())
+        .is_some_and(|pid| pid == std::process::id())
+}
+
+/// Sockets passed by systemd socket activation, like sd_listen_fds().
+///
+/// Empty when the process wasn't socket activated or the variables are
+/// meant for another process. The fds get CLOEXEC and are owned by whoever
+/// calls this first, later calls get nothing. The variables are left
+/// alone, changing the environment isn't safe with other threads around,
+/// but children won't match LISTEN_PID anyway.
+///
+/// Example:
+/// ```rust,no_run
+/// use std::os::unix::net::UnixDatagram;
+///
+/// use uinput_rs::daemon::{systemd, ydotool::{self, Server}};
+///
+/// let server = match systemd::listen_fds().pop() {
+///     Some(fd) => Server::from_socket(UnixDatagram::from(fd), ydotool::device().unwrap()),
+///     None => Server::bind(ydotool::default_socket_path(), ydotool::device().unwrap()).unwrap(),
+/// };
+/// systemd::notify_ready().unwrap();
+/// server.serve().unwrap();
+/// ```
+pub fn listen_fds() -> Vec<OwnedFd> {
+    if !for_this_process("LISTEN_PID") || LISTEN_FDS_TAKEN.swap(true, Ordering::SeqCst) {
+        return Vec::new();
+    }
+    let count = env::var("LISTEN_FDS")
+        .ok()
+        .and_then(|count| count.parse::<i32>().ok())
+        .unwrap_or(0);
+
+    (LISTEN_FDS_START..LISTEN_FDS_START.saturating_add(count))
+        .filter(|&fd| unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == 0)
+        .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) })
+        .collect()
+}
+
+/// Send a raw sd_notify state, e.g. "READY=1" or "STATUS=...".
+/// Returns false when not running under a notify-type unit.
+pub fn notify(state: &str) -> Result<bool> {
+    let Some(path) = env::var_os("NOTIFY_SOCKET") else {
+        return Ok(false);
+    };
+    let path = path.into_encoded_bytes();
+    let address = match path.strip_prefix(b"@") {
+        Some(name) => SocketAddr::from_abstract_name(name)?,
+        None => SocketAddr::from_pathname(std::str::from_utf8(&path).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "NOTIFY_SOCKET is not UTF-8")
+        })?)?,
+    };
+    let socket = UnixDatagram::unbound()?;
+    socket.send_to_addr(state.as_bytes(), &address)?;
+    Ok(true)
+}
+
+/// Tell systemd the service is up. Use after the device is created and
+/// the socket is listening.
+pub fn notify_ready() -> Result<bool> {
+    notify("READY=1")
+}
+
+/// Tell systemd the service is stopping.
+pub fn notify_stopping() -> Result<bool> {
+    notify("STOPPING=1")
+}
+
+/// Free form status shown by `systemctl status`.
+pub fn notify_status(status: &str) -> Result<bool> {
+    notify(&format!("STATUS={status}"))
+}
+
+/// Ping the watchdog.
+pub fn notify_watchdog() -> Result<bool> {
+    notify("WATCHDOG=1")
+}
+
+/// WatchdogSec of the unit, if it has one and it's meant for this process.
+/// Ping at least every half of this.
+pub fn watchdog_interval() -> Option<Duration> {
+    if env::var_os("WATCHDOG_PID").is_some() && !for_this_process("WATCHDOG_PID") {
+        return None;
+    }
+    let usec = env::var("WATCHDOG_USEC").ok()?.parse::<u64>().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec))
+}
+
+/// Ping the watchdog from a background thread at half the interval, for
+/// as long as alive returns true. Returns None when there's no watchdog.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::daemon::systemd;
+///
+/// let _pinger = systemd::spawn_watchdog(|| true);
+/// ```
+pub fn spawn_watchdog(
+    alive: impl Fn() -> bool + Send + 'static,
+) -> Option<std::thread::JoinHandle<()>> {
+    let interval = watchdog_interval()? / 2;
+    Some(std::thread::spawn(move || {
+        while alive() {
+            if notify_watchdog().is_err() {
+                return;
+            }
+            std::thread::sleep(interval);
+        }
+    }))
+}
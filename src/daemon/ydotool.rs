@@ -0,0 +1,165 @@
+use std::{
+    env, fs,
+    io::{self, Result},
+    os::unix::{fs::PermissionsExt, net::UnixDatagram},
+    path::{Path, PathBuf},
+};
+
+use libc::input_event;
+
+use crate::{
+    Device, DeviceBuilder,
+    key_codes::{KEY_ESC, KEY_MAX, REL_HWHEEL, REL_WHEEL, REL_X, REL_Y},
+};
+
+/// Where ydotool clients look for the daemon: $YDOTOOL_SOCKET, then
+/// $XDG_RUNTIME_DIR/.ydotool_socket, then /tmp/.ydotool_socket.
+pub fn default_socket_path() -> PathBuf {
+    if let Some(path) = env::var_os("YDOTOOL_SOCKET") {
+        return path.into();
+    }
+    env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join(".ydotool_socket")
+}
+
+/// A device with what ydotoold enables: every key and button, pointer
+/// motion and both wheels.
+pub fn device() -> Result<Device> {
+    (KEY_ESC..KEY_MAX)
+        .fold(
+            DeviceBuilder::new().name("ydotoold virtual device"),
+            |builder, key| builder.key(key),
+        )
+        .rel(REL_X)
+        .rel(REL_Y)
+        .rel(REL_WHEEL)
+        .rel(REL_HWHEEL)
+        .build()
+}
+
+/// Speaks the ydotool 1.x protocol, so existing ydotool clients and
+/// scripts can drive a device created with this crate.
+///
+/// The protocol is a unix datagram socket where each datagram is one or
+/// more raw input_events, written to the device as they are.
+///
+/// Example:
+/// ```rust
+/// use std::os::unix::net::UnixDatagram;
+///
+/// use uinput_rs::{Device, MockBackend, UInputUserDevice, daemon::ydotool::Server, key_events::KEY_A_EVENT};
+///
+/// let mock = MockBackend::new();
+/// let device = Device::with_backend(mock.clone(), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap();
+/// let path = std::env::temp_dir().join(format!("ydotool-doctest-{}", std::process::id()));
+/// let server = Server::bind(&path, device).unwrap();
+///
+/// // What `ydotool key 30:1` sends.
+/// let press = [1u16.to_ne_bytes(), 30u16.to_ne_bytes()].concat();
+/// let mut event = vec![0u8; std::mem::size_of::<libc::timeval>()];
+/// event.extend(press);
+/// event.extend(1i32.to_ne_bytes());
+/// UnixDatagram::unbound().unwrap().send_to(&event, &path).unwrap();
+///
+/// server.serve_once().unwrap();
+/// assert_eq!(mock.events()[0].value, 1);
+/// ```
+#[derive(Debug)]
+pub struct Server {
+    socket: UnixDatagram,
+    path: Option<PathBuf>,
+    device: Device,
+}
+
+impl Server {
+    /// Bind the socket at path, replacing a stale one, with 0600 permissions
+    /// like ydotoold's default.
+    pub fn bind(path: impl AsRef<Path>, device: Device) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        match fs::remove_file(&path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        let socket = UnixDatagram::bind(&path)?;
+        let server = Self {
+            socket,
+            path: Some(path),
+            device,
+        };
+        server.set_permissions(0o600)?;
+        Ok(server)
+    }
+
+    /// Serve on an already bound socket, e.g. one from systemd socket
+    /// activation (see daemon::systemd::listen_fds). The socket file is
+    /// left alone on drop.
+    pub fn from_socket(socket: UnixDatagram, device: Device) -> Self {
+        Self {
+            socket,
+            path: None,
+            device,
+        }
+    }
+
+    /// Change who can talk to the daemon, like ydotoold's --socket-perm.
+    /// Does nothing for sockets given to from_socket.
+    pub fn set_permissions(&self, mode: u32) -> Result<()> {
+        match &self.path {
+            Some(path) => fs::set_permissions(path, fs::Permissions::from_mode(mode)),
+            None => Ok(()),
+        }
+    }
+
+    /// The device events are written to.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Wait for one datagram and write its events.
+    /// Datagrams that aren't whole input_events are ignored.
+    pub fn serve_once(&self) -> Result<()> {
+        let events = self.recv()?;
+        self.device.emit_batch(&events)
+    }
+
+    /// Serve forever. Returns only on socket errors; write errors are
+    /// logged (with the log feature) and skipped, like ydotoold does.
+    pub fn serve(&self) -> Result<()> {
+        loop {
+            let events = match self.recv() {
+                Ok(events) => events,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if let Err(_err) = self.device.emit_batch(&events) {
+                #[cfg(feature = "log")]
+                log::warn!("ydotool: {_err}");
+            }
+        }
+    }
+
+    fn recv(&self) -> Result<Vec<input_event>> {
+        let size = std::mem::size_of::<input_event>();
+        let mut buf = [0u8; 64 * std::mem::size_of::<input_event>()];
+        let len = self.socket.recv(&mut buf)?;
+        if !len.is_multiple_of(size) {
+            #[cfg(feature = "log")]
+            log::warn!("ydotool: ignoring a {len} byte datagram");
+            return Ok(Vec::new());
+        }
+        Ok(buf[..len]
+            .chunks_exact(size)
+            .map(|bytes| unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const input_event) })
+            .collect())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
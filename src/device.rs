@@ -1,14 +1,35 @@
-use libc::{input_event, input_id, timeval, uinput_user_dev};
+use libc::{input_absinfo, input_event, input_id, timeval, uinput_abs_setup, uinput_user_dev};
 
 // Cross-reference this with other implementations
 use std::{
     ffi::CString,
-    fs::{File, OpenOptions},
     io::{self, Result},
-    os::fd::{AsRawFd, RawFd},
+    path::PathBuf,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU16, Ordering},
+    },
+    thread::sleep,
+    time::{Duration, Instant},
 };
 
-use crate::key_types::*;
+use crate::{
+    Error, LockState,
+    backend::{KernelBackend, UinputBackend},
+    capabilities::Capabilities,
+    hooks::{HookId, Hooks},
+    hygiene::SynHygiene,
+    key_codes::{
+        ABS_MAX, ABS_MT_POSITION_X, ABS_MT_POSITION_Y, ABS_X, ABS_Y, INPUT_PROP_MAX, KEY_CAPSLOCK,
+        KEY_MAX, KEY_NUMLOCK, KEY_SCROLLLOCK, LED_MAX, REL_MAX,
+    },
+    key_types::*,
+    keystate::{AbsState, DuplicatePolicy, KeyState, StateSnapshot},
+    retry::{Outcome, RetryPolicy},
+    stats::{DeviceStats, StatsRecorder},
+    timing::Pacing,
+    wheel::WheelPairing,
+};
 
 // These constants come from <linux/uinput.h>
 pub const UI_SET_EVBIT: u64 = 0x40045564;
@@ -19,39 +40,39 @@ pub const UI_SET_MSCBIT: u64 = 0x40045568;
 pub const UI_SET_LEDBIT: u64 = 0x40045569;
 pub const UI_SET_SNDBIT: u64 = 0x4004556A;
 pub const UI_SET_FFBIT: u64 = 0x4004556B;
-pub const UI_SET_PHYS: u64 = 0x4004556C;
+// _IOW('U', 108, char *), so the size is the pointer's.
+pub const UI_SET_PHYS: u64 = 0x4000556C | (std::mem::size_of::<*const libc::c_char>() as u64) << 16;
 pub const UI_SET_SWBIT: u64 = 0x4004556D;
+pub const UI_SET_PROPBIT: u64 = 0x4004556E;
 
 // For absolute axes setup (ABS ranges: min/max/etc.)
-pub const UI_ABS_SETUP: u64 = 0x401855CB;
+// _IOW('U', 4, struct uinput_abs_setup), 28 bytes.
+pub const UI_ABS_SETUP: u64 = 0x401C5504;
+
+/// _IOC(_IOC_READ, 'U', 44, len). Add the buffer length shifted left by 16.
+pub const UI_GET_SYSNAME_BASE: u64 = 0x8000552C;
 
 pub const UI_DEV_CREATE: u64 = 0x5501;
 pub const UI_DEV_DESTROY: u64 = 0x5502;
 
-pub const SYN_REPORT: u16 = 0;
-
-fn ioctl(fd: RawFd, req: u64, arg: u64) -> Result<()> {
-    let ret = unsafe { libc::ioctl(fd, req, arg) };
-    if ret < 0 {
-        Err(io::Error::last_os_error())
-    } else {
-        Ok(())
-    }
-}
+// Force feedback uploads and erases, _IOWR/_IOW('U', 200..203, ...). The
+// structs hold a pointer, so their size depends on the target.
+pub const UI_BEGIN_FF_UPLOAD: u64 =
+    0xC00055C8 | (std::mem::size_of::<libc::uinput_ff_upload>() as u64) << 16;
+pub const UI_END_FF_UPLOAD: u64 =
+    0x400055C9 | (std::mem::size_of::<libc::uinput_ff_upload>() as u64) << 16;
+pub const UI_BEGIN_FF_ERASE: u64 =
+    0xC00055CA | (std::mem::size_of::<libc::uinput_ff_erase>() as u64) << 16;
+pub const UI_END_FF_ERASE: u64 =
+    0x400055CB | (std::mem::size_of::<libc::uinput_ff_erase>() as u64) << 16;
 
-/// Open the device writer
-fn open_uinput() -> Result<File> {
-    OpenOptions::new()
-        .read(true)
-        .write(true)
-        .open("/dev/uinput")
-}
+pub const SYN_REPORT: u16 = 0;
 
 /// Enable specific key for the device.
 /// event_type: Event type. It's a really small number.
 /// You can use the constants here EV_*
-fn enable_key(fd: RawFd, event_type: u64, key: u64) -> Result<()> {
-    ioctl(fd, UI_SET_EVBIT, event_type)?;
+fn enable_key(backend: &dyn UinputBackend, event_type: u64, key: u64) -> Result<()> {
+    unsafe { backend.ioctl(UI_SET_EVBIT, event_type) }?;
 
     let req = match event_type as u16 {
         EV_KEY => UI_SET_KEYBIT,
@@ -69,20 +90,62 @@ fn enable_key(fd: RawFd, event_type: u64, key: u64) -> Result<()> {
             ));
         }
     };
-    ioctl(fd, req, key)
+    unsafe { backend.ioctl(req, key) }
 }
 
-fn write_device(fd: RawFd, device: &UInputUserDevice) {
+fn write_device(backend: &dyn UinputBackend, device: &UInputUserDevice) -> Result<()> {
     let device = device.as_uinput_user_dev();
-    unsafe {
-        let ptr = &device as *const _ as *const u8;
-        let size = std::mem::size_of::<uinput_user_dev>();
-        libc::write(fd, ptr as *const _, size);
+    let bytes = unsafe {
+        std::slice::from_raw_parts(
+            &device as *const uinput_user_dev as *const u8,
+            std::mem::size_of::<uinput_user_dev>(),
+        )
+    };
+    write_all(backend, bytes)
+}
+
+/// uinput_user_dev has no room for resolution, so axes that have one get
+/// their full absinfo again through UI_ABS_SETUP.
+fn set_resolutions(
+    backend: &dyn UinputBackend,
+    events: &[(u64, u64)],
+    device: &UInputUserDevice,
+) -> Result<()> {
+    for &(event_type, code) in events {
+        let i = code as usize;
+        if event_type as u16 != EV_ABS || i >= device.absres.len() || device.absres[i] == 0 {
+            continue;
+        }
+        let setup = uinput_abs_setup {
+            code: code as u16,
+            absinfo: input_absinfo {
+                value: 0,
+                minimum: device.absmin[i],
+                maximum: device.absmax[i],
+                fuzz: device.absfuzz[i],
+                flat: device.absflat[i],
+                resolution: device.absres[i],
+            },
+        };
+        unsafe { backend.ioctl(UI_ABS_SETUP, &setup as *const uinput_abs_setup as u64) }?;
     }
+    Ok(())
 }
 
-fn send_event(fd: RawFd, event_type: u16, code: u16, value: i32) -> Result<()> {
-    let ev = input_event {
+fn write_all(backend: &dyn UinputBackend, bytes: &[u8]) -> Result<()> {
+    let written = backend.write(bytes)?;
+    if written == bytes.len() {
+        Ok(())
+    } else {
+        // Extremely unlikely for a char device, but still correct handling.
+        // A bare kind keeps this path free of allocations too.
+        Err(io::ErrorKind::WriteZero.into())
+    }
+}
+
+/// Builds an input_event with zeroed time. The kernel fills in the timestamp.
+pub(crate) fn new_event(event_type: u16, code: u16, value: i32) -> input_event {
+    input_event {
         time: timeval {
             tv_sec: 0,
             tv_usec: 0,
@@ -90,26 +153,19 @@ fn send_event(fd: RawFd, event_type: u16, code: u16, value: i32) -> Result<()> {
         type_: event_type,
         code,
         value,
-    };
-
-    send_event_custom(fd, ev)
+    }
 }
 
-fn send_event_custom(fd: RawFd, event: input_event) -> Result<()> {
-    let size = std::mem::size_of::<input_event>();
-    let ptr = &event as *const input_event as *const _;
-    let written = unsafe { libc::write(fd, ptr, std::mem::size_of::<input_event>()) };
-    if written == size as isize {
-        Ok(())
-    } else if written >= 0 {
-        // Extremely unlikely for a char device, but still correct handling.
-        Err(io::Error::new(
-            io::ErrorKind::WriteZero,
-            format!("partial write: {written} / {size} bytes"),
-        ))
-    } else {
-        Err(io::Error::last_os_error())
+/// Writes all events with a single write call.
+fn send_events(backend: &dyn UinputBackend, events: &[input_event]) -> Result<()> {
+    if events.is_empty() {
+        return Ok(());
     }
+
+    let bytes = unsafe {
+        std::slice::from_raw_parts(events.as_ptr() as *const u8, std::mem::size_of_val(events))
+    };
+    write_all(backend, bytes)
 }
 
 /// Create a name for a device with this one.
@@ -139,6 +195,61 @@ pub fn name_from_str(name: &str) -> Result<[i8; 80]> {
     Ok(name_list)
 }
 
+/// How name_from_str_with deals with names that don't fit uinput.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NamePolicy {
+    /// Fail on names that are too long or contain a null byte.
+    #[default]
+    Strict,
+    /// Cut names that are too long at a character boundary.
+    /// A null byte is still an error.
+    Truncate,
+    /// Replace null bytes and control characters with '_' and truncate.
+    /// Never fails.
+    ReplaceInvalid,
+}
+
+/// Same as name_from_str but with a choice of what to do with bad names.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{NamePolicy, name_from_str_with};
+///
+/// let long = "Logitech USB Receiver Mouse (virtual clone) ".repeat(3);
+/// assert!(name_from_str_with(&long, NamePolicy::Strict).is_err());
+/// assert!(name_from_str_with(&long, NamePolicy::Truncate).is_ok());
+/// ```
+pub fn name_from_str_with(name: &str, policy: NamePolicy) -> Result<[i8; 80]> {
+    match policy {
+        NamePolicy::Strict => name_from_str(name),
+        NamePolicy::Truncate => name_from_str(truncate(name, 79)),
+        NamePolicy::ReplaceInvalid => Ok(name_from_str_lossy(name)),
+    }
+}
+
+/// Converts any string to a uinput name. Null bytes and control characters
+/// become '_' and names that are too long get cut.
+/// Handy for user provided or cloned names.
+pub fn name_from_str_lossy(name: &str) -> [i8; 80] {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_control() { '_' } else { c })
+        .collect();
+    name_from_str(truncate(&name, 79)).expect("sanitized name is valid")
+}
+
+/// Longest prefix of name that fits in max bytes without splitting a character.
+fn truncate(name: &str, max: usize) -> &str {
+    if name.len() <= max {
+        return name;
+    }
+    let end = (0..=max)
+        .rev()
+        .find(|&i| name.is_char_boundary(i))
+        .unwrap_or(0);
+    &name[..end]
+}
+
 /// Represents device features.
 /// The only difference this struct has compared to the uinput_user_dev struct is that this one has
 /// default() implemented.
@@ -188,6 +299,22 @@ pub struct UInputUserDevice {
     /// Each value in the list is an axis.
     /// For example first one is X, second Y, third Z etc.
     pub absflat: [i32; 64],
+    /// Resolution in units per millimeter (units per radian for rotation
+    /// axes). 0 means unknown. libinput uses it for the physical size of
+    /// touchpads and tablets, which drives acceleration and palm detection.
+    ///
+    /// Not part of the kernel's uinput_user_dev, axes with a resolution are
+    /// set up with UI_ABS_SETUP.
+    pub absres: [i32; 64],
+    /// Input properties, one bit per INPUT_PROP_* (see key_codes).
+    /// They tell userspace what kind of device this is: INPUT_PROP_DIRECT
+    /// for touchscreens, INPUT_PROP_BUTTONPAD for clickpads and so on.
+    /// Set with UI_SET_PROPBIT.
+    pub properties: u32,
+    /// Physical path, shown as "Phys" in /proc/bus/input/devices and
+    /// ID_PATH-ish rules. Empty leaves it unset. The phys module makes
+    /// realistic ones. Set with UI_SET_PHYS.
+    pub phys: String,
 }
 
 impl UInputUserDevice {
@@ -234,6 +361,121 @@ impl UInputUserDevice {
         self.absflat[i] = absflat;
     }
 
+    /// Set an input property, INPUT_PROP_*.
+    ///
+    /// # Panics
+    /// If prop is past INPUT_PROP_MAX.
+    pub fn property(&mut self, prop: u16) {
+        assert!(prop <= INPUT_PROP_MAX, "unknown input property {prop:#x}");
+        self.properties |= 1 << prop;
+    }
+
+    /// Set the resolution of an axis in units per millimeter.
+    pub fn resolution(&mut self, event: (u64, u64), units_per_mm: i32) {
+        self.absres[event.1 as usize] = units_per_mm;
+    }
+
+    /// Set the resolution of ABS_X/Y and ABS_MT_POSITION_X/Y from the
+    /// surface's physical size and the axes' configured ranges.
+    /// Call it after absinfo.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{UInputUserDevice, key_codes::ABS_X, key_events::{ABS_X_EVENT, ABS_Y_EVENT}};
+    ///
+    /// let mut info = UInputUserDevice::default();
+    /// info.absinfo(ABS_X_EVENT, 0, 4000, 0, 0);
+    /// info.absinfo(ABS_Y_EVENT, 0, 2500, 0, 0);
+    /// info.physical_size(100.0, 62.5);
+    ///
+    /// assert_eq!(info.absres[ABS_X as usize], 40);
+    /// ```
+    pub fn physical_size(&mut self, width_mm: f32, height_mm: f32) {
+        for (code, mm) in [
+            (ABS_X, width_mm),
+            (ABS_Y, height_mm),
+            (ABS_MT_POSITION_X, width_mm),
+            (ABS_MT_POSITION_Y, height_mm),
+        ] {
+            let i = code as usize;
+            let range = self.absmax[i] - self.absmin[i];
+            if range > 0 && mm > 0.0 {
+                self.absres[i] = (range as f32 / mm).round() as i32;
+            }
+        }
+    }
+
+    /// Check this description against the events that will be enabled on it.
+    /// DeviceBuilder runs this before creating the device.
+    ///
+    /// Catches an empty name, codes past KEY_MAX/REL_MAX/ABS_MAX, axes with
+    /// absmin > absmax or negative fuzz/flat, a phys the kernel won't take,
+    /// and ff_effects_max not matching whether EV_FF is enabled. Fails with Error::InvalidDevice.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{Error, UInputUserDevice, key_events::ABS_X_EVENT};
+    ///
+    /// let mut device = UInputUserDevice::default();
+    /// device.absinfo(ABS_X_EVENT, 100, 0, 0, 0);
+    ///
+    /// let err = device.validate(&[ABS_X_EVENT]).unwrap_err();
+    /// assert!(matches!(Error::from_io(&err), Some(Error::InvalidDevice { .. })));
+    /// ```
+    pub fn validate(&self, events: &[(u64, u64)]) -> Result<()> {
+        let invalid =
+            |reason: String| -> Result<()> { Err(Error::InvalidDevice { reason }.into()) };
+
+        if self.name[0] == 0 {
+            return invalid("name is empty".to_owned());
+        }
+
+        let mut has_ff = false;
+        for &(event_type, code) in events {
+            let max = match event_type as u16 {
+                EV_KEY => KEY_MAX,
+                EV_REL => REL_MAX,
+                EV_ABS => ABS_MAX,
+                EV_FF => {
+                    has_ff = true;
+                    continue;
+                }
+                _ => continue,
+            };
+            if code > max as u64 {
+                return invalid(format!(
+                    "code {code:#x} is past the maximum {max:#x} for event type {event_type:#x}"
+                ));
+            }
+            if event_type as u16 != EV_ABS {
+                continue;
+            }
+
+            let i = code as usize;
+            let (min, max) = (self.absmin[i], self.absmax[i]);
+            if min > max {
+                return invalid(format!(
+                    "ABS code {code:#x} has absmin {min} > absmax {max}"
+                ));
+            }
+            if self.absfuzz[i] < 0 || self.absflat[i] < 0 || self.absres[i] < 0 {
+                return invalid(format!(
+                    "ABS code {code:#x} has negative fuzz, flat or resolution"
+                ));
+            }
+        }
+
+        if self.phys.contains('\0') || self.phys.len() >= 1024 {
+            return invalid("phys has a null byte or is 1024 bytes or longer".to_owned());
+        }
+
+        match (has_ff, self.ff_effects_max) {
+            (false, 1..) => invalid("ff_effects_max is set but EV_FF isn't enabled".to_owned()),
+            (true, 0) => invalid("EV_FF is enabled but ff_effects_max is 0".to_owned()),
+            _ => Ok(()),
+        }
+    }
+
     /// Creates a device with name in case you quickly want to create a device.
     /// Panics if name is invalid.
     pub fn with_name(name: &str) -> Self {
@@ -259,14 +501,27 @@ impl Default for UInputUserDevice {
             absmin: [0; 64],
             absfuzz: [0; 64],
             absflat: [0; 64],
+            absres: [0; 64],
+            properties: 0,
+            phys: String::new(),
         }
     }
 }
 
+/// What to do with ABS values outside the configured range in strict mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AbsRangePolicy {
+    /// Pull the value back into range (and log a warning with the log feature).
+    #[default]
+    Clamp,
+    /// Fail with Error::AbsOutOfRange.
+    Reject,
+}
+
 /// Represents a virtual device.
 ///
 /// Example:
-/// ```rust
+/// ```rust,no_run
 /// use std::{thread::sleep, time::Duration};
 ///
 /// use uinput_rs::{
@@ -281,8 +536,8 @@ impl Default for UInputUserDevice {
 /// // Enable the events for the device by passing them.
 /// let device = Device::new(&events).unwrap();
 ///
-/// // Wait for the user space to initialize the device.
-/// sleep(Duration::from_millis(100));
+/// // Wait for the kernel to register the device.
+/// device.wait_until_registered(Duration::from_secs(1)).unwrap();
 ///
 /// for _ in 0..1000 {
 ///     // move to the right
@@ -303,46 +558,266 @@ impl Default for UInputUserDevice {
 /// ```
 #[derive(Debug)]
 pub struct Device {
-    file: File,
+    backend: Box<dyn UinputBackend>,
+    capabilities: Capabilities,
+    strict: bool,
+    abs_range_policy: AbsRangePolicy,
+    info: uinput_user_dev,
+    resolution: [i32; 64],
+    properties: u32,
+    leds: AtomicU16,
+    // Force feedback lock_state read while looking for LEDs, handed out by
+    // the next read_feedback.
+    feedback: Mutex<Vec<input_event>>,
+    // Set by teardown, writes fail and drop doesn't destroy again.
+    destroyed: AtomicBool,
+    syn_hygiene: Option<SynHygiene>,
+    keys: KeyState,
+    abs: AbsState,
+    duplicate_policy: DuplicatePolicy,
+    wheels: Option<WheelPairing>,
+    hooks: Hooks,
+    pacing: Pacing,
+    retry: RetryPolicy,
+    stats: StatsRecorder,
+    #[cfg(feature = "log")]
+    log_sampling: u64,
 }
 
 impl Device {
     /// Create new virtual device with defaults.
     /// Events are in the format: [(TYPE, CODE)]
     pub fn new(events: &[(u64, u64)]) -> Result<Self> {
-        let file = open_uinput()?;
+        Self::new_custom(events, &UInputUserDevice::default())
+    }
+
+    /// Start building a device. Same as DeviceBuilder::new().
+    pub fn builder() -> crate::DeviceBuilder {
+        crate::DeviceBuilder::new()
+    }
+
+    /// Create new device with custom properties.
+    /// Events are in the format: [(TYPE, CODE)]
+    pub fn new_custom(events: &[(u64, u64)], device: &UInputUserDevice) -> Result<Self> {
+        Self::with_backend(KernelBackend::open()?, events, device)
+    }
 
+    /// Create a device on top of a custom backend.
+    /// Use MockBackend to test code that creates devices without /dev/uinput.
+    /// Events are in the format: [(TYPE, CODE)]
+    pub fn with_backend(
+        backend: impl UinputBackend + 'static,
+        events: &[(u64, u64)],
+        device: &UInputUserDevice,
+    ) -> Result<Self> {
+        let backend: Box<dyn UinputBackend> = Box::new(backend);
+        Self::setup(backend.as_ref(), events, device)?;
+        Ok(Self::adopt(backend, events, device))
+    }
+
+    /// Enable the events, describe the device and create it.
+    pub(crate) fn setup(
+        backend: &dyn UinputBackend,
+        events: &[(u64, u64)],
+        device: &UInputUserDevice,
+    ) -> Result<()> {
         for (event_type, key) in events.iter() {
-            enable_key(file.as_raw_fd(), *event_type, *key)?;
+            enable_key(backend, *event_type, *key)?;
+        }
+        for prop in 0..=INPUT_PROP_MAX {
+            if device.properties & (1 << prop) != 0 {
+                unsafe { backend.ioctl(UI_SET_PROPBIT, prop as u64) }?;
+            }
+        }
+        if !device.phys.is_empty() {
+            let phys = CString::new(device.phys.as_str())
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "phys has a null byte"))?;
+            unsafe { backend.ioctl(UI_SET_PHYS, phys.as_ptr() as u64) }?;
         }
 
-        write_device(file.as_raw_fd(), &UInputUserDevice::default());
+        write_device(backend, device)?;
+        set_resolutions(backend, events, device)?;
 
-        ioctl(file.as_raw_fd(), UI_DEV_CREATE, 0)?;
+        unsafe { backend.ioctl(UI_DEV_CREATE, 0) }
+    }
 
-        Ok(Device { file })
+    /// Wrap a backend whose device was already set up and created.
+    pub(crate) fn adopt(
+        backend: Box<dyn UinputBackend>,
+        events: &[(u64, u64)],
+        device: &UInputUserDevice,
+    ) -> Self {
+        let capabilities = Capabilities::from_events(events);
+        Device {
+            backend,
+            wheels: None,
+            capabilities,
+            strict: false,
+            abs_range_policy: AbsRangePolicy::default(),
+            info: device.as_uinput_user_dev(),
+            resolution: device.absres,
+            properties: device.properties,
+            leds: AtomicU16::new(0),
+            feedback: Mutex::default(),
+            destroyed: AtomicBool::new(false),
+            syn_hygiene: None,
+            keys: KeyState::default(),
+            abs: AbsState::default(),
+            duplicate_policy: DuplicatePolicy::default(),
+            hooks: Hooks::default(),
+            pacing: Pacing::default(),
+            retry: RetryPolicy::default(),
+            stats: StatsRecorder::default(),
+            #[cfg(feature = "log")]
+            log_sampling: 1,
+        }
     }
 
-    /// Create new device with custom properties.
-    /// Events are in the format: [(TYPE, CODE)]
-    pub fn new_custom(events: &[(u64, u64)], device: &UInputUserDevice) -> Result<Self> {
-        let file = open_uinput()?;
+    /// The uinput fd, None on backends without one.
+    pub(crate) fn raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        self.backend.raw_fd()
+    }
 
-        for (event_type, key) in events.iter() {
-            enable_key(file.as_raw_fd(), *event_type, *key)?;
+    fn fd_or_unsupported(&self) -> Result<std::os::fd::RawFd> {
+        self.raw_fd().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "the backend has no file descriptor",
+            )
+        })
+    }
+
+    /// Whether the uinput fd is closed on exec. It is unless changed,
+    /// like every fd Rust opens.
+    pub fn cloexec(&self) -> Result<bool> {
+        let flags = unsafe { libc::fcntl(self.fd_or_unsupported()?, libc::F_GETFD) };
+        if flags < 0 {
+            return Err(io::Error::last_os_error());
         }
+        Ok(flags & libc::FD_CLOEXEC != 0)
+    }
 
-        write_device(file.as_raw_fd(), device);
+    /// Close the uinput fd on exec, or let exec'd programs inherit it.
+    /// Inheriting it alone doesn't keep the device alive, dropping this
+    /// Device still destroys it. See persist.
+    pub fn set_cloexec(&self, on: bool) -> Result<()> {
+        let fd = self.fd_or_unsupported()?;
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        if flags < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let flags = match on {
+            true => flags | libc::FD_CLOEXEC,
+            false => flags & !libc::FD_CLOEXEC,
+        };
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, flags) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
 
-        ioctl(file.as_raw_fd(), UI_DEV_CREATE, 0)?;
+    /// Give up the device without destroying it and return the fd. The
+    /// kernel removes the device once the last copy of the fd is closed:
+    /// on exit, on exec if it's still CLOEXEC, or when you close it.
+    ///
+    /// Use this in a forked child that shouldn't take the device down with
+    /// it. Children share the parent's fd, so a Device dropped in the child
+    /// destroys the device for both.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{Device, MockBackend, UInputUserDevice, key_events::KEY_A_EVENT};
+    ///
+    /// let mock = MockBackend::new();
+    /// let device = Device::with_backend(mock.clone(), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap();
+    /// assert!(device.cloexec().is_err()); // the mock has no fd
+    /// assert_eq!(device.leak(), None);
+    /// assert!(!mock.destroyed());
+    /// ```
+    pub fn leak(self) -> Option<std::os::fd::RawFd> {
+        let fd = self.raw_fd();
+        std::mem::forget(self);
+        fd
+    }
 
-        Ok(Device { file })
+    /// Keep the device across exec, for daemons that re-exec themselves
+    /// to upgrade: clears CLOEXEC and leaks the device. Pass the fd to the
+    /// new program (an environment variable or argument) and pick it back
+    /// up with from_persisted_fd. Nothing held is released, the new program
+    /// carries on with the same state.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use std::os::unix::process::CommandExt;
+    /// use uinput_rs::{Device, UInputUserDevice, key_events::KEY_A_EVENT};
+    ///
+    /// let events = [KEY_A_EVENT];
+    /// let info = UInputUserDevice::with_name("upgradable");
+    /// let device = match std::env::var("UINPUT_FD") {
+    ///     // The new binary: the device is still there.
+    ///     Ok(fd) => unsafe { Device::from_persisted_fd(fd.parse().unwrap(), &events, &info) }.unwrap(),
+    ///     Err(_) => Device::new_custom(&events, &info).unwrap(),
+    /// };
+    ///
+    /// // Time to upgrade.
+    /// let fd = device.persist().unwrap();
+    /// let err = std::process::Command::new("/proc/self/exe").env("UINPUT_FD", fd.to_string()).exec();
+    /// panic!("exec failed: {err}");
+    /// ```
+    pub fn persist(self) -> Result<std::os::fd::RawFd> {
+        self.set_cloexec(false)?;
+        let fd = self.fd_or_unsupported()?;
+        std::mem::forget(self);
+        Ok(fd)
+    }
+
+    /// Take over a device that was persisted before exec, or leaked in
+    /// any other way. `events` and `device` should be what it was created
+    /// with, they aren't sent to the kernel again. CLOEXEC is set again.
+    ///
+    /// # Safety
+    /// `fd` has to be an open uinput fd with a created device that nothing
+    /// else owns.
+    pub unsafe fn from_persisted_fd(
+        fd: std::os::fd::RawFd,
+        events: &[(u64, u64)],
+        device: &UInputUserDevice,
+    ) -> Result<Self> {
+        use std::os::fd::{FromRawFd, OwnedFd};
+
+        let file = std::fs::File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+        let device = Self::adopt(Box::new(KernelBackend::from_file(file)), events, device);
+        device.set_cloexec(true)?;
+        Ok(device)
+    }
+
+    /// The events and description this device was created with, as far as
+    /// it remembers them. phys isn't kept.
+    pub(crate) fn description(&self) -> (Vec<(u64, u64)>, UInputUserDevice) {
+        let events = self
+            .capabilities
+            .iter()
+            .map(|(event_type, code)| (event_type as u64, code as u64))
+            .collect();
+        let info = UInputUserDevice {
+            name: self.info.name,
+            id: self.info.id,
+            ff_effects_max: self.info.ff_effects_max,
+            absmax: self.info.absmax,
+            absmin: self.info.absmin,
+            absfuzz: self.info.absfuzz,
+            absflat: self.info.absflat,
+            absres: self.resolution,
+            properties: self.properties,
+            phys: String::new(),
+        };
+        (events, info)
     }
 
     /// Emit a single event.
     /// Remember to call sync to send the events.
     pub fn emit(&self, event_type: u16, code: u16, value: i32) -> Result<()> {
-        send_event(self.file.as_raw_fd(), event_type, code, value)
+        self.emit_custom(new_event(event_type, code, value))
     }
 
     /// Emit an event but ignore the result.
@@ -363,7 +838,188 @@ impl Device {
     /// Emit a custom event by giving in the input_event struct from libc.
     /// Remember to call sync to send the events.
     pub fn emit_custom(&self, event: input_event) -> Result<()> {
-        send_event_custom(self.file.as_raw_fd(), event)
+        self.emit_batch(std::slice::from_ref(&event))
+    }
+
+    /// Emit multiple events with one write.
+    /// Remember to include a SYN_REPORT (or call sync) to send the events.
+    ///
+    /// Every emit function ends up here. With no hooks or strict checks it
+    /// never allocates, tests/alloc.rs makes sure it stays that way.
+    /// `cargo bench` times it on a real device.
+    pub fn emit_batch(&self, events: &[input_event]) -> Result<()> {
+        let hooked = self.hooks.run(events);
+        self.emit_hooked(hooked.as_deref().unwrap_or(events))
+    }
+
+    /// Let hooks that hold events back, like RateLimit, send what's due.
+    /// Call it every so often while they're in use, held events otherwise
+    /// only go out with the next emit.
+    pub fn poll_transformers(&self) -> Result<()> {
+        match self.hooks.poll() {
+            Some(events) => self.emit_hooked(&events),
+            None => Ok(()),
+        }
+    }
+
+    fn emit_hooked(&self, events: &[input_event]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        match &self.wheels {
+            Some(wheels) => wheels.pair(events, |events| self.emit_checked(events)),
+            None => self.emit_checked(events),
+        }
+    }
+
+    fn emit_checked(&self, events: &[input_event]) -> Result<()> {
+        let mut clamped = None;
+        if self.strict {
+            self.check_capabilities(events)?;
+            clamped = self.check_abs_ranges(events)?;
+        }
+        let events = clamped.as_deref().unwrap_or(events);
+
+        if let Some(filtered) = self.check_duplicates(events)? {
+            return self.write_events(&filtered);
+        }
+        self.write_events(events)
+    }
+
+    fn write_events(&self, events: &[input_event]) -> Result<()> {
+        if self.destroyed.load(Ordering::SeqCst) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "the device was destroyed",
+            ));
+        }
+        let backend = self.backend.as_ref();
+        match self.retry.run(|| send_events(backend, events)) {
+            Ok(Outcome::Written) => {
+                #[cfg(feature = "log")]
+                self.log_written(events);
+                self.stats.record_written(events);
+                self.keys.record(events);
+                self.abs.record(events);
+                if let Some(hygiene) = &self.syn_hygiene {
+                    hygiene.observe(events);
+                }
+                Ok(())
+            }
+            Ok(Outcome::Dropped) => {
+                self.stats.record_dropped(events.len());
+                Ok(())
+            }
+            Err(err) => {
+                self.stats.record_error();
+                Err(err)
+            }
+        }
+    }
+
+    fn check_capabilities(&self, events: &[input_event]) -> Result<()> {
+        for event in events {
+            if event.type_ != EV_SYN && !self.capabilities.contains(event.type_, event.code) {
+                return Err(Error::CapabilityNotEnabled {
+                    event_type: event.type_,
+                    code: event.code,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a clamped copy of the events if any ABS value was out of range
+    /// and the policy is Clamp. Axes without a configured range are skipped.
+    fn check_abs_ranges(&self, events: &[input_event]) -> Result<Option<Vec<input_event>>> {
+        let out_of_range = |event: &input_event| {
+            let i = event.code as usize;
+            if event.type_ != EV_ABS || i >= self.info.absmin.len() {
+                return None;
+            }
+            let (min, max) = (self.info.absmin[i], self.info.absmax[i]);
+            let configured = min != 0 || max != 0;
+            (configured && !(min..=max).contains(&event.value)).then_some((min, max))
+        };
+
+        if !events.iter().any(|event| out_of_range(event).is_some()) {
+            return Ok(None);
+        }
+
+        let mut clamped = events.to_vec();
+        for event in clamped.iter_mut() {
+            let Some((min, max)) = out_of_range(event) else {
+                continue;
+            };
+            if self.abs_range_policy == AbsRangePolicy::Reject {
+                return Err(Error::AbsOutOfRange {
+                    code: event.code,
+                    value: event.value,
+                    min,
+                    max,
+                }
+                .into());
+            }
+
+            #[cfg(feature = "log")]
+            log::warn!(
+                "ABS code {:#x} value {} is outside {min}..={max}, clamping",
+                event.code,
+                event.value
+            );
+            event.value = event.value.clamp(min, max);
+        }
+        Ok(Some(clamped))
+    }
+
+    /// Returns the events without duplicate presses and releases if there
+    /// were any and the policy is Ignore.
+    fn check_duplicates(&self, events: &[input_event]) -> Result<Option<Vec<input_event>>> {
+        if self.duplicate_policy == DuplicatePolicy::Pass {
+            return Ok(None);
+        }
+        let Some(first) = (0..events.len()).find(|&i| self.keys.is_duplicate(events, i)) else {
+            return Ok(None);
+        };
+        if self.duplicate_policy == DuplicatePolicy::Reject {
+            return Err(Error::DuplicateKeyState {
+                code: events[first].code,
+                pressed: events[first].value == 1,
+            }
+            .into());
+        }
+
+        let filtered = (0..events.len())
+            .filter(|&i| !self.keys.is_duplicate(events, i))
+            .map(|i| events[i])
+            .collect();
+        Ok(Some(filtered))
+    }
+
+    #[cfg(feature = "log")]
+    fn log_written(&self, events: &[input_event]) {
+        if !log::log_enabled!(target: "uinput_rs::emit", log::Level::Debug) {
+            return;
+        }
+        // Stats are updated after this, so events written so far are the
+        // index of the first event in this batch.
+        let first = self.stats.events();
+        for (i, event) in events.iter().enumerate() {
+            if (first + i as u64).is_multiple_of(self.log_sampling) {
+                log::debug!(target: "uinput_rs::emit", "{}", crate::DisplayEvent(event));
+            }
+        }
+    }
+
+    /// Only log every nth event. 1 (the default) logs everything.
+    /// Handy for high rate devices that would drown the log otherwise.
+    ///
+    /// Events are logged at debug level with the target "uinput_rs::emit".
+    #[cfg(feature = "log")]
+    pub fn set_log_sampling(&mut self, every: u64) {
+        self.log_sampling = every.max(1);
     }
 
     /// Fires all emitted events in queue.
@@ -376,16 +1032,762 @@ impl Device {
         let _ = self.sync();
     }
 
+    /// How many force feedback effects the device holds at once.
+    pub fn ff_effects_max(&self) -> u32 {
+        self.info.ff_effects_max
+    }
+
+    /// Raw ioctl on the device's handle, for requests that take pointers.
+    ///
+    /// # Safety
+    /// Same as UinputBackend::ioctl.
+    pub(crate) unsafe fn ioctl(&self, request: u64, arg: u64) -> Result<()> {
+        unsafe { self.backend.ioctl(request, arg) }
+    }
+
+    /// Name of the device in sysfs, for example "input23".
+    /// The device lives under /sys/devices/virtual/input/ with this name.
+    pub fn sysname(&self) -> Result<String> {
+        let mut buf = [0u8; 64];
+        let req = UI_GET_SYSNAME_BASE | ((buf.len() as u64) << 16);
+        unsafe { self.backend.ioctl(req, buf.as_mut_ptr() as u64) }?;
+
+        let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+        String::from_utf8(buf[..len].to_vec())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "sysname is not UTF-8"))
+    }
+
+    /// Path of the /dev/input/eventN node for this device.
+    /// The node shows up a moment after creation, so this can return
+    /// NotFound right after new().
+    pub fn event_node(&self) -> Result<PathBuf> {
+        crate::evdev::event_node(&self.sysname()?)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "event node doesn't exist yet"))
+    }
+
+    /// Block until the kernel has registered the device and created its
+    /// /dev/input/eventN node, checked through the sysname and sysfs.
+    /// Fails with TimedOut if that takes longer than timeout. Only a missing
+    /// node is waited for, any other error comes back right away.
+    ///
+    /// Replaces the usual "sleep 100 ms after creating" workaround. Desktop
+    /// environments may still need a moment to pick the node up after this.
+    pub fn wait_until_registered(&self, timeout: Duration) -> Result<PathBuf> {
+        let start = Instant::now();
+        loop {
+            match self.event_node() {
+                Ok(node) => return Ok(node),
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                Err(_) if start.elapsed() < timeout => sleep(Duration::from_millis(5)),
+                Err(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "device wasn't registered in time",
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Everything that was enabled when the device was created.
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    /// The whole configuration (name, id, capabilities and absinfo) in a
+    /// stable, line based text format. Commit it as a golden file and compare
+    /// in tests to catch accidental changes to a device definition.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{Device, MockBackend, UInputUserDevice, key_events::{ABS_X_EVENT, KEY_A_EVENT}};
+    ///
+    /// let mut info = UInputUserDevice::with_name("golden");
+    /// info.absinfo(ABS_X_EVENT, 0, 4096, 4, 0);
+    /// let device = Device::with_backend(MockBackend::new(), &[KEY_A_EVENT, ABS_X_EVENT], &info).unwrap();
+    ///
+    /// assert_eq!(
+    ///     device.capability_snapshot(),
+    ///     "name: golden
+    /// id: bustype=0x3 vendor=0x1 product=0x1 version=0x1
+    /// ff_effects_max: 0
+    /// EV_KEY KEY_A
+    /// EV_ABS ABS_X min=0 max=4096 fuzz=4 flat=0
+    /// "
+    /// );
+    /// ```
+    pub fn capability_snapshot(&self) -> String {
+        use std::fmt::Write;
+
+        let info = &self.info;
+        let mut out = String::new();
+        // Writing to a String can't fail.
+        let _ = writeln!(out, "name: {}", self.name_lossy());
+        let _ = writeln!(
+            out,
+            "id: bustype={:#x} vendor={:#x} product={:#x} version={:#x}",
+            info.id.bustype, info.id.vendor, info.id.product, info.id.version
+        );
+        let _ = writeln!(out, "ff_effects_max: {}", info.ff_effects_max);
+        for prop in (0..=INPUT_PROP_MAX).filter(|&prop| self.has_property(prop)) {
+            let _ = writeln!(out, "INPUT_PROP {prop:#x}");
+        }
+        for (event_type, code) in self.capabilities.iter() {
+            let _ = write!(out, "{}", crate::event_type_name(event_type));
+            let _ = match crate::names::code_name(event_type, code) {
+                Some(name) => write!(out, " {name}"),
+                None => write!(out, " {code:#x}"),
+            };
+            let i = code as usize;
+            if event_type == EV_ABS && i < info.absmin.len() {
+                let _ = write!(
+                    out,
+                    " min={} max={} fuzz={} flat={}",
+                    info.absmin[i], info.absmax[i], info.absfuzz[i], info.absflat[i]
+                );
+                if self.resolution[i] != 0 {
+                    let _ = write!(out, " res={}", self.resolution[i]);
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The device description in evemu's format, what `evemu-describe`
+    /// prints for a real device.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{Device, MockBackend, UInputUserDevice, key_events::KEY_A_EVENT};
+    ///
+    /// let device = Device::with_backend(MockBackend::new(), &[KEY_A_EVENT], &UInputUserDevice::with_name("kbd")).unwrap();
+    /// let evemu = device.evemu_description();
+    /// assert!(evemu.contains("N: kbd\n"));
+    /// assert!(evemu.contains("I: 0003 0001 0001 0001\n"));
+    /// // EV_SYN and EV_KEY
+    /// assert!(evemu.contains("B: 00 03 00 00 00 00 00 00 00\n"));
+    /// ```
+    pub fn evemu_description(&self) -> String {
+        use std::fmt::Write;
+
+        // Bytes in each type's bitmask: (TYPE_MAX + 1) / 8.
+        const MASK_BYTES: [(u16, usize); 9] = [
+            (EV_SYN, 4),
+            (EV_KEY, 96),
+            (EV_REL, 2),
+            (EV_ABS, 8),
+            (EV_MSC, 1),
+            (EV_SW, 3),
+            (EV_LED, 2),
+            (EV_SND, 1),
+            (EV_FF, 16),
+        ];
+
+        let info = &self.info;
+        let name = self.name_lossy();
+        let mut out = String::new();
+        let _ = writeln!(out, "# EVEMU 1.3");
+        let _ = writeln!(out, "# Input device name: \"{name}\"");
+        let _ = writeln!(out, "# Created by uinput-rs");
+        let _ = writeln!(out, "N: {name}");
+        let _ = writeln!(
+            out,
+            "I: {:04x} {:04x} {:04x} {:04x}",
+            info.id.bustype, info.id.vendor, info.id.product, info.id.version
+        );
+        let _ = write!(out, "P:");
+        for byte in (self.properties as u64).to_le_bytes() {
+            let _ = write!(out, " {byte:02x}");
+        }
+        out.push('\n');
+
+        for (event_type, len) in MASK_BYTES {
+            let mut mask = vec![0u8; len];
+            for (i, byte) in mask.iter_mut().enumerate() {
+                for bit in 0..8 {
+                    let code = (i * 8 + bit) as u16;
+                    let set = if event_type == EV_SYN {
+                        code == EV_SYN || self.capabilities.has_type(code)
+                    } else {
+                        self.capabilities.contains(event_type, code)
+                    };
+                    *byte |= (set as u8) << bit;
+                }
+            }
+            for chunk in mask.chunks(8) {
+                let _ = write!(out, "B: {event_type:02x}");
+                for i in 0..8 {
+                    let _ = write!(out, " {:02x}", chunk.get(i).copied().unwrap_or(0));
+                }
+                out.push('\n');
+            }
+        }
+
+        for code in self.capabilities.codes(EV_ABS) {
+            let i = code as usize;
+            if i < info.absmin.len() {
+                let _ = writeln!(
+                    out,
+                    "A: {code:02x} {} {} {} {} {}",
+                    info.absmin[i],
+                    info.absmax[i],
+                    info.absfuzz[i],
+                    info.absflat[i],
+                    self.resolution[i]
+                );
+            }
+        }
+        out
+    }
+
+    /// Write evemu_description() to a file, for bug reports or to recreate
+    /// the exact device with `evemu-device`.
+    pub fn export_evemu(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        std::fs::write(path, self.evemu_description())
+    }
+
+    fn name_lossy(&self) -> String {
+        let name: Vec<u8> = self
+            .info
+            .name
+            .iter()
+            .take_while(|&&b| b != 0)
+            .map(|&b| b as u8)
+            .collect();
+        String::from_utf8_lossy(&name).into_owned()
+    }
+
+    /// In strict mode emitting an event whose (TYPE, CODE) was never enabled
+    /// fails with Error::CapabilityNotEnabled instead of being silently
+    /// dropped by the kernel. SYN events are always allowed. Off by default.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{Device, Error, MockBackend, UInputUserDevice, key_events::{KEY_A_EVENT, KEY_B_EVENT}};
+    ///
+    /// let mut device = Device::with_backend(MockBackend::new(), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap();
+    /// device.set_strict(true);
+    ///
+    /// assert!(device.emit_key_code(KEY_A_EVENT, 1).is_ok());
+    /// let err = device.emit_key_code(KEY_B_EVENT, 1).unwrap_err();
+    /// assert!(matches!(Error::from_io(&err), Some(Error::CapabilityNotEnabled { .. })));
+    /// ```
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// What strict mode does with ABS values outside the axis' absmin..=absmax.
+    /// Axes left at 0..0 are treated as unconfigured and never checked.
+    /// Only applies while strict mode is on. The default is Clamp.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{AbsRangePolicy, Device, MockBackend, UInputUserDevice,
+    ///     key_codes::ABS_X, key_events::ABS_X_EVENT, key_types::EV_ABS};
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut info = UInputUserDevice::default();
+    /// info.absinfo(ABS_X_EVENT, 0, 100, 0, 0);
+    /// let mut device = Device::with_backend(mock.clone(), &[ABS_X_EVENT], &info).unwrap();
+    /// device.set_strict(true);
+    ///
+    /// device.emit(EV_ABS, ABS_X, 150).unwrap();
+    /// assert_eq!(mock.events()[0].value, 100);
+    ///
+    /// device.set_abs_range_policy(AbsRangePolicy::Reject);
+    /// assert!(device.emit(EV_ABS, ABS_X, 150).is_err());
+    /// ```
+    pub fn set_abs_range_policy(&mut self, policy: AbsRangePolicy) {
+        self.abs_range_policy = policy;
+    }
+
+    /// absmin and absmax of an ABS axis, (0, 0) when it wasn't configured.
+    pub fn abs_range(&self, code: u16) -> (i32, i32) {
+        let i = code as usize;
+        match (self.info.absmin.get(i), self.info.absmax.get(i)) {
+            (Some(&min), Some(&max)) => (min, max),
+            _ => (0, 0),
+        }
+    }
+
+    /// Whether an INPUT_PROP_* property was set on the device.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UI_SET_PROPBIT, UInputUserDevice,
+    ///     devices::TOUCHSCREEN_EVENTS, key_codes::INPUT_PROP_DIRECT,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut info = UInputUserDevice::default();
+    /// info.property(INPUT_PROP_DIRECT);
+    /// let device = Device::with_backend(mock.clone(), &TOUCHSCREEN_EVENTS, &info).unwrap();
+    ///
+    /// assert!(device.has_property(INPUT_PROP_DIRECT));
+    /// assert!(mock.ioctls().iter().any(|call| call.request == UI_SET_PROPBIT && call.arg == 1));
+    /// ```
+    pub fn has_property(&self, prop: u16) -> bool {
+        prop <= INPUT_PROP_MAX && self.properties & (1 << prop) != 0
+    }
+
+    /// Resolution of an ABS axis in units per millimeter, 0 when unknown.
+    pub fn abs_resolution(&self, code: u16) -> i32 {
+        self.resolution.get(code as usize).copied().unwrap_or(0)
+    }
+
+    /// Watch for missing or empty SYN_REPORTs. None turns it off (the default).
+    pub fn set_syn_hygiene(&mut self, hygiene: Option<SynHygiene>) {
+        self.syn_hygiene = hygiene;
+    }
+
+    /// Drain the events the kernel sent to the device without blocking:
+    /// EV_LED when the host changes an LED, EV_UINPUT for force feedback.
+    /// LED changes are remembered for led() and lock_state().
+    pub fn read_feedback(&self) -> Result<Vec<input_event>> {
+        let mut events = std::mem::take(&mut *self.feedback.lock().unwrap());
+        events.extend(self.read_incoming()?);
+        Ok(events)
+    }
+
+    fn read_incoming(&self) -> Result<Vec<input_event>> {
+        let size = std::mem::size_of::<input_event>();
+        let mut buf = [0u8; 16 * std::mem::size_of::<input_event>()];
+        let mut events = Vec::new();
+        loop {
+            let len = self.backend.read(&mut buf)?;
+            if len == 0 {
+                break;
+            }
+            for bytes in buf[..len].chunks_exact(size) {
+                let event =
+                    unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const input_event) };
+                if event.type_ == EV_LED && event.code <= LED_MAX {
+                    let bit = 1 << event.code;
+                    if event.value != 0 {
+                        self.leds.fetch_or(bit, Ordering::Relaxed);
+                    } else {
+                        self.leds.fetch_and(!bit, Ordering::Relaxed);
+                    }
+                }
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Whether the host last turned this LED_* on. Doesn't read anything,
+    /// call read_feedback (or lock_state) first.
+    pub fn led(&self, code: u16) -> bool {
+        code <= LED_MAX && self.leds.load(Ordering::Relaxed) & (1 << code) != 0
+    }
+
+    /// Caps, Num and Scroll Lock as the host sees them.
+    /// Needs EV_LED with LED_CAPSL, LED_NUML and LED_SCROLLL enabled.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     key_codes::{KEY_CAPSLOCK, LED_CAPSL},
+    ///     key_events::{KEY_CAPSLOCK_EVENT, LED_CAPSL_EVENT},
+    ///     key_types::EV_LED,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let device = Device::with_backend(mock.clone(), &[KEY_CAPSLOCK_EVENT, LED_CAPSL_EVENT], &UInputUserDevice::default()).unwrap();
+    ///
+    /// mock.push_incoming(EV_LED, LED_CAPSL, 1);
+    /// assert!(device.lock_state().unwrap().caps);
+    ///
+    /// device.set_capslock(true).unwrap(); // already on, nothing sent
+    /// assert!(mock.events().is_empty());
+    /// device.set_capslock(false).unwrap();
+    /// assert!(mock.events().iter().any(|e| e.code == KEY_CAPSLOCK));
+    /// ```
+    ///
+    /// Only the LED events are used up. Force feedback read along the way
+    /// waits for the next read_feedback, so uploads still get answered:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     ff::{Effect, EffectKind, EffectTable, FfRequest},
+    ///     key_codes::LED_NUML,
+    ///     key_events::{FF_RUMBLE_EVENT, LED_NUML_EVENT},
+    ///     key_types::EV_LED,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut info = UInputUserDevice::default();
+    /// info.ff_effects_max = 1;
+    /// let device = Device::with_backend(mock.clone(), &[FF_RUMBLE_EVENT, LED_NUML_EVENT], &info).unwrap();
+    /// let mut table = EffectTable::for_device(&device);
+    ///
+    /// let mut rumble = Effect::new(EffectKind::Rumble { strong: 1.0, weak: 0.0 });
+    /// rumble.id = 0;
+    /// mock.push_ff_upload(rumble.to_raw());
+    /// mock.push_incoming(EV_LED, LED_NUML, 1);
+    /// assert!(device.lock_state().unwrap().num);
+    ///
+    /// let requests = table.process(&device).unwrap();
+    /// assert!(matches!(requests[0], FfRequest::Upload { .. }));
+    /// assert_eq!(mock.ff_results(), [0]);
+    /// ```
+    pub fn lock_state(&self) -> Result<LockState> {
+        let other = self
+            .read_incoming()?
+            .into_iter()
+            .filter(|e| e.type_ != EV_LED);
+        self.feedback.lock().unwrap().extend(other);
+        Ok(LockState::from_bits(self.leds.load(Ordering::Relaxed)))
+    }
+
+    /// Turn Caps Lock on or off, tapping KEY_CAPSLOCK only if the host's
+    /// LED says it's in the other state.
+    pub fn set_capslock(&self, on: bool) -> Result<()> {
+        let current = self.lock_state()?.caps;
+        self.toggle_lock(KEY_CAPSLOCK, current, on)
+    }
+
+    /// Turn Num Lock on or off, see set_capslock.
+    pub fn set_numlock(&self, on: bool) -> Result<()> {
+        let current = self.lock_state()?.num;
+        self.toggle_lock(KEY_NUMLOCK, current, on)
+    }
+
+    /// Turn Scroll Lock on or off, see set_capslock.
+    pub fn set_scrolllock(&self, on: bool) -> Result<()> {
+        let current = self.lock_state()?.scroll;
+        self.toggle_lock(KEY_SCROLLLOCK, current, on)
+    }
+
+    fn toggle_lock(&self, key: u16, current: bool, wanted: bool) -> Result<()> {
+        if current == wanted {
+            return Ok(());
+        }
+        for value in [1, 0] {
+            self.emit(EV_KEY, key, value)?;
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    /// Keys and buttons currently held down, lowest code first.
+    /// Only counts presses and releases that were actually written.
+    pub fn pressed_keys(&self) -> Vec<u16> {
+        self.keys.pressed()
+    }
+
+    /// Release every held key and button, then sync.
+    /// Call this before handing control back so nothing stays stuck down.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{Device, MockBackend, UInputUserDevice,
+    ///     key_codes::{KEY_A, KEY_LEFTCTRL}, key_events::{KEY_A_EVENT, KEY_LEFTCTRL_EVENT}};
+    ///
+    /// let device = Device::with_backend(MockBackend::new(), &[KEY_A_EVENT, KEY_LEFTCTRL_EVENT], &UInputUserDevice::default()).unwrap();
+    /// device.emit_key_code(KEY_LEFTCTRL_EVENT, 1).unwrap();
+    /// device.emit_key_code(KEY_A_EVENT, 1).unwrap();
+    /// device.sync().unwrap();
+    /// assert_eq!(device.pressed_keys(), [KEY_LEFTCTRL, KEY_A]);
+    ///
+    /// device.release_all().unwrap();
+    /// assert!(device.pressed_keys().is_empty());
+    /// ```
+    pub fn release_all(&self) -> Result<()> {
+        let mut events: Vec<input_event> = self
+            .keys
+            .pressed()
+            .into_iter()
+            .map(|code| new_event(EV_KEY, code, 0))
+            .collect();
+        if events.is_empty() {
+            return Ok(());
+        }
+        events.push(new_event(EV_SYN, SYN_REPORT, 0));
+        self.write_events(&events)
+    }
+
+    /// Held keys and the last ABS values, for restore.
+    pub fn state_snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            keys: self.keys.pressed(),
+            abs: self.abs.values(),
+        }
+    }
+
+    /// Emit whatever gets from the current state to `snapshot` in one
+    /// frame: releases of keys it doesn't hold, presses of keys it does and
+    /// ABS values that differ. Keys and axes this device doesn't have are
+    /// skipped, so a snapshot can move to another device. Hooks are
+    /// skipped too.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     key_codes::{KEY_A, KEY_LEFTSHIFT},
+    ///     key_events::{KEY_A_EVENT, KEY_LEFTSHIFT_EVENT},
+    ///     key_types::EV_KEY,
+    /// };
+    ///
+    /// let events = [KEY_A_EVENT, KEY_LEFTSHIFT_EVENT];
+    /// let old = Device::with_backend(MockBackend::new(), &events, &UInputUserDevice::default()).unwrap();
+    /// old.emit(EV_KEY, KEY_LEFTSHIFT, 1).unwrap();
+    /// old.sync().unwrap();
+    /// let snapshot = old.state_snapshot();
+    ///
+    /// // Hand over to a fresh device without dropping the held shift.
+    /// let new = Device::with_backend(MockBackend::new(), &events, &UInputUserDevice::default()).unwrap();
+    /// new.emit(EV_KEY, KEY_A, 1).unwrap();
+    /// new.restore(&snapshot).unwrap();
+    /// assert_eq!(new.pressed_keys(), [KEY_LEFTSHIFT]);
+    /// ```
+    pub fn restore(&self, snapshot: &StateSnapshot) -> Result<()> {
+        let held = self.keys.pressed();
+        let current = self.abs.values();
+        let releases = held
+            .iter()
+            .filter(|code| !snapshot.keys.contains(code))
+            .map(|&code| new_event(EV_KEY, code, 0));
+        let presses = snapshot
+            .keys
+            .iter()
+            .filter(|&&code| !held.contains(&code) && self.capabilities.contains(EV_KEY, code))
+            .map(|&code| new_event(EV_KEY, code, 1));
+        let axes = snapshot
+            .abs
+            .iter()
+            .filter(|&&(code, value)| {
+                self.capabilities.contains(EV_ABS, code) && !current.contains(&(code, value))
+            })
+            .map(|&(code, value)| new_event(EV_ABS, code, value));
+        let mut events: Vec<input_event> = axes.chain(releases).chain(presses).collect();
+        if events.is_empty() {
+            return Ok(());
+        }
+        events.push(new_event(EV_SYN, SYN_REPORT, 0));
+        self.write_events(&events)
+    }
+
+    /// Borrow the device through a guard that releases every held key if
+    /// the thread panics. See InputGuard.
+    pub fn guard(&self) -> crate::InputGuard<'_> {
+        crate::InputGuard::new(self)
+    }
+
+    /// Run every emitted event through `hook` before it's written. Return
+    /// the event, changed or not, to pass it on and None to drop it. Hooks
+    /// run in the order they were added, each on what the one before let
+    /// through, SYN events included. release_all skips them.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     devices::MOUSE_EVENTS,
+    ///     key_codes::{BTN_RIGHT, REL_X},
+    ///     key_types::{EV_KEY, EV_REL},
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut device = Device::with_backend(mock.clone(), &MOUSE_EVENTS, &UInputUserDevice::default()).unwrap();
+    /// device.add_hook(|mut event| {
+    ///     if event.type_ == EV_REL && event.code == REL_X {
+    ///         event.value *= 2;
+    ///     }
+    ///     Some(event)
+    /// });
+    /// let no_right_click = device.add_hook(|event| (event.code != BTN_RIGHT || event.type_ != EV_KEY).then_some(event));
+    ///
+    /// device.emit(EV_REL, REL_X, 5).unwrap();
+    /// device.emit(EV_KEY, BTN_RIGHT, 1).unwrap();
+    /// assert_eq!(mock.events().len(), 1);
+    /// assert_eq!(mock.events()[0].value, 10);
+    ///
+    /// assert!(device.remove_hook(no_right_click));
+    /// device.emit(EV_KEY, BTN_RIGHT, 1).unwrap();
+    /// assert_eq!(mock.events().len(), 2);
+    /// ```
+    pub fn add_hook(
+        &mut self,
+        mut hook: impl FnMut(input_event) -> Option<input_event> + Send + 'static,
+    ) -> HookId {
+        self.hooks
+            .add(Box::new(move |event, out: &mut Vec<input_event>| {
+                out.extend(hook(event))
+            }))
+    }
+
+    /// Like add_hook, for hooks that turn one event into any number of
+    /// them by pushing to `out`.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     key_codes::{KEY_A, KEY_B},
+    ///     key_events::{KEY_A_EVENT, KEY_B_EVENT},
+    ///     key_types::EV_KEY,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut device = Device::with_backend(mock.clone(), &[KEY_A_EVENT, KEY_B_EVENT], &UInputUserDevice::default()).unwrap();
+    /// device.add_fan_out_hook(|event, out| {
+    ///     out.push(event);
+    ///     if event.type_ == EV_KEY && event.code == KEY_A {
+    ///         out.push(uinput_rs::input_event { code: KEY_B, ..event });
+    ///     }
+    /// });
+    ///
+    /// device.emit(EV_KEY, KEY_A, 1).unwrap();
+    /// let codes: Vec<u16> = mock.events().iter().map(|e| e.code).collect();
+    /// assert_eq!(codes, [KEY_A, KEY_B]);
+    /// ```
+    pub fn add_fan_out_hook(
+        &mut self,
+        hook: impl FnMut(input_event, &mut Vec<input_event>) + Send + 'static,
+    ) -> HookId {
+        self.hooks.add(Box::new(hook))
+    }
+
+    /// Add a Transformer, like a Pipeline, as a hook.
+    pub fn add_transformer(
+        &mut self,
+        transformer: impl crate::pipeline::Transformer + Send + 'static,
+    ) -> HookId {
+        self.hooks.add(Box::new(transformer))
+    }
+
+    /// Remove a hook, false if it was already gone.
+    pub fn remove_hook(&mut self, id: HookId) -> bool {
+        self.hooks.remove(id)
+    }
+
+    /// Fill in the other half of each wheel frame, for devices with both
+    /// REL_WHEEL and REL_WHEEL_HI_RES (or the horizontal pair). Off by
+    /// default, turn it on when only one of the two gets emitted. Frames
+    /// with only hi-res get a notch every 120ths, frames with only notches
+    /// get 120 hi-res per notch. Emitting one of the two confuses
+    /// consumers that read the other.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     devices::MOUSE_EVENTS,
+    ///     key_codes::{REL_WHEEL, REL_WHEEL_HI_RES},
+    ///     key_types::EV_REL,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut device = Device::with_backend(mock.clone(), &MOUSE_EVENTS, &UInputUserDevice::default()).unwrap();
+    /// device.set_wheel_pairing(true);
+    /// for _ in 0..4 {
+    ///     device.emit(EV_REL, REL_WHEEL_HI_RES, 60).unwrap();
+    ///     device.sync().unwrap();
+    /// }
+    /// device.emit(EV_REL, REL_WHEEL, -1).unwrap();
+    /// device.sync().unwrap();
+    ///
+    /// let wheel: Vec<(u16, i32)> = mock.events().iter().filter(|e| e.type_ == EV_REL).map(|e| (e.code, e.value)).collect();
+    /// assert_eq!(wheel, [
+    ///     (REL_WHEEL_HI_RES, 60),
+    ///     (REL_WHEEL_HI_RES, 60), (REL_WHEEL, 1),
+    ///     (REL_WHEEL_HI_RES, 60),
+    ///     (REL_WHEEL_HI_RES, 60), (REL_WHEEL, 1),
+    ///     (REL_WHEEL, -1), (REL_WHEEL_HI_RES, -120),
+    /// ]);
+    /// ```
+    pub fn set_wheel_pairing(&mut self, on: bool) {
+        self.wheels = match on {
+            true => WheelPairing::for_capabilities(&self.capabilities),
+            false => None,
+        };
+    }
+
+    /// What to do with presses of keys that are already down and releases of
+    /// keys that are already up. The default is DuplicatePolicy::Pass.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{Device, DuplicatePolicy, MockBackend, UInputUserDevice, key_events::KEY_A_EVENT};
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut device = Device::with_backend(mock.clone(), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap();
+    /// device.set_duplicate_policy(DuplicatePolicy::Ignore);
+    ///
+    /// device.emit_key_code(KEY_A_EVENT, 1).unwrap();
+    /// device.emit_key_code(KEY_A_EVENT, 1).unwrap();
+    /// assert_eq!(mock.events().len(), 1);
+    /// ```
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicate_policy = policy;
+    }
+
+    /// Counters for everything written through this device.
+    pub fn stats(&self) -> DeviceStats {
+        self.stats.snapshot()
+    }
+
+    /// Change what happens when a write fails with EINTR or EAGAIN.
+    /// The default is RetryPolicy::FailFast which returns the error right away.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry = policy;
+    }
+
+    /// How helpers that send timed sequences (playback and the like) wait
+    /// between events on this device.
+    pub fn pacing(&self) -> Pacing {
+        self.pacing
+    }
+
+    /// Change how timed helpers wait between events on this device.
+    /// Pacing::Hybrid gives much smoother high rate motion than plain sleeping.
+    pub fn set_pacing(&mut self, pacing: Pacing) {
+        self.pacing = pacing;
+    }
+
     /// Destroys the current device.
     /// Don't call more than once.
     /// This is called on drop. (Automatically)
     unsafe fn destroy(&mut self) -> Result<()> {
-        ioctl(self.file.as_raw_fd(), UI_DEV_DESTROY, 0)
+        unsafe { self.backend.ioctl(UI_DEV_DESTROY, 0) }
+    }
+
+    /// Release everything and destroy the device now, for when other
+    /// owners may keep the Device itself alive. Later emits fail and drop
+    /// doesn't destroy it again.
+    pub(crate) fn teardown(&self) {
+        let _ = self.release_all();
+        if !self.destroyed.swap(true, Ordering::SeqCst) {
+            let _ = unsafe { self.backend.ioctl(UI_DEV_DESTROY, 0) };
+        }
     }
 }
 
 impl Drop for Device {
     fn drop(&mut self) {
-        unsafe { self.destroy() }.expect("Error destroying device.");
+        let panicking = std::thread::panicking();
+        if panicking {
+            // Don't leave anything held down if the device outlives this
+            // (shared fd, persistent device) or the consumer lags behind.
+            let _ = self.release_all();
+        }
+        if *self.destroyed.get_mut() {
+            return;
+        }
+        let destroyed = unsafe { self.destroy() };
+        if panicking {
+            // A second panic while unwinding would abort the process.
+            #[cfg(feature = "log")]
+            if let Err(err) = &destroyed {
+                log::warn!("destroying device while panicking: {err}");
+            }
+            return;
+        }
+        destroyed.expect("Error destroying device.");
     }
 }
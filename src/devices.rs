@@ -7,14 +7,27 @@ use crate::{
     key_codes::{
         ABS_MT_POSITION_X, ABS_MT_POSITION_Y, ABS_MT_PRESSURE, ABS_MT_SLOT, ABS_MT_TOUCH_MAJOR,
         ABS_MT_TOUCH_MINOR, ABS_MT_TRACKING_ID, ABS_PRESSURE, ABS_TILT_X, ABS_TILT_Y, ABS_X, ABS_Y,
+        BUS_HOST, INPUT_PROP_BUTTONPAD, INPUT_PROP_DIRECT, INPUT_PROP_POINTER,
+        INPUT_PROP_POINTING_STICK, MT_TOOL_MAX,
     },
     key_events::{
+        ABS_DISTANCE_EVENT, ABS_HAT0X_EVENT, ABS_HAT0Y_EVENT, ABS_MT_ORIENTATION_EVENT,
         ABS_MT_POSITION_X_EVENT, ABS_MT_POSITION_Y_EVENT, ABS_MT_PRESSURE_EVENT, ABS_MT_SLOT_EVENT,
-        ABS_MT_TOUCH_MAJOR_EVENT, ABS_MT_TOUCH_MINOR_EVENT, ABS_MT_TRACKING_ID_EVENT,
-        ABS_PRESSURE_EVENT, ABS_TILT_X_EVENT, ABS_TILT_Y_EVENT, ABS_X_EVENT, ABS_Y_EVENT,
-        BTN_EXTRA_EVENT, BTN_LEFT_EVENT, BTN_MIDDLE_EVENT, BTN_RIGHT_EVENT, BTN_SIDE_EVENT,
-        BTN_TOUCH_EVENT, REL_HWHEEL_EVENT, REL_HWHEEL_HI_RES_EVENT, REL_WHEEL_EVENT,
-        REL_WHEEL_HI_RES_EVENT, REL_X_EVENT, REL_Y_EVENT,
+        ABS_MT_TOOL_TYPE_EVENT, ABS_MT_TOUCH_MAJOR_EVENT, ABS_MT_TOUCH_MINOR_EVENT,
+        ABS_MT_TRACKING_ID_EVENT, ABS_PRESSURE_EVENT, ABS_RX_EVENT, ABS_RY_EVENT, ABS_RZ_EVENT,
+        ABS_TILT_X_EVENT, ABS_TILT_Y_EVENT, ABS_X_EVENT, ABS_Y_EVENT, ABS_Z_EVENT, BTN_A_EVENT,
+        BTN_B_EVENT, BTN_EXTRA_EVENT, BTN_LEFT_EVENT, BTN_MIDDLE_EVENT, BTN_MODE_EVENT,
+        BTN_RIGHT_EVENT, BTN_SELECT_EVENT, BTN_SIDE_EVENT, BTN_START_EVENT, BTN_STYLUS_EVENT,
+        BTN_STYLUS2_EVENT, BTN_THUMBL_EVENT, BTN_THUMBR_EVENT, BTN_TL_EVENT,
+        BTN_TOOL_DOUBLETAP_EVENT, BTN_TOOL_FINGER_EVENT, BTN_TOOL_PEN_EVENT,
+        BTN_TOOL_QUADTAP_EVENT, BTN_TOOL_QUINTTAP_EVENT, BTN_TOOL_RUBBER_EVENT,
+        BTN_TOOL_TRIPLETAP_EVENT, BTN_TOUCH_EVENT, BTN_TR_EVENT, BTN_X_EVENT, BTN_Y_EVENT,
+        KEY_APPSELECT_EVENT, KEY_BACK_EVENT, KEY_BRIGHTNESSDOWN_EVENT, KEY_BRIGHTNESSUP_EVENT,
+        KEY_HOMEPAGE_EVENT, KEY_MENU_EVENT, KEY_MUTE_EVENT, KEY_NEXTSONG_EVENT,
+        KEY_PLAYPAUSE_EVENT, KEY_POWER_EVENT, KEY_PREVIOUSSONG_EVENT, KEY_SLEEP_EVENT,
+        KEY_STOPCD_EVENT, KEY_VOLUMEDOWN_EVENT, KEY_VOLUMEUP_EVENT, LED_CAPSL_EVENT,
+        LED_NUML_EVENT, LED_SCROLLL_EVENT, REL_HWHEEL_EVENT, REL_HWHEEL_HI_RES_EVENT,
+        REL_WHEEL_EVENT, REL_WHEEL_HI_RES_EVENT, REL_X_EVENT, REL_Y_EVENT, SW_LID_EVENT,
     },
     name_from_str,
 };
@@ -32,6 +45,54 @@ pub const TOUCHSCREEN_EVENTS: [(u64, u64); 10] = [
     ABS_MT_TOUCH_MAJOR_EVENT,
     ABS_MT_TOUCH_MINOR_EVENT,
 ];
+/// Clickpad style touchpad: one button, five slots and a BTN_TOOL_* code
+/// for each finger count up to five.
+pub const TOUCHPAD_EVENTS: [(u64, u64); 19] = [
+    ABS_X_EVENT,
+    ABS_Y_EVENT,
+    ABS_PRESSURE_EVENT,
+    BTN_LEFT_EVENT,
+    BTN_TOUCH_EVENT,
+    BTN_TOOL_FINGER_EVENT,
+    BTN_TOOL_DOUBLETAP_EVENT,
+    BTN_TOOL_TRIPLETAP_EVENT,
+    BTN_TOOL_QUADTAP_EVENT,
+    BTN_TOOL_QUINTTAP_EVENT,
+    ABS_MT_SLOT_EVENT,
+    ABS_MT_TRACKING_ID_EVENT,
+    ABS_MT_POSITION_X_EVENT,
+    ABS_MT_POSITION_Y_EVENT,
+    ABS_MT_PRESSURE_EVENT,
+    ABS_MT_TOOL_TYPE_EVENT,
+    ABS_MT_TOUCH_MAJOR_EVENT,
+    ABS_MT_TOUCH_MINOR_EVENT,
+    ABS_MT_ORIENTATION_EVENT,
+];
+/// Pen tablet: pen and eraser tools, hover distance, pressure, tilt and
+/// the two barrel buttons.
+pub const TABLET_EVENTS: [(u64, u64); 12] = [
+    ABS_X_EVENT,
+    ABS_Y_EVENT,
+    ABS_PRESSURE_EVENT,
+    ABS_DISTANCE_EVENT,
+    ABS_TILT_X_EVENT,
+    ABS_TILT_Y_EVENT,
+    BTN_TOOL_PEN_EVENT,
+    BTN_TOOL_RUBBER_EVENT,
+    BTN_TOUCH_EVENT,
+    BTN_STYLUS_EVENT,
+    BTN_STYLUS2_EVENT,
+    // Keeps udev from tagging it as a joystick.
+    BTN_TOOL_FINGER_EVENT,
+];
+/// Pointing stick with the three buttons below the space bar.
+pub const TRACKPOINT_EVENTS: [(u64, u64); 5] = [
+    REL_X_EVENT,
+    REL_Y_EVENT,
+    BTN_LEFT_EVENT,
+    BTN_RIGHT_EVENT,
+    BTN_MIDDLE_EVENT,
+];
 pub const MOUSE_EVENTS: [(u64, u64); 11] = [
     BTN_LEFT_EVENT,
     BTN_RIGHT_EVENT,
@@ -45,6 +106,42 @@ pub const MOUSE_EVENTS: [(u64, u64); 11] = [
     REL_HWHEEL_EVENT,
     REL_HWHEEL_HI_RES_EVENT,
 ];
+/// Android navigation and hardware buttons. In Android's default Generic.kl
+/// these map to KEYCODE_BACK, KEYCODE_HOME, KEYCODE_APP_SWITCH,
+/// KEYCODE_MENU, KEYCODE_VOLUME_UP, KEYCODE_VOLUME_DOWN and KEYCODE_POWER.
+pub const ANDROID_BUTTON_EVENTS: [(u64, u64); 7] = [
+    KEY_BACK_EVENT,
+    KEY_HOMEPAGE_EVENT,
+    KEY_APPSELECT_EVENT,
+    KEY_MENU_EVENT,
+    KEY_VOLUMEUP_EVENT,
+    KEY_VOLUMEDOWN_EVENT,
+    KEY_POWER_EVENT,
+];
+/// Xbox style gamepad: face buttons, shoulders, sticks with clicks,
+/// analog triggers on ABS_Z/ABS_RZ and a d-pad on ABS_HAT0X/ABS_HAT0Y.
+/// Same layout as the kernel's xpad driver.
+pub const GAMEPAD_EVENTS: [(u64, u64); 19] = [
+    BTN_A_EVENT,
+    BTN_B_EVENT,
+    BTN_X_EVENT,
+    BTN_Y_EVENT,
+    BTN_TL_EVENT,
+    BTN_TR_EVENT,
+    BTN_SELECT_EVENT,
+    BTN_START_EVENT,
+    BTN_MODE_EVENT,
+    BTN_THUMBL_EVENT,
+    BTN_THUMBR_EVENT,
+    ABS_X_EVENT,
+    ABS_Y_EVENT,
+    ABS_Z_EVENT,
+    ABS_RX_EVENT,
+    ABS_RY_EVENT,
+    ABS_RZ_EVENT,
+    ABS_HAT0X_EVENT,
+    ABS_HAT0Y_EVENT,
+];
 pub const ABSOLUTE_EVENTS: [(u64, u64); 14] = [
     ABS_X_EVENT,
     ABS_Y_EVENT,
@@ -63,6 +160,10 @@ pub const ABSOLUTE_EVENTS: [(u64, u64); 14] = [
 ];
 
 /// Creates a multitouch touchscreen device.
+///
+/// It has no physical size to go on, so X and Y are left at resolution 0
+/// and consumers fall back to their own guesses. Use touchscreen_mm when
+/// the size matters, e.g. for libinput's palm and thumb detection.
 pub fn touchscreen(
     name: &str,
     max_x: i32,
@@ -71,6 +172,21 @@ pub fn touchscreen(
     max_minor: i32,
     max_major: i32,
 ) -> Result<Device, Error> {
+    let info = touchscreen_info(name, max_x, max_y, max_pressure, max_minor, max_major);
+    Device::new_custom(&TOUCHSCREEN_EVENTS, &info)
+}
+
+/// The description touchscreen() creates, with INPUT_PROP_DIRECT set.
+/// Tweak it (properties, ids, ranges) and create the device yourself with
+/// TOUCHSCREEN_EVENTS to override the preset's choices.
+pub fn touchscreen_info(
+    name: &str,
+    max_x: i32,
+    max_y: i32,
+    max_pressure: i32,
+    max_minor: i32,
+    max_major: i32,
+) -> UInputUserDevice {
     let mut absmax = [0; 64];
 
     absmax[ABS_X as usize] = max_x;
@@ -85,7 +201,7 @@ pub fn touchscreen(
     absmax[ABS_MT_TOUCH_MAJOR as usize] = max_major;
     absmax[ABS_MT_TOUCH_MINOR as usize] = max_minor;
 
-    let info = UInputUserDevice {
+    let mut info = UInputUserDevice {
         name: name_from_str(name).unwrap(),
         id: input_id {
             bustype: 0x03,
@@ -96,16 +212,266 @@ pub fn touchscreen(
         absmax,
         ..Default::default()
     };
+    info.property(INPUT_PROP_DIRECT);
+    info
+}
 
+/// Touchscreen with a known physical size, so consumers can tell how
+/// big a touch is in millimeters. Pressure and contact size are 0..=255.
+pub fn touchscreen_mm(
+    name: &str,
+    max_x: i32,
+    max_y: i32,
+    width_mm: f32,
+    height_mm: f32,
+) -> Result<Device, Error> {
+    let mut info = touchscreen_info(name, max_x, max_y, 255, 255, 255);
+    info.physical_size(width_mm, height_mm);
     Device::new_custom(&TOUCHSCREEN_EVENTS, &info)
 }
 
+/// Every key of a full size PC keyboard, KEY_ESC through KEY_COMPOSE,
+/// plus the three lock LEDs.
+pub const KEYBOARD_EVENTS: [(u64, u64); 130] = {
+    let mut events = [(0, 0); 130];
+    let mut code = 0;
+    while code < 127 {
+        events[code] = (0x01, code as u64 + 1);
+        code += 1;
+    }
+    events[127] = LED_NUML_EVENT;
+    events[128] = LED_CAPSL_EVENT;
+    events[129] = LED_SCROLLL_EVENT;
+    events
+};
+
+/// Volume, brightness and playback keys, the usual Fn row.
+pub const MEDIA_KEY_EVENTS: [(u64, u64); 9] = [
+    KEY_VOLUMEUP_EVENT,
+    KEY_VOLUMEDOWN_EVENT,
+    KEY_MUTE_EVENT,
+    KEY_BRIGHTNESSUP_EVENT,
+    KEY_BRIGHTNESSDOWN_EVENT,
+    KEY_PLAYPAUSE_EVENT,
+    KEY_NEXTSONG_EVENT,
+    KEY_PREVIOUSSONG_EVENT,
+    KEY_STOPCD_EVENT,
+];
+/// The ACPI power button, as on most laptops and desktops.
+pub const POWER_BUTTON_EVENTS: [(u64, u64); 1] = [KEY_POWER_EVENT];
+/// The ACPI sleep button.
+pub const SLEEP_BUTTON_EVENTS: [(u64, u64); 1] = [KEY_SLEEP_EVENT];
+/// A laptop lid switch.
+pub const LID_SWITCH_EVENTS: [(u64, u64); 1] = [SW_LID_EVENT];
+
+/// ACPI button description the way the kernel's button driver sets it up.
+fn acpi_button(name: &str, product: u16, phys: &str) -> Result<UInputUserDevice, Error> {
+    Ok(UInputUserDevice {
+        name: name_from_str(name)?,
+        id: input_id {
+            bustype: BUS_HOST,
+            vendor: 0,
+            product,
+            version: 0,
+        },
+        phys: phys.to_owned(),
+        ..UInputUserDevice::default()
+    })
+}
+
+/// Power button like the ACPI one ("Power Button", LNXPWRBN). logind
+/// acts on it as long as udev tags it power-switch, which current systemd
+/// does for every device with keys.
+pub fn power_button(name: &str) -> Result<Device, Error> {
+    let info = acpi_button(name, 0x0001, "LNXPWRBN/button/input0")?;
+    Device::new_custom(&POWER_BUTTON_EVENTS, &info)
+}
+
+/// Sleep button like the ACPI one ("Sleep Button", PNP0C0E).
+pub fn sleep_button(name: &str) -> Result<Device, Error> {
+    let info = acpi_button(name, 0x0003, "PNP0C0E/button/input0")?;
+    Device::new_custom(&SLEEP_BUTTON_EVENTS, &info)
+}
+
+/// Lid switch like the ACPI one ("Lid Switch", PNP0C0D). SW_LID 1 is
+/// closed.
+pub fn lid_switch(name: &str) -> Result<Device, Error> {
+    let info = acpi_button(name, 0x0005, "PNP0C0D/button/input0")?;
+    Device::new_custom(&LID_SWITCH_EVENTS, &info)
+}
+
+/// Media and brightness keys with MEDIA_KEY_EVENTS.
+pub fn media_keys(name: &str) -> Result<Device, Error> {
+    Device::new_custom(&MEDIA_KEY_EVENTS, &UInputUserDevice::with_name(name))
+}
+
+/// A full size keyboard with KEYBOARD_EVENTS.
+pub fn keyboard(name: &str) -> Result<Device, Error> {
+    Device::new_custom(&KEYBOARD_EVENTS, &UInputUserDevice::with_name(name))
+}
+
 /// Creates a mouse device
 pub fn mouse(name: &str) -> Result<Device, Error> {
     Device::new_custom(&MOUSE_EVENTS, &UInputUserDevice::with_name(name))
 }
 
+/// Android buttons (back, home, recents, menu, volume, power) for driving
+/// a device over adb as root. See ANDROID_BUTTON_EVENTS for the mapping.
+/// Uses BUS_VIRTUAL so Android picks Generic.kl instead of a vendor layout.
+pub fn android_buttons(name: &str) -> Result<Device, Error> {
+    let info = UInputUserDevice {
+        id: input_id {
+            bustype: 0x06, // BUS_VIRTUAL
+            vendor: 0,
+            product: 0,
+            version: 1,
+        },
+        ..UInputUserDevice::with_name(name)
+    };
+    Device::new_custom(&ANDROID_BUTTON_EVENTS, &info)
+}
+
+/// The identity gamepad() uses.
+pub const GAMEPAD_ID: input_id = input_id {
+    bustype: 0x03, // BUS_USB
+    vendor: 0x1,
+    product: 0x1,
+    version: 1,
+};
+
+/// Xbox style gamepad with GAMEPAD_EVENTS.
+pub fn gamepad(name: &str) -> Result<Device, Error> {
+    gamepad_with_id(name, GAMEPAD_ID)
+}
+
+/// Gamepad with a specific identity, for software that picks mappings by
+/// vendor and product (see the sdl module).
+/// Sticks are -32768..32767, triggers 0..255 and the d-pad -1..1.
+pub fn gamepad_with_id(name: &str, id: input_id) -> Result<Device, Error> {
+    Device::new_custom(&GAMEPAD_EVENTS, &gamepad_info(name, id))
+}
+
+/// The description gamepad_with_id() creates. Set ff_effects_max and add
+/// FF_RUMBLE_EVENT for a pad that rumbles.
+pub fn gamepad_info(name: &str, id: input_id) -> UInputUserDevice {
+    let mut info = UInputUserDevice {
+        id,
+        ..UInputUserDevice::with_name(name)
+    };
+    for stick in [ABS_X_EVENT, ABS_Y_EVENT, ABS_RX_EVENT, ABS_RY_EVENT] {
+        info.absinfo(stick, -32768, 32767, 16, 128);
+    }
+    for trigger in [ABS_Z_EVENT, ABS_RZ_EVENT] {
+        info.absinfo(trigger, 0, 255, 0, 0);
+    }
+    for hat in [ABS_HAT0X_EVENT, ABS_HAT0Y_EVENT] {
+        info.absinfo(hat, -1, 1, 0, 0);
+    }
+    info
+}
+
+/// Touchpad with TOUCHPAD_EVENTS and 5 slots. Coordinates are 0..=max
+/// over width_mm by height_mm, pressure and contact size 0..=255,
+/// orientation -90..=90. Drive it with mt::TouchPad.
+pub fn touchpad(
+    name: &str,
+    max_x: i32,
+    max_y: i32,
+    width_mm: f32,
+    height_mm: f32,
+) -> Result<Device, Error> {
+    let info = touchpad_info(name, max_x, max_y, width_mm, height_mm);
+    Device::new_custom(&TOUCHPAD_EVENTS, &info)
+}
+
+/// The description touchpad() creates, a clickpad with INPUT_PROP_POINTER
+/// and INPUT_PROP_BUTTONPAD. Clear BUTTONPAD for a touchpad with separate
+/// physical buttons.
+pub fn touchpad_info(
+    name: &str,
+    max_x: i32,
+    max_y: i32,
+    width_mm: f32,
+    height_mm: f32,
+) -> UInputUserDevice {
+    let mut info = UInputUserDevice::with_name(name);
+    for (event, max) in [
+        (ABS_X_EVENT, max_x),
+        (ABS_Y_EVENT, max_y),
+        (ABS_MT_POSITION_X_EVENT, max_x),
+        (ABS_MT_POSITION_Y_EVENT, max_y),
+        (ABS_PRESSURE_EVENT, 255),
+        (ABS_MT_PRESSURE_EVENT, 255),
+        (ABS_MT_TOUCH_MAJOR_EVENT, 255),
+        (ABS_MT_TOUCH_MINOR_EVENT, 255),
+        (ABS_MT_SLOT_EVENT, 4),
+        (ABS_MT_TRACKING_ID_EVENT, 65535),
+        (ABS_MT_TOOL_TYPE_EVENT, MT_TOOL_MAX),
+    ] {
+        info.absinfo(event, 0, max, 0, 0);
+    }
+    // Symmetric so 0 means aligned with Y, like the kernel docs ask for.
+    info.absinfo(ABS_MT_ORIENTATION_EVENT, -90, 90, 0, 0);
+    info.physical_size(width_mm, height_mm);
+    info.property(INPUT_PROP_POINTER);
+    info.property(INPUT_PROP_BUTTONPAD);
+    info
+}
+
+/// Pen tablet with TABLET_EVENTS. Coordinates are 0..=max over width_mm
+/// by height_mm, pressure 0..=4095, hover distance 0..=63 and tilt
+/// -64..=63 degrees. Drive it with stylus::Stylus.
+pub fn tablet(
+    name: &str,
+    max_x: i32,
+    max_y: i32,
+    width_mm: f32,
+    height_mm: f32,
+) -> Result<Device, Error> {
+    let info = tablet_info(name, max_x, max_y, width_mm, height_mm);
+    Device::new_custom(&TABLET_EVENTS, &info)
+}
+
+/// The description tablet() creates, an external tablet with
+/// INPUT_PROP_POINTER. Swap it for INPUT_PROP_DIRECT to pretend to be a
+/// pen display.
+pub fn tablet_info(
+    name: &str,
+    max_x: i32,
+    max_y: i32,
+    width_mm: f32,
+    height_mm: f32,
+) -> UInputUserDevice {
+    let mut info = UInputUserDevice::with_name(name);
+    info.absinfo(ABS_X_EVENT, 0, max_x, 0, 0);
+    info.absinfo(ABS_Y_EVENT, 0, max_y, 0, 0);
+    info.absinfo(ABS_PRESSURE_EVENT, 0, 4095, 0, 0);
+    info.absinfo(ABS_DISTANCE_EVENT, 0, 63, 0, 0);
+    info.absinfo(ABS_TILT_X_EVENT, -64, 63, 0, 0);
+    info.absinfo(ABS_TILT_Y_EVENT, -64, 63, 0, 0);
+    info.physical_size(width_mm, height_mm);
+    // Tilt is in degrees, resolution is units per radian.
+    info.resolution(ABS_TILT_X_EVENT, 57);
+    info.resolution(ABS_TILT_Y_EVENT, 57);
+    info.property(INPUT_PROP_POINTER);
+    info
+}
+
+/// TrackPoint style pointing stick with TRACKPOINT_EVENTS and
+/// INPUT_PROP_POINTER plus INPUT_PROP_POINTING_STICK, so libinput applies
+/// its trackpoint acceleration.
+pub fn trackpoint(name: &str) -> Result<Device, Error> {
+    let mut info = UInputUserDevice::with_name(name);
+    info.property(INPUT_PROP_POINTER);
+    info.property(INPUT_PROP_POINTING_STICK);
+    Device::new_custom(&TRACKPOINT_EVENTS, &info)
+}
+
 /// Simple absolute mouse device
+///
+/// Tilt gets the same degrees resolution as tablet(). X and Y don't have a
+/// physical size behind them and stay at resolution 0; use tablet() for a
+/// pointer with one.
 pub fn absolute(name: &str, max_x: i32, max_y: i32) -> Result<Device, Error> {
     let mut absmax = [0; 64];
     let mut absmin = [0; 64];
@@ -120,7 +486,7 @@ pub fn absolute(name: &str, max_x: i32, max_y: i32) -> Result<Device, Error> {
     absmin[ABS_TILT_X as usize] = -64;
     absmin[ABS_TILT_Y as usize] = -64;
 
-    let info = UInputUserDevice {
+    let mut info = UInputUserDevice {
         name: name_from_str(name).unwrap(),
         id: input_id {
             bustype: 0,
@@ -132,6 +498,8 @@ pub fn absolute(name: &str, max_x: i32, max_y: i32) -> Result<Device, Error> {
         absmin,
         ..Default::default()
     };
+    info.resolution(ABS_TILT_X_EVENT, 57);
+    info.resolution(ABS_TILT_Y_EVENT, 57);
 
     Device::new_custom(&ABSOLUTE_EVENTS, &info)
 }
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use libc::input_event;
+
+use crate::{key_types::*, names};
+
+/// Name of an event type, for example "EV_KEY".
+/// Returns an empty string for unknown types.
+pub fn event_type_name(event_type: u16) -> &'static str {
+    match event_type {
+        EV_SYN => "EV_SYN",
+        EV_KEY => "EV_KEY",
+        EV_REL => "EV_REL",
+        EV_ABS => "EV_ABS",
+        EV_MSC => "EV_MSC",
+        EV_SW => "EV_SW",
+        EV_LED => "EV_LED",
+        EV_SND => "EV_SND",
+        EV_REP => "EV_REP",
+        EV_FF => "EV_FF",
+        _ => "",
+    }
+}
+
+/// Formats an event in a human readable way, like `EV_KEY KEY_A 1`.
+/// Codes without a known name are printed as numbers.
+///
+/// # Examples
+/// ```rust
+/// use uinput_rs::{DisplayEvent, input_event, timeval, key_codes::{KEY_A, REL_WHEEL}, key_types::{EV_KEY, EV_REL}};
+///
+/// let event = input_event {
+///     time: timeval { tv_sec: 0, tv_usec: 0 },
+///     type_: EV_KEY,
+///     code: KEY_A,
+///     value: 1,
+/// };
+/// assert_eq!(DisplayEvent(&event).to_string(), "EV_KEY KEY_A 1");
+///
+/// let scroll = input_event { type_: EV_REL, code: REL_WHEEL, value: -1, ..event };
+/// assert_eq!(DisplayEvent(&scroll).to_string(), "EV_REL REL_WHEEL -1");
+/// ```
+pub struct DisplayEvent<'a>(pub &'a input_event);
+
+impl fmt::Display for DisplayEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let event = self.0;
+
+        match event_type_name(event.type_) {
+            "" => write!(f, "{:#x}", event.type_)?,
+            name => f.write_str(name)?,
+        }
+
+        let code_name = match event.type_ {
+            EV_SYN if event.code == 0 => Some("SYN_REPORT"),
+            event_type => names::code_name(event_type, event.code),
+        };
+        match code_name {
+            Some(name) => write!(f, " {name}")?,
+            None => write!(f, " {:#x}", event.code)?,
+        }
+
+        write!(f, " {}", event.value)
+    }
+}
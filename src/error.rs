@@ -0,0 +1,125 @@
+use std::{fmt, io};
+
+/// Errors specific to this crate.
+///
+/// Functions still return std::io::Result. These travel inside the io::Error
+/// (with kind InvalidInput unless noted), so use Error::from_io to tell them apart.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::Error;
+///
+/// fn describe(err: &std::io::Error) -> String {
+///     match Error::from_io(err) {
+///         Some(Error::CapabilityNotEnabled { event_type, code }) => {
+///             format!("forgot to enable {event_type:#x}/{code:#x}")
+///         }
+///         _ => err.to_string(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Strict mode caught an event whose code was never enabled on the device.
+    /// The kernel would have silently dropped it.
+    CapabilityNotEnabled {
+        /// EV_* type of the event.
+        event_type: u16,
+        /// Code of the event.
+        code: u16,
+    },
+    /// Strict mode caught an ABS value outside absmin..=absmax.
+    /// Out of range MT coordinates make consumers misbehave in odd ways.
+    AbsOutOfRange {
+        /// ABS_* code of the axis.
+        code: u16,
+        /// The rejected value.
+        value: i32,
+        /// Configured minimum.
+        min: i32,
+        /// Configured maximum.
+        max: i32,
+    },
+    /// DuplicatePolicy::Reject caught a press of a key that was already down
+    /// or a release of a key that was already up.
+    DuplicateKeyState {
+        /// KEY_* or BTN_* code.
+        code: u16,
+        /// The key was already down.
+        pressed: bool,
+    },
+    /// UInputUserDevice::validate found a configuration the kernel would
+    /// reject or that makes no sense.
+    InvalidDevice {
+        /// What's wrong, in words.
+        reason: String,
+    },
+    /// Opening a device node failed with EACCES while SELinux is enforcing
+    /// and plain file permissions would have allowed it. Common on Android,
+    /// where even root needs a permissive domain (or `setenforce 0`).
+    /// Has kind PermissionDenied.
+    SelinuxDenied {
+        /// The node that couldn't be opened.
+        path: String,
+    },
+    /// A CancelToken stopped whatever was running. Has kind Other, not
+    /// Interrupted, so retry loops don't take it for a signal and go again.
+    Cancelled,
+}
+
+impl Error {
+    /// Finds this crate's error inside an io::Error, if there is one.
+    pub fn from_io(err: &io::Error) -> Option<&Error> {
+        err.get_ref()?.downcast_ref()
+    }
+
+    fn kind(&self) -> io::ErrorKind {
+        match self {
+            Error::CapabilityNotEnabled { .. }
+            | Error::AbsOutOfRange { .. }
+            | Error::DuplicateKeyState { .. }
+            | Error::InvalidDevice { .. } => io::ErrorKind::InvalidInput,
+            Error::SelinuxDenied { .. } => io::ErrorKind::PermissionDenied,
+            Error::Cancelled => io::ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CapabilityNotEnabled { event_type, code } => write!(
+                f,
+                "event type {event_type:#x} code {code:#x} was not enabled on this device"
+            ),
+            Error::AbsOutOfRange {
+                code,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "ABS code {code:#x} value {value} is outside {min}..={max}"
+            ),
+            Error::DuplicateKeyState { code, pressed } => {
+                let state = if *pressed { "down" } else { "up" };
+                write!(f, "key {code:#x} is already {state}")
+            }
+            Error::InvalidDevice { reason } => write!(f, "invalid device: {reason}"),
+            Error::SelinuxDenied { path } => write!(
+                f,
+                "SELinux denied access to {path} even though file permissions allow it"
+            ),
+            Error::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        io::Error::new(err.kind(), err)
+    }
+}
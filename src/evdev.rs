@@ -0,0 +1,91 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read, Result},
+    os::fd::AsRawFd,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use libc::input_event;
+
+use crate::{SYN_REPORT, device::new_event, key_types::EV_SYN};
+
+/// Finds the /dev/input/eventN node that belongs to a sysfs input device,
+/// for example "input23" from Device::sysname().
+/// Returns None if the kernel hasn't created the event handler yet.
+pub fn event_node(sysname: &str) -> Option<PathBuf> {
+    let dir = Path::new("/sys/devices/virtual/input").join(sysname);
+    std::fs::read_dir(dir).ok()?.find_map(|entry| {
+        let name = entry.ok()?.file_name();
+        let name = name.to_str()?;
+        name.starts_with("event")
+            .then(|| Path::new("/dev/input").join(name))
+    })
+}
+
+/// Reads events back from an evdev node.
+/// Mostly useful for checking what a virtual device actually sent.
+#[derive(Debug)]
+pub struct EvdevReader {
+    file: File,
+}
+
+impl EvdevReader {
+    /// Open an evdev node for reading.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().read(true).open(path)?;
+        Ok(Self { file })
+    }
+
+    /// Wait up to timeout for the node to become readable.
+    /// Returns false if nothing arrived in time.
+    pub fn wait(&self, timeout: Duration) -> Result<bool> {
+        let mut pollfd = libc::pollfd {
+            fd: self.file.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout = timeout.as_millis().min(i32::MAX as u128) as i32;
+        loop {
+            let ret = unsafe { libc::poll(&mut pollfd, 1, timeout) };
+            if ret >= 0 {
+                return Ok(ret > 0);
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+
+    /// Read a single event. Blocks until one is available.
+    pub fn read_event(&mut self) -> Result<input_event> {
+        let mut event = new_event(0, 0, 0);
+        let buf = unsafe {
+            std::slice::from_raw_parts_mut(
+                &mut event as *mut input_event as *mut u8,
+                std::mem::size_of::<input_event>(),
+            )
+        };
+        self.file.read_exact(buf)?;
+        Ok(event)
+    }
+
+    /// Read events up to and including the next SYN_REPORT.
+    /// Returns None if the timeout passes before a whole frame arrives.
+    pub fn read_frame(&mut self, timeout: Duration) -> Result<Option<Vec<input_event>>> {
+        let deadline = Instant::now() + timeout;
+        let mut frame = Vec::new();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !self.wait(remaining)? {
+                return Ok(None);
+            }
+            let event = self.read_event()?;
+            frame.push(event);
+            if event.type_ == EV_SYN && event.code == SYN_REPORT {
+                return Ok(Some(frame));
+            }
+        }
+    }
+}
@@ -0,0 +1,1103 @@
+use std::{
+    f32::consts::TAU,
+    fs::{File, OpenOptions},
+    io::{self, Result, Write},
+    os::fd::AsRawFd,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use libc::{
+    ff_constant_effect, ff_effect, ff_envelope, ff_periodic_effect, ff_ramp_effect,
+    ff_rumble_effect, input_event, uinput_ff_erase, uinput_ff_upload,
+};
+
+use crate::{
+    Device, UI_BEGIN_FF_ERASE, UI_BEGIN_FF_UPLOAD, UI_END_FF_ERASE, UI_END_FF_UPLOAD,
+    device::new_event,
+    key_codes::{
+        FF_AUTOCENTER, FF_CONSTANT, FF_CUSTOM, FF_GAIN, FF_PERIODIC, FF_RAMP, FF_RUMBLE,
+        FF_SAW_DOWN, FF_SAW_UP, FF_SINE, FF_SQUARE, FF_TRIANGLE, UI_FF_ERASE, UI_FF_UPLOAD,
+    },
+    key_types::{EV_FF, EV_UINPUT},
+    pause::PauseToken,
+};
+
+/// Attack and fade around a constant, periodic or ramp effect. Levels are
+/// fractions of full strength, 0.0 to 1.0.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Envelope {
+    /// How long it takes to go from attack_level to the effect's own level.
+    pub attack_length: Duration,
+    /// Strength at the very start.
+    pub attack_level: f32,
+    /// How long before the end the fade starts.
+    pub fade_length: Duration,
+    /// Strength at the very end.
+    pub fade_level: f32,
+}
+
+impl Envelope {
+    fn from_raw(raw: &ff_envelope) -> Self {
+        Self {
+            attack_length: Duration::from_millis(raw.attack_length.into()),
+            attack_level: level(raw.attack_level.min(0x7fff) as i16),
+            fade_length: Duration::from_millis(raw.fade_length.into()),
+            fade_level: level(raw.fade_level.min(0x7fff) as i16),
+        }
+    }
+
+    fn to_raw(self) -> ff_envelope {
+        ff_envelope {
+            attack_length: self.attack_length.as_millis().min(u16::MAX.into()) as u16,
+            attack_level: raw_level(self.attack_level.abs()) as u16,
+            fade_length: self.fade_length.as_millis().min(u16::MAX.into()) as u16,
+            fade_level: raw_level(self.fade_level.abs()) as u16,
+        }
+    }
+
+    /// Shape `level` at `elapsed` into an effect that lasts `length` (None
+    /// for effects that play until stopped, which never fade). Works on the
+    /// magnitude and keeps the sign, like the kernel's memless FF does.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    /// use uinput_rs::ff::Envelope;
+    ///
+    /// let envelope = Envelope {
+    ///     attack_length: Duration::from_millis(100),
+    ///     attack_level: 0.0,
+    ///     fade_length: Duration::from_millis(100),
+    ///     fade_level: 0.0,
+    /// };
+    /// let length = Some(Duration::from_millis(1000));
+    ///
+    /// assert_eq!(envelope.apply(-1.0, Duration::ZERO, length), -0.0);
+    /// assert_eq!(envelope.apply(-1.0, Duration::from_millis(50), length), -0.5);
+    /// assert_eq!(envelope.apply(-1.0, Duration::from_millis(500), length), -1.0);
+    /// assert_eq!(envelope.apply(-1.0, Duration::from_millis(950), length), -0.5);
+    /// ```
+    pub fn apply(&self, level: f32, elapsed: Duration, length: Option<Duration>) -> f32 {
+        let magnitude = level.abs();
+        let shaped = if !self.attack_length.is_zero() && elapsed < self.attack_length {
+            let progress = elapsed.as_secs_f32() / self.attack_length.as_secs_f32();
+            self.attack_level + (magnitude - self.attack_level) * progress
+        } else {
+            match length {
+                Some(length)
+                    if !self.fade_length.is_zero() && elapsed + self.fade_length >= length =>
+                {
+                    let remaining = length.saturating_sub(elapsed).as_secs_f32();
+                    self.fade_level
+                        + (magnitude - self.fade_level) * remaining / self.fade_length.as_secs_f32()
+                }
+                _ => magnitude,
+            }
+        };
+        shaped.copysign(level)
+    }
+}
+
+/// Wave shape of a periodic effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Waveform {
+    Square,
+    Triangle,
+    Sine,
+    SawUp,
+    SawDown,
+    /// FF_CUSTOM. The samples live in the uploading process, so there's
+    /// nothing to render.
+    Custom,
+}
+
+impl Waveform {
+    fn from_code(code: u16) -> Self {
+        match code {
+            FF_SQUARE => Waveform::Square,
+            FF_TRIANGLE => Waveform::Triangle,
+            FF_SINE => Waveform::Sine,
+            FF_SAW_UP => Waveform::SawUp,
+            FF_SAW_DOWN => Waveform::SawDown,
+            _ => Waveform::Custom,
+        }
+    }
+
+    /// FF_* code of the waveform.
+    pub fn code(self) -> u16 {
+        match self {
+            Waveform::Square => FF_SQUARE,
+            Waveform::Triangle => FF_TRIANGLE,
+            Waveform::Sine => FF_SINE,
+            Waveform::SawUp => FF_SAW_UP,
+            Waveform::SawDown => FF_SAW_DOWN,
+            Waveform::Custom => FF_CUSTOM,
+        }
+    }
+
+    /// Value of one cycle at `position` (0.0 to 1.0), from -1.0 to 1.0.
+    /// Custom is always 0.
+    pub fn sample(self, position: f32) -> f32 {
+        let x = position.rem_euclid(1.0);
+        match self {
+            Waveform::Square if x < 0.5 => 1.0,
+            Waveform::Square => -1.0,
+            Waveform::Triangle if x < 0.25 => 4.0 * x,
+            Waveform::Triangle if x < 0.75 => 2.0 - 4.0 * x,
+            Waveform::Triangle => 4.0 * x - 4.0,
+            Waveform::Sine => (TAU * x).sin(),
+            Waveform::SawUp => 2.0 * x - 1.0,
+            Waveform::SawDown => 1.0 - 2.0 * x,
+            Waveform::Custom => 0.0,
+        }
+    }
+}
+
+/// What an effect does. Levels are fractions of full strength, -1.0 to 1.0
+/// (rumble motors 0.0 to 1.0).
+#[derive(Debug, Clone, PartialEq)]
+pub enum EffectKind {
+    /// FF_RUMBLE, two motors.
+    Rumble { strong: f32, weak: f32 },
+    /// FF_CONSTANT, one steady force.
+    Constant { level: f32, envelope: Envelope },
+    /// FF_PERIODIC. `phase` is the fraction of a cycle the wave starts at.
+    Periodic {
+        waveform: Waveform,
+        period: Duration,
+        magnitude: f32,
+        offset: f32,
+        phase: f32,
+        envelope: Envelope,
+    },
+    /// FF_RAMP, a force going linearly from `start` to `end`.
+    Ramp {
+        start: f32,
+        end: f32,
+        envelope: Envelope,
+    },
+    /// Spring, damper and the other conditions, which depend on where the
+    /// wheel is rather than on time. Holds the FF_* code.
+    Other(u16),
+}
+
+/// A force feedback effect as the application uploaded it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Effect {
+    /// Effect ID the kernel refers to it by.
+    pub id: i16,
+    pub kind: EffectKind,
+    /// Direction of the force, 0x0000 down, 0x4000 left, 0x8000 up,
+    /// 0xc000 right.
+    pub direction: u16,
+    /// How long one playback lasts, None until stopped.
+    pub length: Option<Duration>,
+    /// Wait before it starts.
+    pub delay: Duration,
+}
+
+impl Effect {
+    /// An effect that starts right away, plays until stopped and pushes
+    /// down.
+    pub fn new(kind: EffectKind) -> Self {
+        Self {
+            id: -1,
+            kind,
+            direction: 0,
+            length: None,
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Decode the kernel's `struct ff_effect`, as found in an upload.
+    pub fn from_raw(raw: &ff_effect) -> Self {
+        let union = raw.u.as_ptr() as *const u8;
+        // The union is big enough for every member and type_ says which one
+        // the application filled in.
+        let kind = unsafe {
+            match raw.type_ {
+                FF_RUMBLE => {
+                    let rumble = std::ptr::read_unaligned(union as *const ff_rumble_effect);
+                    EffectKind::Rumble {
+                        strong: rumble.strong_magnitude as f32 / u16::MAX as f32,
+                        weak: rumble.weak_magnitude as f32 / u16::MAX as f32,
+                    }
+                }
+                FF_CONSTANT => {
+                    let constant = std::ptr::read_unaligned(union as *const ff_constant_effect);
+                    EffectKind::Constant {
+                        level: level(constant.level),
+                        envelope: Envelope::from_raw(&constant.envelope),
+                    }
+                }
+                FF_PERIODIC => {
+                    let periodic = std::ptr::read_unaligned(union as *const ff_periodic_effect);
+                    EffectKind::Periodic {
+                        waveform: Waveform::from_code(periodic.waveform),
+                        period: Duration::from_millis(periodic.period.into()),
+                        magnitude: level(periodic.magnitude),
+                        offset: level(periodic.offset),
+                        phase: periodic.phase as f32 / (u16::MAX as f32 + 1.0),
+                        envelope: Envelope::from_raw(&periodic.envelope),
+                    }
+                }
+                FF_RAMP => {
+                    let ramp = std::ptr::read_unaligned(union as *const ff_ramp_effect);
+                    EffectKind::Ramp {
+                        start: level(ramp.start_level),
+                        end: level(ramp.end_level),
+                        envelope: Envelope::from_raw(&ramp.envelope),
+                    }
+                }
+                other => EffectKind::Other(other),
+            }
+        };
+        Self {
+            id: raw.id,
+            kind,
+            direction: raw.direction,
+            length: match raw.replay.length {
+                0 => None,
+                ms => Some(Duration::from_millis(ms.into())),
+            },
+            delay: Duration::from_millis(raw.replay.delay.into()),
+        }
+    }
+
+    /// Encode back into a `struct ff_effect`, for uploading to a real
+    /// device or faking an upload with MockBackend. Other effects come out
+    /// with their type and nothing else.
+    pub fn to_raw(&self) -> ff_effect {
+        let mut raw: ff_effect = unsafe { std::mem::zeroed() };
+        raw.id = self.id;
+        raw.direction = self.direction;
+        raw.replay.length = self
+            .length
+            .map_or(0, |length| length.as_millis().min(u16::MAX.into()) as u16);
+        raw.replay.delay = self.delay.as_millis().min(u16::MAX.into()) as u16;
+        let union = raw.u.as_mut_ptr() as *mut u8;
+        // Same as from_raw, every member fits.
+        unsafe {
+            raw.type_ = match self.kind {
+                EffectKind::Rumble { strong, weak } => {
+                    let rumble = ff_rumble_effect {
+                        strong_magnitude: (strong.clamp(0.0, 1.0) * u16::MAX as f32) as u16,
+                        weak_magnitude: (weak.clamp(0.0, 1.0) * u16::MAX as f32) as u16,
+                    };
+                    std::ptr::write_unaligned(union as *mut ff_rumble_effect, rumble);
+                    FF_RUMBLE
+                }
+                EffectKind::Constant { level, envelope } => {
+                    let constant = ff_constant_effect {
+                        level: raw_level(level),
+                        envelope: envelope.to_raw(),
+                    };
+                    std::ptr::write_unaligned(union as *mut ff_constant_effect, constant);
+                    FF_CONSTANT
+                }
+                EffectKind::Periodic {
+                    waveform,
+                    period,
+                    magnitude,
+                    offset,
+                    phase,
+                    envelope,
+                } => {
+                    let periodic = ff_periodic_effect {
+                        waveform: waveform.code(),
+                        period: period.as_millis().min(u16::MAX.into()) as u16,
+                        magnitude: raw_level(magnitude),
+                        offset: raw_level(offset),
+                        phase: (phase.rem_euclid(1.0) * (u16::MAX as f32 + 1.0)) as u16,
+                        envelope: envelope.to_raw(),
+                        custom_len: 0,
+                        custom_data: std::ptr::null_mut(),
+                    };
+                    std::ptr::write_unaligned(union as *mut ff_periodic_effect, periodic);
+                    FF_PERIODIC
+                }
+                EffectKind::Ramp {
+                    start,
+                    end,
+                    envelope,
+                } => {
+                    let ramp = ff_ramp_effect {
+                        start_level: raw_level(start),
+                        end_level: raw_level(end),
+                        envelope: envelope.to_raw(),
+                    };
+                    std::ptr::write_unaligned(union as *mut ff_ramp_effect, ramp);
+                    FF_RAMP
+                }
+                EffectKind::Other(code) => code,
+            };
+        }
+        raw
+    }
+
+    /// Strength of a constant, periodic or ramp effect `elapsed` after it
+    /// was started, delay included. 0 before the delay is over and after it
+    /// ended, None for effects that don't play out over time.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    /// use uinput_rs::ff::{Effect, EffectKind, Envelope};
+    ///
+    /// let mut ramp = Effect::new(EffectKind::Ramp { start: 0.0, end: 1.0, envelope: Envelope::default() });
+    /// ramp.length = Some(Duration::from_secs(2));
+    /// ramp.delay = Duration::from_secs(1);
+    ///
+    /// assert_eq!(ramp.level_at(Duration::from_millis(500)), Some(0.0));
+    /// assert_eq!(ramp.level_at(Duration::from_secs(2)), Some(0.5));
+    /// assert_eq!(ramp.level_at(Duration::from_secs(4)), Some(0.0));
+    /// ```
+    pub fn level_at(&self, elapsed: Duration) -> Option<f32> {
+        let Some(elapsed) = elapsed.checked_sub(self.delay) else {
+            return matches!(
+                self.kind,
+                EffectKind::Constant { .. } | EffectKind::Periodic { .. } | EffectKind::Ramp { .. }
+            )
+            .then_some(0.0);
+        };
+        let over = self.length.is_some_and(|length| elapsed >= length);
+        let level = match &self.kind {
+            EffectKind::Constant { level, envelope } => {
+                envelope.apply(*level, elapsed, self.length)
+            }
+            EffectKind::Periodic {
+                waveform,
+                period,
+                magnitude,
+                offset,
+                phase,
+                envelope,
+            } => {
+                let position = match period.as_secs_f32() {
+                    0.0 => *phase,
+                    period => elapsed.as_secs_f32() / period + phase,
+                };
+                let magnitude = envelope.apply(*magnitude, elapsed, self.length);
+                (offset + magnitude * waveform.sample(position)).clamp(-1.0, 1.0)
+            }
+            EffectKind::Ramp {
+                start,
+                end,
+                envelope,
+            } => {
+                let progress = match self.length {
+                    Some(length) if !length.is_zero() => {
+                        (elapsed.as_secs_f32() / length.as_secs_f32()).min(1.0)
+                    }
+                    _ => 0.0,
+                };
+                envelope.apply(start + (end - start) * progress, elapsed, self.length)
+            }
+            EffectKind::Rumble { .. } | EffectKind::Other(_) => return None,
+        };
+        Some(if over { 0.0 } else { level })
+    }
+
+    /// level_at split into x and y along the effect's direction, the way
+    /// the kernel does it for memless devices. A wheel only cares about x.
+    pub fn force_at(&self, elapsed: Duration) -> Option<(f32, f32)> {
+        let level = self.level_at(elapsed)?;
+        let angle = self.direction as f32 / (u16::MAX as f32 + 1.0) * TAU;
+        Some((angle.sin() * level, -angle.cos() * level))
+    }
+
+    fn cycle(&self) -> Option<Duration> {
+        self.length.map(|length| self.delay + length)
+    }
+}
+
+fn level(raw: i16) -> f32 {
+    (raw as f32 / i16::MAX as f32).max(-1.0)
+}
+
+fn raw_level(level: f32) -> i16 {
+    (level.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+}
+
+#[derive(Debug)]
+struct Playing {
+    effect: Effect,
+    started: Instant,
+    count: u32,
+}
+
+impl Playing {
+    /// Time into the current repetition, None when all of them are done.
+    fn elapsed(&self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.started);
+        match self.effect.cycle() {
+            Some(cycle) if cycle.is_zero() => None,
+            Some(cycle) => {
+                let repetition = elapsed.as_nanos() / cycle.as_nanos();
+                (repetition < self.count as u128)
+                    .then(|| Duration::from_nanos((elapsed.as_nanos() % cycle.as_nanos()) as u64))
+            }
+            None => Some(elapsed),
+        }
+    }
+}
+
+/// Mixes the effects an application is playing into one force and one
+/// pair of rumble strengths, for forwarding to wherever the device side
+/// renders them. Feed it the EV_FF play and gain events read back from the
+/// device and sample it as often as the output needs.
+///
+/// Example:
+/// ```rust
+/// use std::time::{Duration, Instant};
+/// use uinput_rs::ff::{Effect, EffectKind, Envelope, Player};
+///
+/// let mut push = Effect::new(EffectKind::Constant { level: 0.8, envelope: Envelope::default() });
+/// push.id = 0;
+/// push.direction = 0xc000;
+/// push.length = Some(Duration::from_millis(100));
+///
+/// let start = Instant::now();
+/// let mut player = Player::new();
+/// player.play(push, 2, start);
+/// player.set_gain(0.5);
+///
+/// let (x, _) = player.force_at(start + Duration::from_millis(150));
+/// assert!((x + 0.4).abs() < 1e-3);
+/// assert!(player.is_playing(0));
+///
+/// player.force_at(start + Duration::from_millis(200));
+/// assert!(!player.is_playing(0)); // both repetitions done
+/// ```
+#[derive(Debug)]
+pub struct Player {
+    playing: Vec<Playing>,
+    gain: f32,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player {
+    /// Nothing playing, full gain.
+    pub fn new() -> Self {
+        Self {
+            playing: Vec::new(),
+            gain: 1.0,
+        }
+    }
+
+    /// Start `effect` `count` times in a row, the value of the EV_FF event
+    /// that asked for it. 0 stops it, and starting it again restarts it.
+    pub fn play(&mut self, effect: Effect, count: i32, now: Instant) {
+        self.stop(effect.id);
+        if count > 0 {
+            self.playing.push(Playing {
+                effect,
+                started: now,
+                count: count as u32,
+            });
+        }
+    }
+
+    /// Stop an effect by ID.
+    pub fn stop(&mut self, id: i16) {
+        self.playing.retain(|playing| playing.effect.id != id);
+    }
+
+    /// Stop everything.
+    pub fn stop_all(&mut self) {
+        self.playing.clear();
+    }
+
+    /// Overall strength, 0.0 to 1.0. FF_GAIN events carry it as 0 to 0xffff.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain.clamp(0.0, 1.0);
+    }
+
+    /// Whether an effect is still playing, as of the last sample.
+    pub fn is_playing(&self, id: i16) -> bool {
+        self.playing.iter().any(|playing| playing.effect.id == id)
+    }
+
+    /// Sum of every playing constant, periodic and ramp effect, x and y,
+    /// clamped to -1.0 to 1.0. Forgets effects that are done.
+    pub fn force_at(&mut self, now: Instant) -> (f32, f32) {
+        self.forget_finished(now);
+        let (x, y) = self
+            .playing
+            .iter()
+            .filter_map(|playing| playing.effect.force_at(playing.elapsed(now)?))
+            .fold((0.0, 0.0), |(x, y), (dx, dy)| (x + dx, y + dy));
+        (
+            (x * self.gain).clamp(-1.0, 1.0),
+            (y * self.gain).clamp(-1.0, 1.0),
+        )
+    }
+
+    /// Strong and weak motor strength of every playing rumble effect, summed
+    /// and clamped to 1.0. Forgets effects that are done.
+    pub fn rumble_at(&mut self, now: Instant) -> (f32, f32) {
+        self.forget_finished(now);
+        let (strong, weak) = self
+            .playing
+            .iter()
+            .filter_map(|playing| match playing.effect.kind {
+                EffectKind::Rumble { strong, weak } => {
+                    let elapsed = playing.elapsed(now)?;
+                    (elapsed >= playing.effect.delay).then_some((strong, weak))
+                }
+                _ => None,
+            })
+            .fold((0.0, 0.0), |(s, w), (ds, dw)| (s + ds, w + dw));
+        ((strong * self.gain).min(1.0), (weak * self.gain).min(1.0))
+    }
+
+    fn forget_finished(&mut self, now: Instant) {
+        self.playing
+            .retain(|playing| playing.elapsed(now).is_some());
+    }
+}
+
+/// Something the application asked of the device's force feedback, as
+/// EffectTable saw it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FfRequest {
+    /// An effect was uploaded, into a free slot or over an effect of the
+    /// same kind.
+    Upload {
+        effect: Effect,
+        replaced: Option<Effect>,
+    },
+    /// An upload was refused with this errno, the kernel passes it on to
+    /// the application.
+    Refused { effect: Effect, errno: i32 },
+    /// An effect was erased. Erasing stops it if it was playing.
+    Erase { effect: Effect, was_playing: bool },
+    /// Start an effect, `count` times in a row.
+    Play { effect: Effect, count: i32 },
+    /// Stop an effect.
+    Stop { effect: Effect },
+    /// Overall strength changed, 0.0 to 1.0.
+    Gain(f32),
+    /// Autocenter strength changed, 0.0 to 1.0.
+    Autocenter(f32),
+}
+
+/// One uploaded effect.
+struct Slot {
+    effect: Effect,
+    raw: ff_effect,
+}
+
+impl std::fmt::Debug for Slot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.effect.fmt(f)
+    }
+}
+
+/// The device's effect slots, kept the way the kernel's force feedback
+/// core keeps them: IDs from 0 to ff_effects_max - 1, new effects in the
+/// lowest free slot, updates only over an effect of the same kind, and
+/// erasing stops a playing effect first. Answering uploads and erases
+/// through this keeps the application, the kernel and the device agreeing
+/// on what's where.
+///
+/// The application waits in EVIOCSFF until its upload is answered, so call
+/// process() often.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     ff::{Effect, EffectKind, EffectTable, FfRequest},
+///     key_events::FF_RUMBLE_EVENT,
+///     key_types::EV_FF,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let mut info = UInputUserDevice::default();
+/// info.ff_effects_max = 2;
+/// let device = Device::with_backend(mock.clone(), &[FF_RUMBLE_EVENT], &info).unwrap();
+/// let mut table = EffectTable::for_device(&device);
+///
+/// let mut rumble = Effect::new(EffectKind::Rumble { strong: 1.0, weak: 0.5 });
+/// rumble.id = 0;
+/// mock.push_ff_upload(rumble.to_raw());
+/// mock.push_incoming(EV_FF, 0, 1);
+/// mock.push_ff_erase(0);
+/// rumble.id = 5; // past ff_effects_max
+/// mock.push_ff_upload(rumble.to_raw());
+///
+/// let requests = table.process(&device).unwrap();
+/// assert!(matches!(requests[0], FfRequest::Upload { replaced: None, .. }));
+/// assert!(matches!(requests[1], FfRequest::Play { count: 1, .. }));
+/// assert!(matches!(requests[2], FfRequest::Erase { was_playing: true, .. }));
+/// assert!(matches!(requests[3], FfRequest::Refused { errno: 22, .. }));
+/// assert_eq!(mock.ff_results(), [0, 0, -22]);
+/// assert!(table.is_empty());
+/// ```
+#[derive(Debug)]
+pub struct EffectTable {
+    slots: Vec<Option<Slot>>,
+    playing: Vec<bool>,
+    player: Player,
+}
+
+impl EffectTable {
+    /// Room for `capacity` effects. The kernel never allows more than
+    /// FF_GAIN (96), so neither does this.
+    pub fn new(capacity: u32) -> Self {
+        let capacity = capacity.min(FF_GAIN as u32) as usize;
+        Self {
+            slots: (0..capacity).map(|_| None).collect(),
+            playing: vec![false; capacity],
+            player: Player::new(),
+        }
+    }
+
+    /// Room for as many effects as the device said it holds.
+    pub fn for_device(device: &Device) -> Self {
+        Self::new(device.ff_effects_max())
+    }
+
+    /// Number of slots.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Number of uploaded effects.
+    pub fn len(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+
+    /// Whether no effect is uploaded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The effect in a slot.
+    pub fn get(&self, id: i16) -> Option<&Effect> {
+        self.slot(id).map(|slot| &slot.effect)
+    }
+
+    /// The effect in a slot as the application uploaded it.
+    pub fn raw(&self, id: i16) -> Option<&ff_effect> {
+        self.slot(id).map(|slot| &slot.raw)
+    }
+
+    /// Every uploaded effect, by ID.
+    pub fn iter(&self) -> impl Iterator<Item = &Effect> {
+        self.slots.iter().flatten().map(|slot| &slot.effect)
+    }
+
+    /// Whether the application last started the effect rather than stopped
+    /// it. Finite effects run out on their own, the player knows when.
+    pub fn is_playing(&self, id: i16) -> bool {
+        self.slot(id).is_some() && self.playing[id as usize]
+    }
+
+    /// Mixes everything the application is playing.
+    pub fn player(&mut self) -> &mut Player {
+        &mut self.player
+    }
+
+    fn slot(&self, id: i16) -> Option<&Slot> {
+        self.slots.get(usize::try_from(id).ok()?)?.as_ref()
+    }
+
+    /// Store an effect under the kernel's rules. ID -1 takes the lowest
+    /// free slot (ENOSPC when there's none), any other ID has to be an
+    /// uploaded effect of the same kind (EINVAL otherwise). Returns the ID
+    /// and the effect it replaced.
+    pub fn upload(&mut self, raw: ff_effect) -> Result<(i16, Option<Effect>)> {
+        let mut raw = raw;
+        if raw.id == -1 {
+            let free = self.slots.iter().position(Option::is_none);
+            raw.id = free.ok_or_else(|| io::Error::from_raw_os_error(libc::ENOSPC))? as i16;
+        }
+        let effect = Effect::from_raw(&raw);
+        let index = usize::try_from(raw.id)
+            .ok()
+            .filter(|&index| index < self.slots.len())
+            .ok_or_else(|| io::Error::from_raw_os_error(libc::EINVAL))?;
+        let replaced = match &self.slots[index] {
+            Some(old) if !compatible(&old.effect.kind, &effect.kind) => {
+                return Err(io::Error::from_raw_os_error(libc::EINVAL));
+            }
+            old => old.as_ref().map(|old| old.effect.clone()),
+        };
+        self.slots[index] = Some(Slot { effect, raw });
+        Ok((raw.id, replaced))
+    }
+
+    /// Free a slot, stopping the effect first. EINVAL for an empty slot.
+    /// Returns the effect and whether it was playing.
+    pub fn erase(&mut self, id: i16) -> Result<(Effect, bool)> {
+        let slot = usize::try_from(id)
+            .ok()
+            .and_then(|index| self.slots.get_mut(index)?.take())
+            .ok_or_else(|| io::Error::from_raw_os_error(libc::EINVAL))?;
+        let was_playing = std::mem::take(&mut self.playing[id as usize]);
+        self.player.stop(id);
+        Ok((slot.effect, was_playing))
+    }
+
+    /// Answer every force feedback request the device has pending.
+    /// Reads the device's feedback, so LED changes get noticed as well.
+    pub fn process(&mut self, device: &Device) -> Result<Vec<FfRequest>> {
+        let mut requests = Vec::new();
+        for event in device.read_feedback()? {
+            requests.extend(self.handle(device, &event)?);
+        }
+        Ok(requests)
+    }
+
+    /// Act on one event read back from the device: answer uploads and
+    /// erases, and track playback and gain. None for events that aren't
+    /// force feedback or refer to empty slots.
+    pub fn handle(&mut self, device: &Device, event: &input_event) -> Result<Option<FfRequest>> {
+        let full = |value: i32| value.clamp(0, u16::MAX.into()) as f32 / u16::MAX as f32;
+        Ok(match (event.type_, event.code) {
+            (EV_UINPUT, UI_FF_UPLOAD) => Some(self.answer_upload(device, event.value as u32)?),
+            (EV_UINPUT, UI_FF_ERASE) => self.answer_erase(device, event.value as u32)?,
+            (EV_FF, FF_GAIN) => {
+                self.player.set_gain(full(event.value));
+                Some(FfRequest::Gain(full(event.value)))
+            }
+            (EV_FF, FF_AUTOCENTER) => Some(FfRequest::Autocenter(full(event.value))),
+            (EV_FF, id) => {
+                let id = id as i16;
+                let Some(effect) = self.get(id).cloned() else {
+                    return Ok(None);
+                };
+                self.playing[id as usize] = event.value > 0;
+                self.player
+                    .play(effect.clone(), event.value, Instant::now());
+                Some(match event.value {
+                    0 => FfRequest::Stop { effect },
+                    count => FfRequest::Play { effect, count },
+                })
+            }
+            _ => None,
+        })
+    }
+
+    fn answer_upload(&mut self, device: &Device, request_id: u32) -> Result<FfRequest> {
+        let mut upload: uinput_ff_upload = unsafe { std::mem::zeroed() };
+        upload.request_id = request_id;
+        unsafe { device.ioctl(UI_BEGIN_FF_UPLOAD, &mut upload as *mut _ as u64) }?;
+
+        let effect = Effect::from_raw(&upload.effect);
+        let request = match self.upload(upload.effect) {
+            Ok((_, replaced)) => FfRequest::Upload { effect, replaced },
+            Err(err) => {
+                let errno = err.raw_os_error().unwrap_or(libc::EINVAL);
+                upload.retval = -errno;
+                FfRequest::Refused { effect, errno }
+            }
+        };
+        unsafe { device.ioctl(UI_END_FF_UPLOAD, &upload as *const _ as u64) }?;
+        Ok(request)
+    }
+
+    fn answer_erase(&mut self, device: &Device, request_id: u32) -> Result<Option<FfRequest>> {
+        let mut erase: uinput_ff_erase = unsafe { std::mem::zeroed() };
+        erase.request_id = request_id;
+        unsafe { device.ioctl(UI_BEGIN_FF_ERASE, &mut erase as *mut _ as u64) }?;
+
+        let request = match self.erase(erase.effect_id as i16) {
+            Ok((effect, was_playing)) => Some(FfRequest::Erase {
+                effect,
+                was_playing,
+            }),
+            Err(err) => {
+                erase.retval = -err.raw_os_error().unwrap_or(libc::EINVAL);
+                None
+            }
+        };
+        unsafe { device.ioctl(UI_END_FF_ERASE, &erase as *const _ as u64) }?;
+        Ok(request)
+    }
+}
+
+/// The kernel only lets an upload replace an effect of the same type, and
+/// of the same waveform for periodic ones.
+fn compatible(old: &EffectKind, new: &EffectKind) -> bool {
+    match (old, new) {
+        (EffectKind::Periodic { waveform: a, .. }, EffectKind::Periodic { waveform: b, .. }) => {
+            a == b
+        }
+        (EffectKind::Other(a), EffectKind::Other(b)) => a == b,
+        _ => std::mem::discriminant(old) == std::mem::discriminant(new),
+    }
+}
+
+/// _IOW('E', 0x80, struct ff_effect)
+const EVIOCSFF: u64 = 0x40004580 | (std::mem::size_of::<ff_effect>() as u64) << 16;
+/// _IOW('E', 0x81, int)
+const EVIOCRMFF: u64 = 0x40044581;
+
+/// Plays the effects a virtual device gets on a real evdev device, for
+/// feeling what a game asks for. Effect IDs are translated, the real
+/// device picks its own.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::{
+///     Device, UInputUserDevice,
+///     ff::{FfMirror, FfRecorder},
+///     key_events::FF_RUMBLE_EVENT,
+/// };
+///
+/// let mut info = UInputUserDevice::with_name("Virtual pad");
+/// info.ff_effects_max = 16;
+/// let device = Device::new_custom(&[FF_RUMBLE_EVENT], &info).unwrap();
+///
+/// let mirror = FfMirror::open("/dev/input/by-id/usb-Real_Pad-event-joystick").unwrap();
+/// let mut recorder = FfRecorder::new(&device).mirror(mirror);
+/// loop {
+///     for recorded in recorder.process(&device).unwrap() {
+///         println!("{:>8.3}s {:?}", recorded.at.as_secs_f32(), recorded.request);
+///     }
+///     std::thread::sleep(std::time::Duration::from_millis(5));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct FfMirror {
+    file: File,
+    ids: Vec<(i16, i16)>,
+}
+
+impl FfMirror {
+    /// Open an evdev node read/write.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Ok(Self::from_file(file))
+    }
+
+    /// Use an already opened evdev node, it has to be writable.
+    pub fn from_file(file: File) -> Self {
+        Self {
+            file,
+            ids: Vec::new(),
+        }
+    }
+
+    fn real_id(&self, id: i16) -> Option<i16> {
+        self.ids
+            .iter()
+            .find(|(virtual_id, _)| *virtual_id == id)
+            .map(|(_, real_id)| *real_id)
+    }
+
+    /// Do the same to the real device. `table` has to have seen the request
+    /// already, uploads are copied from it.
+    pub fn forward(&mut self, request: &FfRequest, table: &EffectTable) -> Result<()> {
+        match request {
+            FfRequest::Upload { effect, .. } => {
+                let Some(raw) = table.raw(effect.id) else {
+                    return Ok(());
+                };
+                let mut raw = *raw;
+                if raw.type_ == FF_PERIODIC {
+                    // custom_data points into the game's memory, not ours.
+                    let union = raw.u.as_mut_ptr() as *mut ff_periodic_effect;
+                    unsafe {
+                        let mut periodic = std::ptr::read_unaligned(union);
+                        periodic.custom_len = 0;
+                        periodic.custom_data = std::ptr::null_mut();
+                        std::ptr::write_unaligned(union, periodic);
+                    }
+                }
+                raw.id = self.real_id(effect.id).unwrap_or(-1);
+                let ret = unsafe { libc::ioctl(self.file.as_raw_fd(), EVIOCSFF as _, &mut raw) };
+                if ret < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                self.ids.retain(|(virtual_id, _)| *virtual_id != effect.id);
+                self.ids.push((effect.id, raw.id));
+            }
+            FfRequest::Erase { effect, .. } => {
+                let Some(real_id) = self.real_id(effect.id) else {
+                    return Ok(());
+                };
+                self.ids.retain(|(virtual_id, _)| *virtual_id != effect.id);
+                let ret = unsafe {
+                    libc::ioctl(
+                        self.file.as_raw_fd(),
+                        EVIOCRMFF as _,
+                        real_id as libc::c_int,
+                    )
+                };
+                if ret < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            FfRequest::Play { effect, count } => {
+                if let Some(real_id) = self.real_id(effect.id) {
+                    self.write(real_id as u16, *count)?;
+                }
+            }
+            FfRequest::Stop { effect } => {
+                if let Some(real_id) = self.real_id(effect.id) {
+                    self.write(real_id as u16, 0)?;
+                }
+            }
+            FfRequest::Gain(gain) => self.write(FF_GAIN, (gain * u16::MAX as f32) as i32)?,
+            FfRequest::Autocenter(strength) => {
+                self.write(FF_AUTOCENTER, (strength * u16::MAX as f32) as i32)?
+            }
+            FfRequest::Refused { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn write(&self, code: u16, value: i32) -> Result<()> {
+        let event = new_event(EV_FF, code, value);
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                &event as *const input_event as *const u8,
+                std::mem::size_of::<input_event>(),
+            )
+        };
+        (&self.file).write_all(bytes)
+    }
+}
+
+/// A force feedback request and how long after the recorder started it
+/// came in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub at: Duration,
+    pub request: FfRequest,
+}
+
+/// Ready made force feedback handler for checking what a game sends:
+/// answers uploads and erases through an EffectTable, keeps every request
+/// with a timestamp, logs them with the log feature, and optionally plays
+/// them on a real device through FfMirror. Mirroring failures are logged
+/// and otherwise ignored, the game still gets its answers.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     ff::{Effect, EffectKind, FfRecorder},
+///     key_events::FF_RUMBLE_EVENT,
+///     key_types::EV_FF,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let mut info = UInputUserDevice::default();
+/// info.ff_effects_max = 4;
+/// let device = Device::with_backend(mock.clone(), &[FF_RUMBLE_EVENT], &info).unwrap();
+/// let mut recorder = FfRecorder::new(&device);
+///
+/// // What a game would do on a hit.
+/// let mut rumble = Effect::new(EffectKind::Rumble { strong: 0.0, weak: 1.0 });
+/// rumble.id = 0;
+/// mock.push_ff_upload(rumble.to_raw());
+/// mock.push_incoming(EV_FF, 0, 1);
+/// recorder.process(&device).unwrap();
+///
+/// let rumbles: Vec<(f32, f32)> = recorder.rumbles().map(|(_, strong, weak)| (strong, weak)).collect();
+/// assert_eq!(rumbles, [(0.0, 1.0)]);
+/// assert_eq!(recorder.requests().len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct FfRecorder {
+    table: EffectTable,
+    started: Instant,
+    requests: Vec<RecordedRequest>,
+    mirror: Option<FfMirror>,
+    pause: Option<PauseToken>,
+}
+
+impl FfRecorder {
+    /// Record the requests a device gets, with its effect slots.
+    pub fn new(device: &Device) -> Self {
+        Self {
+            table: EffectTable::for_device(device),
+            started: Instant::now(),
+            requests: Vec::new(),
+            mirror: None,
+            pause: None,
+        }
+    }
+
+    /// Also play everything on a real device.
+    pub fn mirror(mut self, mirror: FfMirror) -> Self {
+        self.mirror = Some(mirror);
+        self
+    }
+
+    /// While the token is paused, requests are still answered so the game
+    /// doesn't hang, but nothing is recorded and only uploads and erases
+    /// reach the mirror, which keeps its slots in step without playing.
+    pub fn pause_token(mut self, token: PauseToken) -> Self {
+        self.pause = Some(token);
+        self
+    }
+
+    /// Answer and record what the device has pending. Returns the requests
+    /// that just came in.
+    pub fn process(&mut self, device: &Device) -> Result<&[RecordedRequest]> {
+        let new = self.requests.len();
+        for request in self.table.process(device)? {
+            let paused = self.pause.as_ref().is_some_and(PauseToken::is_paused);
+            if paused && !matches!(request, FfRequest::Upload { .. } | FfRequest::Erase { .. }) {
+                continue;
+            }
+            if let Some(mirror) = &mut self.mirror
+                && let Err(_err) = mirror.forward(&request, &self.table)
+            {
+                #[cfg(feature = "log")]
+                log::warn!("ff mirror: {_err}");
+            }
+            if paused {
+                continue;
+            }
+            #[cfg(feature = "log")]
+            log::info!("ff: {request:?}");
+            self.requests.push(RecordedRequest {
+                at: self.started.elapsed(),
+                request,
+            });
+        }
+        Ok(&self.requests[new..])
+    }
+
+    /// Everything recorded so far.
+    pub fn requests(&self) -> &[RecordedRequest] {
+        &self.requests
+    }
+
+    /// Hand over the recording and start a new one.
+    pub fn take(&mut self) -> Vec<RecordedRequest> {
+        std::mem::take(&mut self.requests)
+    }
+
+    /// When rumble effects were started, with their strong and weak motor
+    /// strength.
+    pub fn rumbles(&self) -> impl Iterator<Item = (Duration, f32, f32)> + '_ {
+        self.requests
+            .iter()
+            .filter_map(|recorded| match &recorded.request {
+                FfRequest::Play { effect, .. } => match effect.kind {
+                    EffectKind::Rumble { strong, weak } => Some((recorded.at, strong, weak)),
+                    _ => None,
+                },
+                _ => None,
+            })
+    }
+
+    /// The effect slots as the game sees them.
+    pub fn table(&self) -> &EffectTable {
+        &self.table
+    }
+}
@@ -0,0 +1,202 @@
+use std::{io::Result, time::Instant};
+
+use crate::{
+    Device, SYN_REPORT,
+    device::new_event,
+    devices::{GAMEPAD_EVENTS, GAMEPAD_ID, gamepad_info},
+    ff::{EffectTable, FfRequest},
+    key_codes::{ABS_HAT0X, ABS_HAT0Y, ABS_RX, ABS_RY, ABS_RZ, ABS_X, ABS_Y, ABS_Z},
+    key_events::{FF_GAIN_EVENT, FF_RUMBLE_EVENT},
+    key_types::{EV_ABS, EV_KEY, EV_SYN},
+};
+
+/// Effects a Gamepad::new pad holds, like xpad.
+const EFFECTS: u32 = 16;
+
+/// One of the two sticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stick {
+    /// ABS_X and ABS_Y.
+    Left,
+    /// ABS_RX and ABS_RY.
+    Right,
+}
+
+/// One of the two analog triggers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    /// ABS_Z.
+    Left,
+    /// ABS_RZ.
+    Right,
+}
+
+/// An Xbox style gamepad with axes in -1.0..=1.0 (sticks) and 0.0..=1.0
+/// (triggers) instead of device units, and rumble answered for you. Every
+/// call is its own frame.
+///
+/// Stick y is down positive, like the kernel reports it.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend,
+///     devices::{GAMEPAD_EVENTS, GAMEPAD_ID, gamepad_info},
+///     ff::{Effect, EffectKind, FfRequest},
+///     gamepad::{Gamepad, Stick, Trigger},
+///     key_codes::{ABS_X, ABS_Z, BTN_A},
+///     key_events::FF_RUMBLE_EVENT,
+///     key_types::{EV_ABS, EV_FF},
+/// };
+///
+/// let mock = MockBackend::new();
+/// let mut info = gamepad_info("pad", GAMEPAD_ID);
+/// info.ff_effects_max = 4;
+/// let events: Vec<_> = GAMEPAD_EVENTS.iter().copied().chain([FF_RUMBLE_EVENT]).collect();
+/// let device = Device::with_backend(mock.clone(), &events, &info).unwrap();
+/// let mut pad = Gamepad::from_device(device);
+///
+/// pad.set_stick(Stick::Left, 1.0, 0.0).unwrap();
+/// pad.set_trigger(Trigger::Left, 0.5).unwrap();
+/// pad.tap(BTN_A).unwrap();
+/// let axes: Vec<(u16, i32)> = mock.events().iter().filter(|e| e.type_ == EV_ABS).map(|e| (e.code, e.value)).collect();
+/// assert_eq!(axes[0], (ABS_X, 32767));
+/// assert_eq!(axes[2], (ABS_Z, 128));
+///
+/// // A game uploads a rumble and plays it.
+/// let mut rumble = Effect::new(EffectKind::Rumble { strong: 1.0, weak: 0.25 });
+/// rumble.id = -1;
+/// mock.push_ff_upload(rumble.to_raw());
+/// mock.push_incoming(EV_FF, 0, 1);
+/// let requests: Vec<_> = pad.rumble_requests().unwrap().collect();
+/// assert!(matches!(requests[1], FfRequest::Play { .. }));
+/// let (strong, weak) = pad.rumble();
+/// assert_eq!(strong, 1.0);
+/// assert!((weak - 0.25).abs() < 0.001);
+/// ```
+#[derive(Debug)]
+pub struct Gamepad {
+    device: Device,
+    effects: EffectTable,
+}
+
+impl Gamepad {
+    /// Create the gamepad preset through /dev/uinput, with FF_RUMBLE and
+    /// room for 16 effects.
+    pub fn new(name: &str) -> Result<Self> {
+        let mut info = gamepad_info(name, GAMEPAD_ID);
+        info.ff_effects_max = EFFECTS;
+        let events: Vec<_> = GAMEPAD_EVENTS
+            .iter()
+            .copied()
+            .chain([FF_RUMBLE_EVENT, FF_GAIN_EVENT])
+            .collect();
+        Ok(Self::from_device(Device::new_custom(&events, &info)?))
+    }
+
+    /// Wrap a device with the gamepad preset's axes, e.g. one from
+    /// sdl::gamepad.
+    pub fn from_device(device: Device) -> Self {
+        Self {
+            effects: EffectTable::for_device(&device),
+            device,
+        }
+    }
+
+    /// Press a button (BTN_A, BTN_TL, ...) and leave it down.
+    pub fn press(&self, button: u16) -> Result<()> {
+        self.frame(&[(EV_KEY, button, 1)])
+    }
+
+    /// Let go of a button.
+    pub fn release(&self, button: u16) -> Result<()> {
+        self.frame(&[(EV_KEY, button, 0)])
+    }
+
+    /// Press and release a button.
+    pub fn tap(&self, button: u16) -> Result<()> {
+        self.press(button)?;
+        self.release(button)
+    }
+
+    /// Move a stick, both axes -1.0..=1.0 with 0.0 centered.
+    pub fn set_stick(&self, stick: Stick, x: f32, y: f32) -> Result<()> {
+        let (code_x, code_y) = match stick {
+            Stick::Left => (ABS_X, ABS_Y),
+            Stick::Right => (ABS_RX, ABS_RY),
+        };
+        self.frame(&[
+            (EV_ABS, code_x, self.centered(code_x, x)),
+            (EV_ABS, code_y, self.centered(code_y, y)),
+        ])
+    }
+
+    /// Pull a trigger, 0.0 released to 1.0 all the way.
+    pub fn set_trigger(&self, trigger: Trigger, value: f32) -> Result<()> {
+        let code = match trigger {
+            Trigger::Left => ABS_Z,
+            Trigger::Right => ABS_RZ,
+        };
+        let (min, max) = self.device.abs_range(code);
+        let value = min + ((max - min) as f32 * value.clamp(0.0, 1.0)).round() as i32;
+        self.frame(&[(EV_ABS, code, value)])
+    }
+
+    /// Hold the d-pad, each of x and y -1, 0 or 1.
+    pub fn set_dpad(&self, x: i32, y: i32) -> Result<()> {
+        self.frame(&[
+            (EV_ABS, ABS_HAT0X, x.signum()),
+            (EV_ABS, ABS_HAT0Y, y.signum()),
+        ])
+    }
+
+    /// Answer every force feedback request that came in since the last
+    /// call and go through them. Call this often, the game is stuck in
+    /// EVIOCSFF until its upload is answered.
+    pub fn rumble_requests(&mut self) -> Result<impl Iterator<Item = FfRequest> + use<>> {
+        Ok(self.effects.process(&self.device)?.into_iter())
+    }
+
+    /// Strong and weak motor strength right now, 0.0 to 1.0, from what the
+    /// game is playing.
+    pub fn rumble(&mut self) -> (f32, f32) {
+        self.effects.player().rumble_at(Instant::now())
+    }
+
+    /// The uploaded effects.
+    pub fn effects(&self) -> &EffectTable {
+        &self.effects
+    }
+
+    /// The wrapped device.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Unwrap the device.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+
+    /// -1.0..=1.0 onto an axis, 0.0 landing on its center.
+    fn centered(&self, code: u16, value: f32) -> i32 {
+        let (min, max) = self.device.abs_range(code);
+        let center = min + (max - min) / 2;
+        let value = value.clamp(-1.0, 1.0);
+        let reach = if value < 0.0 {
+            center - min
+        } else {
+            max - center
+        };
+        center + (reach as f32 * value).round() as i32
+    }
+
+    fn frame(&self, events: &[(u16, u16, i32)]) -> Result<()> {
+        let mut frame: Vec<_> = events
+            .iter()
+            .map(|&(type_, code, value)| new_event(type_, code, value))
+            .collect();
+        frame.push(new_event(EV_SYN, SYN_REPORT, 0));
+        self.device.emit_batch(&frame)
+    }
+}
@@ -0,0 +1,55 @@
+use std::{ops::Deref, thread};
+
+use crate::Device;
+
+/// Releases every held key and button if the thread panics while this is
+/// alive, so a crashing script doesn't leave Ctrl held down system wide.
+///
+/// Dereferences to the device, so emit through it like normal.
+/// Dropping it without a panic does nothing.
+///
+/// Example:
+/// ```rust
+/// use std::panic::{AssertUnwindSafe, catch_unwind};
+///
+/// use uinput_rs::{Device, MockBackend, UInputUserDevice, key_events::KEY_LEFTCTRL_EVENT};
+///
+/// let device = Device::with_backend(MockBackend::new(), &[KEY_LEFTCTRL_EVENT], &UInputUserDevice::default()).unwrap();
+///
+/// let _ = catch_unwind(AssertUnwindSafe(|| {
+///     let input = device.guard();
+///     input.emit_key_code(KEY_LEFTCTRL_EVENT, 1).unwrap();
+///     input.sync().unwrap();
+///     panic!("oops");
+/// }));
+///
+/// assert!(device.pressed_keys().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct InputGuard<'a> {
+    device: &'a Device,
+}
+
+impl<'a> InputGuard<'a> {
+    /// Guard a device. Same as Device::guard().
+    pub fn new(device: &'a Device) -> Self {
+        Self { device }
+    }
+}
+
+impl Deref for InputGuard<'_> {
+    type Target = Device;
+
+    fn deref(&self) -> &Device {
+        self.device
+    }
+}
+
+impl Drop for InputGuard<'_> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            // Already unwinding, nothing useful to do with an error here.
+            let _ = self.device.release_all();
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::key_codes::*;
+
+/// HID usage page of keyboard keys.
+pub const KEYBOARD_PAGE: u16 = 0x07;
+/// HID usage page of buttons.
+pub const BUTTON_PAGE: u16 = 0x09;
+/// HID usage page of media and application keys.
+pub const CONSUMER_PAGE: u16 = 0x0c;
+
+/// Keyboard page usages and the evdev keys the kernel maps them to
+/// (the hid_keyboard table in hid-input.c).
+pub const KEYBOARD: &[(u16, u16)] = &[
+    (0x04, KEY_A),
+    (0x05, KEY_B),
+    (0x06, KEY_C),
+    (0x07, KEY_D),
+    (0x08, KEY_E),
+    (0x09, KEY_F),
+    (0x0a, KEY_G),
+    (0x0b, KEY_H),
+    (0x0c, KEY_I),
+    (0x0d, KEY_J),
+    (0x0e, KEY_K),
+    (0x0f, KEY_L),
+    (0x10, KEY_M),
+    (0x11, KEY_N),
+    (0x12, KEY_O),
+    (0x13, KEY_P),
+    (0x14, KEY_Q),
+    (0x15, KEY_R),
+    (0x16, KEY_S),
+    (0x17, KEY_T),
+    (0x18, KEY_U),
+    (0x19, KEY_V),
+    (0x1a, KEY_W),
+    (0x1b, KEY_X),
+    (0x1c, KEY_Y),
+    (0x1d, KEY_Z),
+    (0x1e, KEY_1),
+    (0x1f, KEY_2),
+    (0x20, KEY_3),
+    (0x21, KEY_4),
+    (0x22, KEY_5),
+    (0x23, KEY_6),
+    (0x24, KEY_7),
+    (0x25, KEY_8),
+    (0x26, KEY_9),
+    (0x27, KEY_0),
+    (0x28, KEY_ENTER),
+    (0x29, KEY_ESC),
+    (0x2a, KEY_BACKSPACE),
+    (0x2b, KEY_TAB),
+    (0x2c, KEY_SPACE),
+    (0x2d, KEY_MINUS),
+    (0x2e, KEY_EQUAL),
+    (0x2f, KEY_LEFTBRACE),
+    (0x30, KEY_RIGHTBRACE),
+    (0x31, KEY_BACKSLASH),
+    (0x32, KEY_BACKSLASH),
+    (0x33, KEY_SEMICOLON),
+    (0x34, KEY_APOSTROPHE),
+    (0x35, KEY_GRAVE),
+    (0x36, KEY_COMMA),
+    (0x37, KEY_DOT),
+    (0x38, KEY_SLASH),
+    (0x39, KEY_CAPSLOCK),
+    (0x3a, KEY_F1),
+    (0x3b, KEY_F2),
+    (0x3c, KEY_F3),
+    (0x3d, KEY_F4),
+    (0x3e, KEY_F5),
+    (0x3f, KEY_F6),
+    (0x40, KEY_F7),
+    (0x41, KEY_F8),
+    (0x42, KEY_F9),
+    (0x43, KEY_F10),
+    (0x44, KEY_F11),
+    (0x45, KEY_F12),
+    (0x46, KEY_SYSRQ),
+    (0x47, KEY_SCROLLLOCK),
+    (0x48, KEY_PAUSE),
+    (0x49, KEY_INSERT),
+    (0x4a, KEY_HOME),
+    (0x4b, KEY_PAGEUP),
+    (0x4c, KEY_DELETE),
+    (0x4d, KEY_END),
+    (0x4e, KEY_PAGEDOWN),
+    (0x4f, KEY_RIGHT),
+    (0x50, KEY_LEFT),
+    (0x51, KEY_DOWN),
+    (0x52, KEY_UP),
+    (0x53, KEY_NUMLOCK),
+    (0x54, KEY_KPSLASH),
+    (0x55, KEY_KPASTERISK),
+    (0x56, KEY_KPMINUS),
+    (0x57, KEY_KPPLUS),
+    (0x58, KEY_KPENTER),
+    (0x59, KEY_KP1),
+    (0x5a, KEY_KP2),
+    (0x5b, KEY_KP3),
+    (0x5c, KEY_KP4),
+    (0x5d, KEY_KP5),
+    (0x5e, KEY_KP6),
+    (0x5f, KEY_KP7),
+    (0x60, KEY_KP8),
+    (0x61, KEY_KP9),
+    (0x62, KEY_KP0),
+    (0x63, KEY_KPDOT),
+    (0x64, KEY_102ND),
+    (0x65, KEY_COMPOSE),
+    (0x66, KEY_POWER),
+    (0x67, KEY_KPEQUAL),
+    (0x68, KEY_F13),
+    (0x69, KEY_F14),
+    (0x6a, KEY_F15),
+    (0x6b, KEY_F16),
+    (0x6c, KEY_F17),
+    (0x6d, KEY_F18),
+    (0x6e, KEY_F19),
+    (0x6f, KEY_F20),
+    (0x70, KEY_F21),
+    (0x71, KEY_F22),
+    (0x72, KEY_F23),
+    (0x73, KEY_F24),
+    (0x74, KEY_OPEN),
+    (0x75, KEY_HELP),
+    (0x76, KEY_PROPS),
+    (0x77, KEY_FRONT),
+    (0x78, KEY_STOP),
+    (0x79, KEY_AGAIN),
+    (0x7a, KEY_UNDO),
+    (0x7b, KEY_CUT),
+    (0x7c, KEY_COPY),
+    (0x7d, KEY_PASTE),
+    (0x7e, KEY_FIND),
+    (0x7f, KEY_MUTE),
+    (0x80, KEY_VOLUMEUP),
+    (0x81, KEY_VOLUMEDOWN),
+    (0x85, KEY_KPCOMMA),
+    (0x87, KEY_RO),
+    (0x88, KEY_KATAKANAHIRAGANA),
+    (0x89, KEY_YEN),
+    (0x8a, KEY_HENKAN),
+    (0x8b, KEY_MUHENKAN),
+    (0x8c, KEY_KPJPCOMMA),
+    (0x90, KEY_HANGEUL),
+    (0x91, KEY_HANJA),
+    (0x92, KEY_KATAKANA),
+    (0x93, KEY_HIRAGANA),
+    (0x94, KEY_ZENKAKUHANKAKU),
+    (0x9c, KEY_DELETE),
+    (0xb6, KEY_KPLEFTPAREN),
+    (0xb7, KEY_KPRIGHTPAREN),
+    (0xd8, KEY_DELETE),
+    (0xe0, KEY_LEFTCTRL),
+    (0xe1, KEY_LEFTSHIFT),
+    (0xe2, KEY_LEFTALT),
+    (0xe3, KEY_LEFTMETA),
+    (0xe4, KEY_RIGHTCTRL),
+    (0xe5, KEY_RIGHTSHIFT),
+    (0xe6, KEY_RIGHTALT),
+    (0xe7, KEY_RIGHTMETA),
+    (0xe8, KEY_PLAYPAUSE),
+    (0xe9, KEY_STOPCD),
+    (0xea, KEY_PREVIOUSSONG),
+    (0xeb, KEY_NEXTSONG),
+    (0xec, KEY_EJECTCD),
+    (0xed, KEY_VOLUMEUP),
+    (0xee, KEY_VOLUMEDOWN),
+    (0xef, KEY_MUTE),
+    (0xf0, KEY_WWW),
+    (0xf1, KEY_BACK),
+    (0xf2, KEY_FORWARD),
+    (0xf3, KEY_STOP),
+    (0xf4, KEY_FIND),
+    (0xf5, KEY_SCROLLUP),
+    (0xf6, KEY_SCROLLDOWN),
+    (0xf7, KEY_EDIT),
+    (0xf8, KEY_SLEEP),
+    (0xf9, KEY_COFFEE),
+    (0xfa, KEY_REFRESH),
+    (0xfb, KEY_CALC),
+];
+
+/// The commonly used part of the consumer page.
+pub const CONSUMER: &[(u16, u16)] = &[
+    (0x030, KEY_POWER),
+    (0x032, KEY_SLEEP),
+    (0x06f, KEY_BRIGHTNESSUP),
+    (0x070, KEY_BRIGHTNESSDOWN),
+    (0x0b0, KEY_PLAYCD),
+    (0x0b1, KEY_PAUSECD),
+    (0x0b3, KEY_FASTFORWARD),
+    (0x0b4, KEY_REWIND),
+    (0x0b5, KEY_NEXTSONG),
+    (0x0b6, KEY_PREVIOUSSONG),
+    (0x0b7, KEY_STOPCD),
+    (0x0b8, KEY_EJECTCD),
+    (0x0cd, KEY_PLAYPAUSE),
+    (0x0e2, KEY_MUTE),
+    (0x0e9, KEY_VOLUMEUP),
+    (0x0ea, KEY_VOLUMEDOWN),
+    (0x183, KEY_CONFIG),
+    (0x18a, KEY_MAIL),
+    (0x192, KEY_CALC),
+    (0x194, KEY_FILE),
+    (0x221, KEY_SEARCH),
+    (0x223, KEY_HOMEPAGE),
+    (0x224, KEY_BACK),
+    (0x225, KEY_FORWARD),
+    (0x226, KEY_STOP),
+    (0x227, KEY_REFRESH),
+    (0x22a, KEY_BOOKMARKS),
+];
+
+/// Evdev code for a HID usage. Buttons map the way the kernel maps them
+/// for mice: usage 1 is BTN_LEFT, 2 BTN_RIGHT and so on up to 16.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{hid, key_codes::{BTN_RIGHT, KEY_A, KEY_VOLUMEUP}};
+///
+/// assert_eq!(hid::to_evdev(hid::KEYBOARD_PAGE, 0x04), Some(KEY_A));
+/// assert_eq!(hid::to_evdev(hid::CONSUMER_PAGE, 0xe9), Some(KEY_VOLUMEUP));
+/// assert_eq!(hid::to_evdev(hid::BUTTON_PAGE, 2), Some(BTN_RIGHT));
+/// assert_eq!(hid::from_evdev(KEY_A), Some((hid::KEYBOARD_PAGE, 0x04)));
+/// ```
+pub fn to_evdev(page: u16, usage: u16) -> Option<u16> {
+    let table = match page {
+        KEYBOARD_PAGE => KEYBOARD,
+        CONSUMER_PAGE => CONSUMER,
+        BUTTON_PAGE if (1..=16).contains(&usage) => return Some(BTN_MOUSE + usage - 1),
+        _ => return None,
+    };
+    table
+        .iter()
+        .find(|&&(u, _)| u == usage)
+        .map(|&(_, code)| code)
+}
+
+/// HID (page, usage) for an evdev code. The keyboard page wins when a key is
+/// on several pages.
+pub fn from_evdev(code: u16) -> Option<(u16, u16)> {
+    let find = |table: &[(u16, u16)]| table.iter().find(|&&(_, c)| c == code).map(|&(u, _)| u);
+    if let Some(usage) = find(KEYBOARD) {
+        return Some((KEYBOARD_PAGE, usage));
+    }
+    if let Some(usage) = find(CONSUMER) {
+        return Some((CONSUMER_PAGE, usage));
+    }
+    (BTN_MOUSE..BTN_MOUSE + 16)
+        .contains(&code)
+        .then(|| (BUTTON_PAGE, code - BTN_MOUSE + 1))
+}
+
+/// The MSC_SCAN value real HID devices send along with a key:
+/// the page in the high 16 bits and the usage in the low ones.
+pub fn scancode(page: u16, usage: u16) -> i32 {
+    ((page as i32) << 16) | usage as i32
+}
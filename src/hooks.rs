@@ -0,0 +1,86 @@
+use std::{fmt, sync::Mutex};
+
+use libc::input_event;
+
+use crate::pipeline::Transformer;
+
+/// Identifies a hook added to a Device, for removing it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HookId(u64);
+
+type Hook = Box<dyn Transformer + Send>;
+
+/// The hooks of a device, run in the order they were added.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    next_id: u64,
+    /// Whether there are any, so emitting without hooks skips the lock.
+    active: bool,
+    hooks: Mutex<Vec<(HookId, Hook)>>,
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.hooks.lock().map(|hooks| hooks.len()).unwrap_or(0);
+        f.debug_struct("Hooks").field("count", &count).finish()
+    }
+}
+
+impl Hooks {
+    pub(crate) fn add(&mut self, hook: Hook) -> HookId {
+        let id = HookId(self.next_id);
+        self.next_id += 1;
+        let hooks = self.hooks.get_mut().unwrap_or_else(|err| err.into_inner());
+        hooks.push((id, hook));
+        self.active = true;
+        id
+    }
+
+    pub(crate) fn remove(&mut self, id: HookId) -> bool {
+        let hooks = self.hooks.get_mut().unwrap_or_else(|err| err.into_inner());
+        let before = hooks.len();
+        hooks.retain(|(hook_id, _)| *hook_id != id);
+        self.active = !hooks.is_empty();
+        hooks.len() != before
+    }
+
+    /// The events after every hook had its turn, None without hooks.
+    pub(crate) fn run(&self, events: &[input_event]) -> Option<Vec<input_event>> {
+        if !self.active {
+            return None;
+        }
+        let mut hooks = self.hooks.lock().unwrap_or_else(|err| err.into_inner());
+        let mut current = events.to_vec();
+        let mut next = Vec::with_capacity(current.len());
+        for (_, hook) in hooks.iter_mut() {
+            for event in current.drain(..) {
+                hook.transform(event, &mut next);
+            }
+            std::mem::swap(&mut current, &mut next);
+        }
+        Some(current)
+    }
+
+    /// What the hooks released on poll, each run through the hooks after
+    /// it. None without hooks.
+    pub(crate) fn poll(&self) -> Option<Vec<input_event>> {
+        if !self.active {
+            return None;
+        }
+        let mut hooks = self.hooks.lock().unwrap_or_else(|err| err.into_inner());
+        let mut out = Vec::new();
+        for i in 0..hooks.len() {
+            let mut current = Vec::new();
+            hooks[i].1.poll(&mut current);
+            let mut next = Vec::with_capacity(current.len());
+            for (_, hook) in hooks[i + 1..].iter_mut() {
+                for event in current.drain(..) {
+                    hook.transform(event, &mut next);
+                }
+                std::mem::swap(&mut current, &mut next);
+            }
+            out.append(&mut current);
+        }
+        Some(out)
+    }
+}
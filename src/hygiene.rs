@@ -0,0 +1,124 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use libc::input_event;
+
+use crate::{SYN_REPORT, key_types::EV_SYN};
+
+/// A suspicious SYN pattern spotted by SynHygiene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SynIssue {
+    /// This many events were emitted without a SYN_REPORT.
+    /// Consumers won't act on them until one arrives. Forgot to sync?
+    MissingSyn {
+        /// Events since the last SYN_REPORT.
+        events: u64,
+    },
+    /// This many SYN_REPORTs in a row were emitted with nothing before them.
+    /// Usually a tight loop syncing empty frames.
+    EmptySyn {
+        /// Empty frames in a row.
+        consecutive: u64,
+    },
+}
+
+impl fmt::Display for SynIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SynIssue::MissingSyn { events } => {
+                write!(f, "{events} events emitted without a SYN_REPORT")
+            }
+            SynIssue::EmptySyn { consecutive } => {
+                write!(f, "{consecutive} empty SYN_REPORTs in a row")
+            }
+        }
+    }
+}
+
+/// Watches emitted events for the classic "forgot to sync" and
+/// "synced empty frames in a loop" bugs and reports them to a callback.
+///
+/// Each issue is reported once when its threshold is reached, and again only
+/// after the pattern was broken and starts over.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{Device, MockBackend, SynHygiene, UInputUserDevice, key_events::REL_X_EVENT};
+///
+/// let mut device = Device::with_backend(MockBackend::new(), &[REL_X_EVENT], &UInputUserDevice::default()).unwrap();
+/// device.set_syn_hygiene(Some(SynHygiene::new(|issue| eprintln!("SYN hygiene: {issue}"))));
+///
+/// for _ in 0..5 {
+///     device.sync().unwrap(); // Reported on the third one.
+/// }
+/// ```
+pub struct SynHygiene {
+    max_events_without_syn: u64,
+    max_empty_syns: u64,
+    on_issue: Box<dyn Fn(SynIssue) + Send + Sync>,
+    pending: AtomicU64,
+    empty: AtomicU64,
+}
+
+impl fmt::Debug for SynHygiene {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SynHygiene")
+            .field("max_events_without_syn", &self.max_events_without_syn)
+            .field("max_empty_syns", &self.max_empty_syns)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SynHygiene {
+    /// Report issues to the callback.
+    /// Defaults: 64 events without a SYN, 3 empty SYNs in a row.
+    pub fn new(on_issue: impl Fn(SynIssue) + Send + Sync + 'static) -> Self {
+        Self {
+            max_events_without_syn: 64,
+            max_empty_syns: 3,
+            on_issue: Box::new(on_issue),
+            pending: AtomicU64::new(0),
+            empty: AtomicU64::new(0),
+        }
+    }
+
+    /// Report issues as warnings through the log crate.
+    #[cfg(feature = "log")]
+    pub fn log() -> Self {
+        Self::new(|issue| log::warn!("SYN hygiene: {issue}"))
+    }
+
+    /// How many events without a SYN_REPORT count as suspicious.
+    pub fn max_events_without_syn(mut self, events: u64) -> Self {
+        self.max_events_without_syn = events.max(1);
+        self
+    }
+
+    /// How many empty SYN_REPORTs in a row count as suspicious.
+    pub fn max_empty_syns(mut self, syns: u64) -> Self {
+        self.max_empty_syns = syns.max(1);
+        self
+    }
+
+    pub(crate) fn observe(&self, events: &[input_event]) {
+        for event in events {
+            if event.type_ == EV_SYN && event.code == SYN_REPORT {
+                if self.pending.swap(0, Ordering::Relaxed) == 0 {
+                    let empty = self.empty.fetch_add(1, Ordering::Relaxed) + 1;
+                    if empty == self.max_empty_syns {
+                        (self.on_issue)(SynIssue::EmptySyn { consecutive: empty });
+                    }
+                } else {
+                    self.empty.store(0, Ordering::Relaxed);
+                }
+            } else {
+                let pending = self.pending.fetch_add(1, Ordering::Relaxed) + 1;
+                if pending == self.max_events_without_syn {
+                    (self.on_issue)(SynIssue::MissingSyn { events: pending });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+pub const LED_NUML: u16 = 0x00;
+pub const LED_CAPSL: u16 = 0x01;
+pub const LED_SCROLLL: u16 = 0x02;
+pub const LED_COMPOSE: u16 = 0x03;
+pub const LED_KANA: u16 = 0x04;
+pub const LED_SLEEP: u16 = 0x05;
+pub const LED_SUSPEND: u16 = 0x06;
+pub const LED_MUTE: u16 = 0x07;
+pub const LED_MISC: u16 = 0x08;
+pub const LED_MAIL: u16 = 0x09;
+pub const LED_CHARGING: u16 = 0x0a;
+pub const LED_MAX: u16 = 0x0f;
+
+/// Every distinct code, without aliases, KEY_RESERVED or *_MAX.
+pub const ALL: &[u16] = &[
+    LED_NUML,
+    LED_CAPSL,
+    LED_SCROLLL,
+    LED_COMPOSE,
+    LED_KANA,
+    LED_SLEEP,
+    LED_SUSPEND,
+    LED_MUTE,
+    LED_MISC,
+    LED_MAIL,
+    LED_CHARGING,
+];
@@ -0,0 +1,17 @@
+pub const MSC_SERIAL: u16 = 0x00;
+pub const MSC_PULSELED: u16 = 0x01;
+pub const MSC_GESTURE: u16 = 0x02;
+pub const MSC_RAW: u16 = 0x03;
+pub const MSC_SCAN: u16 = 0x04;
+pub const MSC_TIMESTAMP: u16 = 0x05;
+pub const MSC_MAX: u16 = 0x07;
+
+/// Every distinct code, without aliases, KEY_RESERVED or *_MAX.
+pub const ALL: &[u16] = &[
+    MSC_SERIAL,
+    MSC_PULSELED,
+    MSC_GESTURE,
+    MSC_RAW,
+    MSC_SCAN,
+    MSC_TIMESTAMP,
+];
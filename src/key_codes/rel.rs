@@ -0,0 +1,29 @@
+pub const REL_X: u16 = 0x00;
+pub const REL_Y: u16 = 0x01;
+pub const REL_Z: u16 = 0x02;
+pub const REL_RX: u16 = 0x03;
+pub const REL_RY: u16 = 0x04;
+pub const REL_RZ: u16 = 0x05;
+pub const REL_HWHEEL: u16 = 0x06;
+pub const REL_DIAL: u16 = 0x07;
+pub const REL_WHEEL: u16 = 0x08;
+pub const REL_MISC: u16 = 0x09;
+pub const REL_WHEEL_HI_RES: u16 = 0x0b;
+pub const REL_HWHEEL_HI_RES: u16 = 0x0c;
+pub const REL_MAX: u16 = 0x0f;
+
+/// Every distinct code, without aliases, KEY_RESERVED or *_MAX.
+pub const ALL: &[u16] = &[
+    REL_X,
+    REL_Y,
+    REL_Z,
+    REL_RX,
+    REL_RY,
+    REL_RZ,
+    REL_HWHEEL,
+    REL_DIAL,
+    REL_WHEEL,
+    REL_MISC,
+    REL_WHEEL_HI_RES,
+    REL_HWHEEL_HI_RES,
+];
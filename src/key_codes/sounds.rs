@@ -0,0 +1,7 @@
+pub const SND_CLICK: u16 = 0x00;
+pub const SND_BELL: u16 = 0x01;
+pub const SND_TONE: u16 = 0x02;
+pub const SND_MAX: u16 = 0x07;
+
+/// Every distinct code, without aliases, KEY_RESERVED or *_MAX.
+pub const ALL: &[u16] = &[SND_CLICK, SND_BELL, SND_TONE];
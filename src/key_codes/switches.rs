@@ -0,0 +1,39 @@
+pub const SW_LID: u16 = 0x00;
+pub const SW_TABLET_MODE: u16 = 0x01;
+pub const SW_HEADPHONE_INSERT: u16 = 0x02;
+pub const SW_RFKILL_ALL: u16 = 0x03;
+pub const SW_MICROPHONE_INSERT: u16 = 0x04;
+pub const SW_DOCK: u16 = 0x05;
+pub const SW_LINEOUT_INSERT: u16 = 0x06;
+pub const SW_JACK_PHYSICAL_INSERT: u16 = 0x07;
+pub const SW_VIDEOOUT_INSERT: u16 = 0x08;
+pub const SW_CAMERA_LENS_COVER: u16 = 0x09;
+pub const SW_KEYPAD_SLIDE: u16 = 0x0a;
+pub const SW_FRONT_PROXIMITY: u16 = 0x0b;
+pub const SW_ROTATE_LOCK: u16 = 0x0c;
+pub const SW_LINEIN_INSERT: u16 = 0x0d;
+pub const SW_MUTE_DEVICE: u16 = 0x0e;
+pub const SW_PEN_INSERTED: u16 = 0x0f;
+pub const SW_MACHINE_COVER: u16 = 0x10;
+pub const SW_MAX: u16 = 0x10;
+
+/// Every distinct code, without aliases, KEY_RESERVED or *_MAX.
+pub const ALL: &[u16] = &[
+    SW_LID,
+    SW_TABLET_MODE,
+    SW_HEADPHONE_INSERT,
+    SW_RFKILL_ALL,
+    SW_MICROPHONE_INSERT,
+    SW_DOCK,
+    SW_LINEOUT_INSERT,
+    SW_JACK_PHYSICAL_INSERT,
+    SW_VIDEOOUT_INSERT,
+    SW_CAMERA_LENS_COVER,
+    SW_KEYPAD_SLIDE,
+    SW_FRONT_PROXIMITY,
+    SW_ROTATE_LOCK,
+    SW_LINEIN_INSERT,
+    SW_MUTE_DEVICE,
+    SW_PEN_INSERTED,
+    SW_MACHINE_COVER,
+];
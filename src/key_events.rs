@@ -595,3 +595,65 @@ pub const ABS_MT_DISTANCE_EVENT: (u64, u64) = (0x03, 0x3b);
 pub const ABS_MT_TOOL_X_EVENT: (u64, u64) = (0x03, 0x3c);
 pub const ABS_MT_TOOL_Y_EVENT: (u64, u64) = (0x03, 0x3d);
 pub const ABS_MAX_EVENT: (u64, u64) = (0x03, 0x3f);
+pub const SW_LID_EVENT: (u64, u64) = (0x05, 0x00);
+pub const SW_TABLET_MODE_EVENT: (u64, u64) = (0x05, 0x01);
+pub const SW_HEADPHONE_INSERT_EVENT: (u64, u64) = (0x05, 0x02);
+pub const SW_RFKILL_ALL_EVENT: (u64, u64) = (0x05, 0x03);
+pub const SW_MICROPHONE_INSERT_EVENT: (u64, u64) = (0x05, 0x04);
+pub const SW_DOCK_EVENT: (u64, u64) = (0x05, 0x05);
+pub const SW_LINEOUT_INSERT_EVENT: (u64, u64) = (0x05, 0x06);
+pub const SW_JACK_PHYSICAL_INSERT_EVENT: (u64, u64) = (0x05, 0x07);
+pub const SW_VIDEOOUT_INSERT_EVENT: (u64, u64) = (0x05, 0x08);
+pub const SW_CAMERA_LENS_COVER_EVENT: (u64, u64) = (0x05, 0x09);
+pub const SW_KEYPAD_SLIDE_EVENT: (u64, u64) = (0x05, 0x0a);
+pub const SW_FRONT_PROXIMITY_EVENT: (u64, u64) = (0x05, 0x0b);
+pub const SW_ROTATE_LOCK_EVENT: (u64, u64) = (0x05, 0x0c);
+pub const SW_LINEIN_INSERT_EVENT: (u64, u64) = (0x05, 0x0d);
+pub const SW_MUTE_DEVICE_EVENT: (u64, u64) = (0x05, 0x0e);
+pub const SW_PEN_INSERTED_EVENT: (u64, u64) = (0x05, 0x0f);
+pub const SW_MACHINE_COVER_EVENT: (u64, u64) = (0x05, 0x10);
+pub const SW_MAX_EVENT: (u64, u64) = (0x05, 0x10);
+pub const LED_NUML_EVENT: (u64, u64) = (0x11, 0x00);
+pub const LED_CAPSL_EVENT: (u64, u64) = (0x11, 0x01);
+pub const LED_SCROLLL_EVENT: (u64, u64) = (0x11, 0x02);
+pub const LED_COMPOSE_EVENT: (u64, u64) = (0x11, 0x03);
+pub const LED_KANA_EVENT: (u64, u64) = (0x11, 0x04);
+pub const LED_SLEEP_EVENT: (u64, u64) = (0x11, 0x05);
+pub const LED_SUSPEND_EVENT: (u64, u64) = (0x11, 0x06);
+pub const LED_MUTE_EVENT: (u64, u64) = (0x11, 0x07);
+pub const LED_MISC_EVENT: (u64, u64) = (0x11, 0x08);
+pub const LED_MAIL_EVENT: (u64, u64) = (0x11, 0x09);
+pub const LED_CHARGING_EVENT: (u64, u64) = (0x11, 0x0a);
+pub const LED_MAX_EVENT: (u64, u64) = (0x11, 0x0f);
+pub const SND_CLICK_EVENT: (u64, u64) = (0x12, 0x00);
+pub const SND_BELL_EVENT: (u64, u64) = (0x12, 0x01);
+pub const SND_TONE_EVENT: (u64, u64) = (0x12, 0x02);
+pub const SND_MAX_EVENT: (u64, u64) = (0x12, 0x07);
+pub const FF_RUMBLE_EVENT: (u64, u64) = (0x15, 0x50);
+pub const FF_PERIODIC_EVENT: (u64, u64) = (0x15, 0x51);
+pub const FF_CONSTANT_EVENT: (u64, u64) = (0x15, 0x52);
+pub const FF_SPRING_EVENT: (u64, u64) = (0x15, 0x53);
+pub const FF_FRICTION_EVENT: (u64, u64) = (0x15, 0x54);
+pub const FF_DAMPER_EVENT: (u64, u64) = (0x15, 0x55);
+pub const FF_INERTIA_EVENT: (u64, u64) = (0x15, 0x56);
+pub const FF_RAMP_EVENT: (u64, u64) = (0x15, 0x57);
+pub const FF_SQUARE_EVENT: (u64, u64) = (0x15, 0x58);
+pub const FF_TRIANGLE_EVENT: (u64, u64) = (0x15, 0x59);
+pub const FF_SINE_EVENT: (u64, u64) = (0x15, 0x5a);
+pub const FF_SAW_UP_EVENT: (u64, u64) = (0x15, 0x5b);
+pub const FF_SAW_DOWN_EVENT: (u64, u64) = (0x15, 0x5c);
+pub const FF_CUSTOM_EVENT: (u64, u64) = (0x15, 0x5d);
+pub const FF_GAIN_EVENT: (u64, u64) = (0x15, 0x60);
+pub const FF_AUTOCENTER_EVENT: (u64, u64) = (0x15, 0x61);
+pub const FF_MAX_EVENT: (u64, u64) = (0x15, 0x7f);
+pub const MSC_SERIAL_EVENT: (u64, u64) = (0x04, 0x00);
+pub const MSC_PULSELED_EVENT: (u64, u64) = (0x04, 0x01);
+pub const MSC_GESTURE_EVENT: (u64, u64) = (0x04, 0x02);
+pub const MSC_RAW_EVENT: (u64, u64) = (0x04, 0x03);
+pub const MSC_SCAN_EVENT: (u64, u64) = (0x04, 0x04);
+pub const MSC_TIMESTAMP_EVENT: (u64, u64) = (0x04, 0x05);
+pub const MSC_MAX_EVENT: (u64, u64) = (0x04, 0x07);
+
+// Codes the installed headers have on top of the pregenerated tables.
+#[cfg(feature = "system-codes")]
+include!(concat!(env!("OUT_DIR"), "/key_codes/key_events.rs"));
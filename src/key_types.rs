@@ -8,3 +8,4 @@ pub const EV_LED: u16 = 0x11;
 pub const EV_SND: u16 = 0x12;
 pub const EV_REP: u16 = 0x14;
 pub const EV_FF: u16 = 0x15;
+pub const EV_UINPUT: u16 = 0x0101;
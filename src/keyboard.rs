@@ -0,0 +1,436 @@
+use std::io::{self, Result};
+
+use crate::{
+    Device, LockState, SYN_REPORT,
+    cancel::{CancelToken, cancelled},
+    device::new_event,
+    devices,
+    key_codes::{KEY_LEFTSHIFT, MSC_RAW},
+    key_types::{EV_KEY, EV_MSC, EV_SYN},
+    keysyms, scancodes,
+};
+
+/// Whether a key is one of the eight modifiers, which boot protocol
+/// keyboards report in their own byte and never count against rollover.
+pub use crate::key_codes::keys::is_modifier;
+
+/// How many keys the keyboard can report down at once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Rollover {
+    /// Every key, like most modern USB keyboards.
+    #[default]
+    Nkro,
+    /// Six keys plus modifiers, like a USB keyboard in boot protocol.
+    /// Pressing a seventh makes it report ErrorRollOver, which the host
+    /// ignores: nothing changes until enough keys are let go, then it
+    /// catches up with whatever is still held.
+    SixKey,
+}
+
+impl Rollover {
+    fn limit(self) -> usize {
+        match self {
+            Rollover::Nkro => usize::MAX,
+            Rollover::SixKey => 6,
+        }
+    }
+}
+
+/// How type_with gets text into the focused application.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TypeStrategy {
+    /// A key press per character on a US layout. Characters without a key
+    /// are refused before anything is typed.
+    #[default]
+    Keys,
+    /// Copy the text into the clipboard and press Ctrl+V. Works for emoji,
+    /// complex scripts and anything an IME would normally handle.
+    #[cfg(feature = "clipboard")]
+    Paste,
+    /// Keys when every character has one, Paste otherwise.
+    #[cfg(feature = "clipboard")]
+    Auto,
+}
+
+/// A keyboard that remembers which keys are physically held and reports
+/// them the way the chosen kind of keyboard would. Every press and release
+/// is its own frame.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     key_codes::{KEY_1, KEY_2, KEY_3, KEY_4, KEY_5, KEY_6, KEY_7, KEY_LEFTSHIFT},
+///     key_events::{KEY_1_EVENT, KEY_2_EVENT, KEY_3_EVENT, KEY_4_EVENT, KEY_5_EVENT, KEY_6_EVENT, KEY_7_EVENT, KEY_LEFTSHIFT_EVENT},
+///     keyboard::{Keyboard, Rollover},
+/// };
+///
+/// let events = [KEY_1_EVENT, KEY_2_EVENT, KEY_3_EVENT, KEY_4_EVENT, KEY_5_EVENT, KEY_6_EVENT, KEY_7_EVENT, KEY_LEFTSHIFT_EVENT];
+/// let device = Device::with_backend(MockBackend::new(), &events, &UInputUserDevice::default()).unwrap();
+/// let mut keyboard = Keyboard::from_device(device);
+/// keyboard.set_rollover(Rollover::SixKey).unwrap();
+///
+/// for key in [KEY_1, KEY_2, KEY_3, KEY_4, KEY_5, KEY_6, KEY_7] {
+///     keyboard.press(key).unwrap();
+/// }
+/// keyboard.press(KEY_LEFTSHIFT).unwrap(); // modifiers always get through
+/// assert_eq!(keyboard.device().pressed_keys(), [KEY_1, KEY_2, KEY_3, KEY_4, KEY_5, KEY_6, KEY_LEFTSHIFT]);
+///
+/// keyboard.release(KEY_1).unwrap(); // back to six, the 7 shows up
+/// assert_eq!(keyboard.device().pressed_keys(), [KEY_2, KEY_3, KEY_4, KEY_5, KEY_6, KEY_7, KEY_LEFTSHIFT]);
+/// ```
+#[derive(Debug)]
+pub struct Keyboard {
+    device: Device,
+    rollover: Rollover,
+    /// Non-modifier keys physically down, in the order they went down.
+    held: Vec<u16>,
+    /// Non-modifier keys the host was told are down.
+    reported: Vec<u16>,
+    raw_scancodes: bool,
+    cancel: Option<CancelToken>,
+}
+
+impl Keyboard {
+    /// Create a full size keyboard through /dev/uinput with
+    /// devices::keyboard.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use uinput_rs::{key_codes::{KEY_C, KEY_LEFTCTRL}, keyboard::Keyboard};
+    ///
+    /// let mut keyboard = Keyboard::new("my keyboard").unwrap();
+    /// keyboard.type_str("Hello, world!\n").unwrap();
+    /// keyboard.combo(&[KEY_LEFTCTRL, KEY_C]).unwrap();
+    /// if keyboard.lock_state().unwrap().caps {
+    ///     println!("caps lock is on");
+    /// }
+    /// ```
+    pub fn new(name: &str) -> Result<Self> {
+        Ok(Self::from_device(devices::keyboard(name)?))
+    }
+
+    /// Wrap a device with EV_KEY and the keys you're going to press.
+    pub fn from_device(device: Device) -> Self {
+        Self {
+            device,
+            rollover: Rollover::default(),
+            held: Vec::new(),
+            reported: Vec::new(),
+            raw_scancodes: false,
+            cancel: None,
+        }
+    }
+
+    /// Stop typing between characters once `token` is cancelled, letting
+    /// go of every key. None to type everything no matter what.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     cancel::CancelToken,
+    ///     devices::KEYBOARD_EVENTS,
+    ///     key_types::EV_KEY,
+    ///     keyboard::Keyboard,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut device = Device::with_backend(mock.clone(), &KEYBOARD_EVENTS, &UInputUserDevice::default()).unwrap();
+    /// let token = CancelToken::new();
+    /// // Someone hits Stop after the third key press.
+    /// let stop = token.clone();
+    /// let mut presses = 0;
+    /// device.add_hook(move |event| {
+    ///     if event.type_ == EV_KEY && event.value == 1 {
+    ///         presses += 1;
+    ///         if presses == 3 {
+    ///             stop.cancel();
+    ///         }
+    ///     }
+    ///     Some(event)
+    /// });
+    /// let mut keyboard = Keyboard::from_device(device);
+    /// keyboard.set_cancel_token(Some(token));
+    ///
+    /// let err = keyboard.type_str("hello world").unwrap_err();
+    /// assert_eq!(uinput_rs::Error::from_io(&err), Some(&uinput_rs::Error::Cancelled));
+    /// assert_eq!(mock.events().iter().filter(|e| e.type_ == EV_KEY && e.value == 1).count(), 3);
+    /// assert!(keyboard.device().pressed_keys().is_empty());
+    /// ```
+    pub fn set_cancel_token(&mut self, token: Option<CancelToken>) {
+        self.cancel = token;
+    }
+
+    /// The rollover mode, Nkro unless changed.
+    pub fn rollover(&self) -> Rollover {
+        self.rollover
+    }
+
+    /// Switch rollover modes. Keys already held are reported again under
+    /// the new limit.
+    pub fn set_rollover(&mut self, rollover: Rollover) -> Result<()> {
+        self.rollover = rollover;
+        self.report()
+    }
+
+    /// Send the AT set 2 bytes of every key as MSC_RAW events in front of
+    /// it, like atkbd does for PS/2 keyboards. Keys without a scancode go
+    /// out alone. The device needs MSC_RAW enabled.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     key_codes::{KEY_UP, MSC_RAW},
+    ///     key_events::{KEY_UP_EVENT, MSC_RAW_EVENT},
+    ///     keyboard::Keyboard,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let device = Device::with_backend(mock.clone(), &[KEY_UP_EVENT, MSC_RAW_EVENT], &UInputUserDevice::default()).unwrap();
+    /// let mut keyboard = Keyboard::from_device(device);
+    /// keyboard.set_raw_scancodes(true).unwrap();
+    ///
+    /// keyboard.press(KEY_UP).unwrap();
+    /// keyboard.release(KEY_UP).unwrap();
+    /// let raw: Vec<i32> = mock.events().iter().filter(|e| e.code == MSC_RAW).map(|e| e.value).collect();
+    /// assert_eq!(raw, [0xe0, 0x75, 0xe0, 0xf0, 0x75]);
+    /// ```
+    pub fn set_raw_scancodes(&mut self, on: bool) -> Result<()> {
+        if on && !self.device.capabilities().contains(EV_MSC, MSC_RAW) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "raw scancodes need MSC_RAW enabled on the device",
+            ));
+        }
+        self.raw_scancodes = on;
+        Ok(())
+    }
+
+    /// Press a key and leave it down.
+    pub fn press(&mut self, code: u16) -> Result<()> {
+        if is_modifier(code) {
+            return self.frame(&[(code, 1)]);
+        }
+        if !self.held.contains(&code) {
+            self.held.push(code);
+        }
+        self.report()
+    }
+
+    /// Let go of a key.
+    pub fn release(&mut self, code: u16) -> Result<()> {
+        if is_modifier(code) {
+            return self.frame(&[(code, 0)]);
+        }
+        self.held.retain(|&held| held != code);
+        self.report()
+    }
+
+    /// Press and release a key.
+    pub fn tap(&mut self, code: u16) -> Result<()> {
+        self.press(code)?;
+        self.release(code)
+    }
+
+    /// Press the keys in order, then let go of them in reverse, like
+    /// Ctrl+Shift+T. If a press fails, the keys already down are let go
+    /// before the error is returned.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     key_codes::{KEY_LEFTCTRL, KEY_LEFTSHIFT, KEY_T},
+    ///     key_events::{KEY_LEFTCTRL_EVENT, KEY_LEFTSHIFT_EVENT, KEY_T_EVENT},
+    ///     key_types::EV_KEY,
+    ///     keyboard::Keyboard,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let events = [KEY_LEFTCTRL_EVENT, KEY_LEFTSHIFT_EVENT, KEY_T_EVENT];
+    /// let device = Device::with_backend(mock.clone(), &events, &UInputUserDevice::default()).unwrap();
+    /// let mut keyboard = Keyboard::from(device);
+    /// keyboard.combo(&[KEY_LEFTCTRL, KEY_LEFTSHIFT, KEY_T]).unwrap();
+    ///
+    /// let keys: Vec<(u16, i32)> = mock.events().iter().filter(|e| e.type_ == EV_KEY).map(|e| (e.code, e.value)).collect();
+    /// assert_eq!(
+    ///     keys,
+    ///     [(KEY_LEFTCTRL, 1), (KEY_LEFTSHIFT, 1), (KEY_T, 1), (KEY_T, 0), (KEY_LEFTSHIFT, 0), (KEY_LEFTCTRL, 0)]
+    /// );
+    /// assert!(keyboard.device().pressed_keys().is_empty());
+    /// ```
+    pub fn combo(&mut self, codes: &[u16]) -> Result<()> {
+        for (i, &code) in codes.iter().enumerate() {
+            if let Err(e) = self.press(code) {
+                for &pressed in codes[..i].iter().rev() {
+                    let _ = self.release(pressed);
+                }
+                return Err(e);
+            }
+        }
+        for &code in codes.iter().rev() {
+            self.release(code)?;
+        }
+        Ok(())
+    }
+
+    /// Caps, Num and Scroll Lock as the host sees them, see
+    /// Device::lock_state.
+    pub fn lock_state(&self) -> Result<LockState> {
+        self.device.lock_state()
+    }
+
+    /// Let go of every key, modifiers included.
+    pub fn release_all(&mut self) -> Result<()> {
+        self.held.clear();
+        self.reported.clear();
+        self.device.release_all()
+    }
+
+    /// Non-modifier keys physically down, whether or not the host was told.
+    pub fn held(&self) -> &[u16] {
+        &self.held
+    }
+
+    /// Type text with key presses, shift included where needed.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     key_codes::{KEY_H, KEY_I, KEY_LEFTSHIFT},
+    ///     key_events::{KEY_H_EVENT, KEY_I_EVENT, KEY_LEFTSHIFT_EVENT},
+    ///     key_types::EV_KEY,
+    ///     keyboard::Keyboard,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let events = [KEY_H_EVENT, KEY_I_EVENT, KEY_LEFTSHIFT_EVENT];
+    /// let device = Device::with_backend(mock.clone(), &events, &UInputUserDevice::default()).unwrap();
+    /// let mut keyboard = Keyboard::from_device(device);
+    /// keyboard.type_str("Hi").unwrap();
+    ///
+    /// let keys: Vec<(u16, i32)> = mock.events().iter().filter(|e| e.type_ == EV_KEY).map(|e| (e.code, e.value)).collect();
+    /// assert_eq!(keys, [(KEY_LEFTSHIFT, 1), (KEY_H, 1), (KEY_H, 0), (KEY_LEFTSHIFT, 0), (KEY_I, 1), (KEY_I, 0)]);
+    /// assert!(keyboard.type_str("héllo 🙂").is_err());
+    /// ```
+    pub fn type_str(&mut self, text: &str) -> Result<()> {
+        self.type_with(text, TypeStrategy::Keys)
+    }
+
+    /// Type text the way `strategy` says.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// # #[cfg(feature = "clipboard")] {
+    /// use uinput_rs::{
+    ///     Device,
+    ///     key_events::{KEY_LEFTCTRL_EVENT, KEY_V_EVENT},
+    ///     keyboard::{Keyboard, TypeStrategy},
+    /// };
+    ///
+    /// let device = Device::new(&[KEY_LEFTCTRL_EVENT, KEY_V_EVENT]).unwrap();
+    /// let mut keyboard = Keyboard::from_device(device);
+    /// keyboard.type_with("こんにちは 🙂", TypeStrategy::Auto).unwrap();
+    /// # }
+    /// ```
+    pub fn type_with(&mut self, text: &str, strategy: TypeStrategy) -> Result<()> {
+        let keys: Option<Vec<_>> = text.chars().map(keysyms::from_char).collect();
+        match (strategy, keys) {
+            (TypeStrategy::Keys, None) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "text has characters without a key, try pasting it",
+            )),
+            #[cfg(feature = "clipboard")]
+            (TypeStrategy::Paste, _) | (TypeStrategy::Auto, None) => self.paste(text),
+            (_, Some(keys)) => {
+                for key in keys {
+                    if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+                        self.release_all()?;
+                        return Err(cancelled());
+                    }
+                    if key.shift {
+                        self.press(KEY_LEFTSHIFT)?;
+                    }
+                    self.press(key.code)?;
+                    self.release(key.code)?;
+                    if key.shift {
+                        self.release(KEY_LEFTSHIFT)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    #[cfg(feature = "clipboard")]
+    fn paste(&mut self, text: &str) -> Result<()> {
+        crate::clipboard::copy(text)?;
+        use crate::key_codes::{KEY_LEFTCTRL, KEY_V};
+
+        self.press(KEY_LEFTCTRL)?;
+        self.press(KEY_V)?;
+        self.release(KEY_V)?;
+        self.release(KEY_LEFTCTRL)
+    }
+
+    /// Tell the host about every change since the last report, unless more
+    /// keys are down than the rollover mode can report.
+    fn report(&mut self) -> Result<()> {
+        if self.held.len() > self.rollover.limit() {
+            return Ok(());
+        }
+        let released = self
+            .reported
+            .iter()
+            .filter(|code| !self.held.contains(code))
+            .map(|&code| (code, 0));
+        let pressed = self
+            .held
+            .iter()
+            .filter(|code| !self.reported.contains(code))
+            .map(|&code| (code, 1));
+        let changes: Vec<_> = released.chain(pressed).collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        self.frame(&changes)?;
+        self.reported = self.held.clone();
+        Ok(())
+    }
+
+    fn frame(&self, keys: &[(u16, i32)]) -> Result<()> {
+        let mut frame = Vec::new();
+        for &(code, value) in keys {
+            if self.raw_scancodes {
+                let bytes = scancodes::set2_bytes(code, value).unwrap_or_default();
+                frame.extend(
+                    bytes
+                        .iter()
+                        .map(|&byte| new_event(EV_MSC, MSC_RAW, byte.into())),
+                );
+            }
+            frame.push(new_event(EV_KEY, code, value));
+        }
+        frame.push(new_event(EV_SYN, SYN_REPORT, 0));
+        self.device.emit_batch(&frame)
+    }
+
+    /// The wrapped device.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Unwrap the device.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+}
+
+impl From<Device> for Keyboard {
+    fn from(device: Device) -> Self {
+        Self::from_device(device)
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
+
+use libc::input_event;
+
+use crate::key_types::{EV_ABS, EV_KEY};
+
+/// Number of u64 words needed for every key code (KEY_CNT = 0x300).
+const WORDS: usize = 0x300 / 64;
+
+/// What the device does with a press of a key that is already down, or a
+/// release of a key that is already up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    /// Send it anyway. The kernel drops it, but it still counts as written.
+    #[default]
+    Pass,
+    /// Leave it out of the write.
+    Ignore,
+    /// Fail with Error::DuplicateKeyState and write nothing.
+    Reject,
+}
+
+/// Which keys are currently held down. Updated after every successful write.
+#[derive(Debug, Default)]
+pub(crate) struct KeyState {
+    bits: [AtomicU64; WORDS],
+}
+
+impl KeyState {
+    pub(crate) fn is_pressed(&self, code: u16) -> bool {
+        let code = code as usize;
+        code < WORDS * 64 && self.bits[code / 64].load(Ordering::Relaxed) & (1 << (code % 64)) != 0
+    }
+
+    fn set(&self, code: u16, pressed: bool) {
+        let code = code as usize;
+        if code >= WORDS * 64 {
+            return;
+        }
+        let bit = 1 << (code % 64);
+        if pressed {
+            self.bits[code / 64].fetch_or(bit, Ordering::Relaxed);
+        } else {
+            self.bits[code / 64].fetch_and(!bit, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record(&self, events: &[input_event]) {
+        for event in events {
+            if let Some(pressed) = press_or_release(event) {
+                self.set(event.code, pressed);
+            }
+        }
+    }
+
+    /// The press or release at events[i] wouldn't change anything, counting
+    /// the events before it in the same batch.
+    pub(crate) fn is_duplicate(&self, events: &[input_event], i: usize) -> bool {
+        let Some(pressed) = press_or_release(&events[i]) else {
+            return false;
+        };
+        let code = events[i].code;
+        let before = events[..i]
+            .iter()
+            .rev()
+            .filter(|event| event.code == code)
+            .find_map(press_or_release)
+            .unwrap_or_else(|| self.is_pressed(code));
+        before == pressed
+    }
+
+    /// Held keys, lowest code first.
+    pub(crate) fn pressed(&self) -> Vec<u16> {
+        (0..(WORDS * 64) as u16)
+            .filter(|&code| self.is_pressed(code))
+            .collect()
+    }
+}
+
+/// Last value written for every ABS axis. Updated after every successful
+/// write.
+#[derive(Debug)]
+pub(crate) struct AbsState {
+    values: [AtomicI32; 64],
+    written: AtomicU64,
+}
+
+impl Default for AbsState {
+    fn default() -> Self {
+        Self {
+            values: std::array::from_fn(|_| AtomicI32::new(0)),
+            written: AtomicU64::new(0),
+        }
+    }
+}
+
+impl AbsState {
+    pub(crate) fn record(&self, events: &[input_event]) {
+        for event in events {
+            let code = event.code as usize;
+            if event.type_ == EV_ABS && code < 64 {
+                self.values[code].store(event.value, Ordering::Relaxed);
+                self.written.fetch_or(1 << code, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// (code, value) of every axis written so far, lowest code first.
+    pub(crate) fn values(&self) -> Vec<(u16, i32)> {
+        let written = self.written.load(Ordering::Relaxed);
+        (0..64)
+            .filter(|code| written & (1 << code) != 0)
+            .map(|code| (code as u16, self.values[code].load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+/// What a device has held down and where its axes are, from
+/// Device::state_snapshot. Give it to Device::restore, on the same device
+/// or another one, to get back there.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateSnapshot {
+    /// Held keys and buttons, lowest code first.
+    pub keys: Vec<u16>,
+    /// Last value of every ABS axis that was written, lowest code first.
+    /// Multitouch axes only keep the value of the last slot written.
+    pub abs: Vec<(u16, i32)>,
+}
+
+/// Some(true) for a press, Some(false) for a release, None for anything else
+/// (including autorepeat).
+fn press_or_release(event: &input_event) -> Option<bool> {
+    match (event.type_, event.value) {
+        (EV_KEY, 1) => Some(true),
+        (EV_KEY, 0) => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,249 @@
+use crate::key_codes::*;
+
+/// An X11 keysym and the evdev key that produces it on a US layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keysym {
+    /// Name without the XK_ prefix, as xdotool spells it ("Return", "a").
+    pub name: &'static str,
+    /// The keysym value.
+    pub keysym: u32,
+    /// KEY_* code.
+    pub code: u16,
+    /// Shift has to be held.
+    pub shift: bool,
+}
+
+impl Keysym {
+    const fn new(name: &'static str, keysym: u32, code: u16, shift: bool) -> Self {
+        Self {
+            name,
+            keysym,
+            code,
+            shift,
+        }
+    }
+}
+
+/// Keysyms with an evdev equivalent.
+///
+/// Characters assume a US layout, the rest (function keys, modifiers,
+/// navigation, media) are the same everywhere.
+/// When several keysyms share a key the first one wins for reverse lookups.
+pub const KEYSYMS: &[Keysym] = &[
+    Keysym::new("a", 0x61, KEY_A, false),
+    Keysym::new("b", 0x62, KEY_B, false),
+    Keysym::new("c", 0x63, KEY_C, false),
+    Keysym::new("d", 0x64, KEY_D, false),
+    Keysym::new("e", 0x65, KEY_E, false),
+    Keysym::new("f", 0x66, KEY_F, false),
+    Keysym::new("g", 0x67, KEY_G, false),
+    Keysym::new("h", 0x68, KEY_H, false),
+    Keysym::new("i", 0x69, KEY_I, false),
+    Keysym::new("j", 0x6a, KEY_J, false),
+    Keysym::new("k", 0x6b, KEY_K, false),
+    Keysym::new("l", 0x6c, KEY_L, false),
+    Keysym::new("m", 0x6d, KEY_M, false),
+    Keysym::new("n", 0x6e, KEY_N, false),
+    Keysym::new("o", 0x6f, KEY_O, false),
+    Keysym::new("p", 0x70, KEY_P, false),
+    Keysym::new("q", 0x71, KEY_Q, false),
+    Keysym::new("r", 0x72, KEY_R, false),
+    Keysym::new("s", 0x73, KEY_S, false),
+    Keysym::new("t", 0x74, KEY_T, false),
+    Keysym::new("u", 0x75, KEY_U, false),
+    Keysym::new("v", 0x76, KEY_V, false),
+    Keysym::new("w", 0x77, KEY_W, false),
+    Keysym::new("x", 0x78, KEY_X, false),
+    Keysym::new("y", 0x79, KEY_Y, false),
+    Keysym::new("z", 0x7a, KEY_Z, false),
+    Keysym::new("A", 0x41, KEY_A, true),
+    Keysym::new("B", 0x42, KEY_B, true),
+    Keysym::new("C", 0x43, KEY_C, true),
+    Keysym::new("D", 0x44, KEY_D, true),
+    Keysym::new("E", 0x45, KEY_E, true),
+    Keysym::new("F", 0x46, KEY_F, true),
+    Keysym::new("G", 0x47, KEY_G, true),
+    Keysym::new("H", 0x48, KEY_H, true),
+    Keysym::new("I", 0x49, KEY_I, true),
+    Keysym::new("J", 0x4a, KEY_J, true),
+    Keysym::new("K", 0x4b, KEY_K, true),
+    Keysym::new("L", 0x4c, KEY_L, true),
+    Keysym::new("M", 0x4d, KEY_M, true),
+    Keysym::new("N", 0x4e, KEY_N, true),
+    Keysym::new("O", 0x4f, KEY_O, true),
+    Keysym::new("P", 0x50, KEY_P, true),
+    Keysym::new("Q", 0x51, KEY_Q, true),
+    Keysym::new("R", 0x52, KEY_R, true),
+    Keysym::new("S", 0x53, KEY_S, true),
+    Keysym::new("T", 0x54, KEY_T, true),
+    Keysym::new("U", 0x55, KEY_U, true),
+    Keysym::new("V", 0x56, KEY_V, true),
+    Keysym::new("W", 0x57, KEY_W, true),
+    Keysym::new("X", 0x58, KEY_X, true),
+    Keysym::new("Y", 0x59, KEY_Y, true),
+    Keysym::new("Z", 0x5a, KEY_Z, true),
+    Keysym::new("0", 0x30, KEY_0, false),
+    Keysym::new("1", 0x31, KEY_1, false),
+    Keysym::new("2", 0x32, KEY_2, false),
+    Keysym::new("3", 0x33, KEY_3, false),
+    Keysym::new("4", 0x34, KEY_4, false),
+    Keysym::new("5", 0x35, KEY_5, false),
+    Keysym::new("6", 0x36, KEY_6, false),
+    Keysym::new("7", 0x37, KEY_7, false),
+    Keysym::new("8", 0x38, KEY_8, false),
+    Keysym::new("9", 0x39, KEY_9, false),
+    Keysym::new("space", 0x20, KEY_SPACE, false),
+    Keysym::new("minus", 0x2d, KEY_MINUS, false),
+    Keysym::new("equal", 0x3d, KEY_EQUAL, false),
+    Keysym::new("bracketleft", 0x5b, KEY_LEFTBRACE, false),
+    Keysym::new("bracketright", 0x5d, KEY_RIGHTBRACE, false),
+    Keysym::new("semicolon", 0x3b, KEY_SEMICOLON, false),
+    Keysym::new("apostrophe", 0x27, KEY_APOSTROPHE, false),
+    Keysym::new("grave", 0x60, KEY_GRAVE, false),
+    Keysym::new("backslash", 0x5c, KEY_BACKSLASH, false),
+    Keysym::new("comma", 0x2c, KEY_COMMA, false),
+    Keysym::new("period", 0x2e, KEY_DOT, false),
+    Keysym::new("slash", 0x2f, KEY_SLASH, false),
+    Keysym::new("exclam", 0x21, KEY_1, true),
+    Keysym::new("at", 0x40, KEY_2, true),
+    Keysym::new("numbersign", 0x23, KEY_3, true),
+    Keysym::new("dollar", 0x24, KEY_4, true),
+    Keysym::new("percent", 0x25, KEY_5, true),
+    Keysym::new("asciicircum", 0x5e, KEY_6, true),
+    Keysym::new("ampersand", 0x26, KEY_7, true),
+    Keysym::new("asterisk", 0x2a, KEY_8, true),
+    Keysym::new("parenleft", 0x28, KEY_9, true),
+    Keysym::new("parenright", 0x29, KEY_0, true),
+    Keysym::new("underscore", 0x5f, KEY_MINUS, true),
+    Keysym::new("plus", 0x2b, KEY_EQUAL, true),
+    Keysym::new("braceleft", 0x7b, KEY_LEFTBRACE, true),
+    Keysym::new("braceright", 0x7d, KEY_RIGHTBRACE, true),
+    Keysym::new("colon", 0x3a, KEY_SEMICOLON, true),
+    Keysym::new("quotedbl", 0x22, KEY_APOSTROPHE, true),
+    Keysym::new("asciitilde", 0x7e, KEY_GRAVE, true),
+    Keysym::new("bar", 0x7c, KEY_BACKSLASH, true),
+    Keysym::new("less", 0x3c, KEY_COMMA, true),
+    Keysym::new("greater", 0x3e, KEY_DOT, true),
+    Keysym::new("question", 0x3f, KEY_SLASH, true),
+    Keysym::new("BackSpace", 0xff08, KEY_BACKSPACE, false),
+    Keysym::new("Tab", 0xff09, KEY_TAB, false),
+    Keysym::new("Return", 0xff0d, KEY_ENTER, false),
+    Keysym::new("Pause", 0xff13, KEY_PAUSE, false),
+    Keysym::new("Scroll_Lock", 0xff14, KEY_SCROLLLOCK, false),
+    Keysym::new("Sys_Req", 0xff15, KEY_SYSRQ, false),
+    Keysym::new("Escape", 0xff1b, KEY_ESC, false),
+    Keysym::new("Home", 0xff50, KEY_HOME, false),
+    Keysym::new("Left", 0xff51, KEY_LEFT, false),
+    Keysym::new("Up", 0xff52, KEY_UP, false),
+    Keysym::new("Right", 0xff53, KEY_RIGHT, false),
+    Keysym::new("Down", 0xff54, KEY_DOWN, false),
+    Keysym::new("Prior", 0xff55, KEY_PAGEUP, false),
+    Keysym::new("Next", 0xff56, KEY_PAGEDOWN, false),
+    Keysym::new("End", 0xff57, KEY_END, false),
+    Keysym::new("Print", 0xff61, KEY_SYSRQ, false),
+    Keysym::new("Insert", 0xff63, KEY_INSERT, false),
+    Keysym::new("Menu", 0xff67, KEY_COMPOSE, false),
+    Keysym::new("Num_Lock", 0xff7f, KEY_NUMLOCK, false),
+    Keysym::new("KP_Enter", 0xff8d, KEY_KPENTER, false),
+    Keysym::new("KP_Multiply", 0xffaa, KEY_KPASTERISK, false),
+    Keysym::new("KP_Add", 0xffab, KEY_KPPLUS, false),
+    Keysym::new("KP_Subtract", 0xffad, KEY_KPMINUS, false),
+    Keysym::new("KP_Decimal", 0xffae, KEY_KPDOT, false),
+    Keysym::new("KP_Divide", 0xffaf, KEY_KPSLASH, false),
+    Keysym::new("KP_0", 0xffb0, KEY_KP0, false),
+    Keysym::new("KP_1", 0xffb1, KEY_KP1, false),
+    Keysym::new("KP_2", 0xffb2, KEY_KP2, false),
+    Keysym::new("KP_3", 0xffb3, KEY_KP3, false),
+    Keysym::new("KP_4", 0xffb4, KEY_KP4, false),
+    Keysym::new("KP_5", 0xffb5, KEY_KP5, false),
+    Keysym::new("KP_6", 0xffb6, KEY_KP6, false),
+    Keysym::new("KP_7", 0xffb7, KEY_KP7, false),
+    Keysym::new("KP_8", 0xffb8, KEY_KP8, false),
+    Keysym::new("KP_9", 0xffb9, KEY_KP9, false),
+    Keysym::new("F1", 0xffbe, KEY_F1, false),
+    Keysym::new("F2", 0xffbf, KEY_F2, false),
+    Keysym::new("F3", 0xffc0, KEY_F3, false),
+    Keysym::new("F4", 0xffc1, KEY_F4, false),
+    Keysym::new("F5", 0xffc2, KEY_F5, false),
+    Keysym::new("F6", 0xffc3, KEY_F6, false),
+    Keysym::new("F7", 0xffc4, KEY_F7, false),
+    Keysym::new("F8", 0xffc5, KEY_F8, false),
+    Keysym::new("F9", 0xffc6, KEY_F9, false),
+    Keysym::new("F10", 0xffc7, KEY_F10, false),
+    Keysym::new("F11", 0xffc8, KEY_F11, false),
+    Keysym::new("F12", 0xffc9, KEY_F12, false),
+    Keysym::new("F13", 0xffca, KEY_F13, false),
+    Keysym::new("F14", 0xffcb, KEY_F14, false),
+    Keysym::new("F15", 0xffcc, KEY_F15, false),
+    Keysym::new("F16", 0xffcd, KEY_F16, false),
+    Keysym::new("F17", 0xffce, KEY_F17, false),
+    Keysym::new("F18", 0xffcf, KEY_F18, false),
+    Keysym::new("F19", 0xffd0, KEY_F19, false),
+    Keysym::new("F20", 0xffd1, KEY_F20, false),
+    Keysym::new("F21", 0xffd2, KEY_F21, false),
+    Keysym::new("F22", 0xffd3, KEY_F22, false),
+    Keysym::new("F23", 0xffd4, KEY_F23, false),
+    Keysym::new("F24", 0xffd5, KEY_F24, false),
+    Keysym::new("Shift_L", 0xffe1, KEY_LEFTSHIFT, false),
+    Keysym::new("Shift_R", 0xffe2, KEY_RIGHTSHIFT, false),
+    Keysym::new("Control_L", 0xffe3, KEY_LEFTCTRL, false),
+    Keysym::new("Control_R", 0xffe4, KEY_RIGHTCTRL, false),
+    Keysym::new("Caps_Lock", 0xffe5, KEY_CAPSLOCK, false),
+    Keysym::new("Meta_L", 0xffe7, KEY_LEFTMETA, false),
+    Keysym::new("Meta_R", 0xffe8, KEY_RIGHTMETA, false),
+    Keysym::new("Alt_L", 0xffe9, KEY_LEFTALT, false),
+    Keysym::new("Alt_R", 0xffea, KEY_RIGHTALT, false),
+    Keysym::new("Super_L", 0xffeb, KEY_LEFTMETA, false),
+    Keysym::new("Super_R", 0xffec, KEY_RIGHTMETA, false),
+    Keysym::new("Delete", 0xffff, KEY_DELETE, false),
+    Keysym::new("XF86MonBrightnessUp", 0x1008ff02, KEY_BRIGHTNESSUP, false),
+    Keysym::new(
+        "XF86MonBrightnessDown",
+        0x1008ff03,
+        KEY_BRIGHTNESSDOWN,
+        false,
+    ),
+    Keysym::new("XF86AudioLowerVolume", 0x1008ff11, KEY_VOLUMEDOWN, false),
+    Keysym::new("XF86AudioMute", 0x1008ff12, KEY_MUTE, false),
+    Keysym::new("XF86AudioRaiseVolume", 0x1008ff13, KEY_VOLUMEUP, false),
+    Keysym::new("XF86AudioPlay", 0x1008ff14, KEY_PLAYPAUSE, false),
+    Keysym::new("XF86AudioStop", 0x1008ff15, KEY_STOPCD, false),
+    Keysym::new("XF86AudioPrev", 0x1008ff16, KEY_PREVIOUSSONG, false),
+    Keysym::new("XF86AudioNext", 0x1008ff17, KEY_NEXTSONG, false),
+];
+
+/// Finds a keysym by value, like 0xff0d for Return.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{key_codes::{KEY_A, KEY_ENTER}, keysyms};
+///
+/// let ret = keysyms::from_name("Return").unwrap();
+/// assert_eq!((ret.code, ret.shift), (KEY_ENTER, false));
+/// assert_eq!(keysyms::from_keysym('A' as u32).map(|k| (k.code, k.shift)), Some((KEY_A, true)));
+/// assert_eq!(keysyms::from_code(KEY_A, false).unwrap().name, "a");
+/// ```
+pub fn from_keysym(keysym: u32) -> Option<&'static Keysym> {
+    KEYSYMS.iter().find(|k| k.keysym == keysym)
+}
+
+/// The keysym that types a character on a US layout: Latin-1 keysyms
+/// are the code point, newline and tab are Return and Tab.
+pub fn from_char(c: char) -> Option<&'static Keysym> {
+    match c {
+        '\n' => from_name("Return"),
+        '\t' => from_name("Tab"),
+        c if (c as u32) < 0x100 => from_keysym(c as u32),
+        c => from_keysym(0x0100_0000 | c as u32),
+    }
+}
+
+/// Finds a keysym by its xdotool style name, like "Return" or "Control_L".
+pub fn from_name(name: &str) -> Option<&'static Keysym> {
+    KEYSYMS.iter().find(|k| k.name == name)
+}
+
+/// The keysym a key produces with or without shift.
+pub fn from_code(code: u16, shift: bool) -> Option<&'static Keysym> {
+    KEYSYMS.iter().find(|k| k.code == code && k.shift == shift)
+}
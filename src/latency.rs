@@ -0,0 +1,253 @@
+use std::{
+    collections::VecDeque,
+    fmt::Write as _,
+    io::Result,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use libc::input_event;
+
+use crate::{
+    Device, SYN_REPORT,
+    device::new_event,
+    evdev::EvdevReader,
+    key_codes::MSC_TIMESTAMP,
+    key_types::{EV_MSC, EV_SYN},
+};
+
+/// How long one frame took from emit to reading it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTrace {
+    /// The MSC_TIMESTAMP tag, None when frames were matched in order.
+    pub tag: Option<i32>,
+    /// When it was sent, from the start of tracing.
+    pub sent: Duration,
+    /// Time spent in the write.
+    pub write: Duration,
+    /// From the start of the write to the kernel's timestamp on the event.
+    /// None if the clock went backwards in between.
+    pub to_kernel: Option<Duration>,
+    /// From the start of the write until it was read back from evdev.
+    pub delivered: Duration,
+}
+
+impl FrameTrace {
+    /// From the kernel's timestamp to being read back.
+    pub fn kernel_to_reader(&self) -> Option<Duration> {
+        self.to_kernel
+            .and_then(|to_kernel| self.delivered.checked_sub(to_kernel))
+    }
+}
+
+#[derive(Debug)]
+struct Pending {
+    tag: Option<i32>,
+    sent: Instant,
+    sent_wall: SystemTime,
+    write: Duration,
+}
+
+/// Sends frames and reads them back from the device's evdev node to see
+/// how long each took. With MSC_TIMESTAMP enabled on the device every
+/// frame carries the microseconds since tracing started as its tag, like
+/// hardware timestamps, and frames are matched by tag. Without it they're
+/// matched in order.
+///
+/// Example:
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use uinput_rs::{
+///     Device, input_event, timeval,
+///     key_codes::REL_X,
+///     key_events::{MSC_TIMESTAMP_EVENT, REL_X_EVENT, REL_Y_EVENT},
+///     key_types::EV_REL,
+///     latency::LatencyTracer,
+/// };
+///
+/// let device = Device::new(&[REL_X_EVENT, REL_Y_EVENT, MSC_TIMESTAMP_EVENT]).unwrap();
+/// let mut tracer = LatencyTracer::new(&device).unwrap();
+/// let step = input_event { time: timeval { tv_sec: 0, tv_usec: 0 }, type_: EV_REL, code: REL_X, value: 1 };
+/// for _ in 0..100 {
+///     tracer.send(&device, &[step]).unwrap();
+///     tracer.collect(Duration::from_millis(100)).unwrap();
+/// }
+/// std::fs::write("latency.json", tracer.to_json()).unwrap();
+/// std::fs::write("latency.folded", tracer.to_folded()).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct LatencyTracer {
+    reader: EvdevReader,
+    started: Instant,
+    tagged: bool,
+    last_tag: i32,
+    pending: VecDeque<Pending>,
+    traces: Vec<FrameTrace>,
+    lost: usize,
+}
+
+impl LatencyTracer {
+    /// Start tracing a device, waiting up to a second for its evdev node.
+    pub fn new(device: &Device) -> Result<Self> {
+        let node = device.wait_until_registered(Duration::from_secs(1))?;
+        Ok(Self {
+            reader: EvdevReader::open(node)?,
+            started: Instant::now(),
+            tagged: device.capabilities().contains(EV_MSC, MSC_TIMESTAMP),
+            last_tag: 0,
+            pending: VecDeque::new(),
+            traces: Vec::new(),
+            lost: 0,
+        })
+    }
+
+    /// Emit the events as one frame, tagged if possible. The SYN_REPORT is
+    /// added.
+    pub fn send(&mut self, device: &Device, events: &[input_event]) -> Result<()> {
+        let mut frame: Vec<input_event> = events
+            .iter()
+            .filter(|event| event.type_ != EV_SYN)
+            .copied()
+            .collect();
+        let tag = self.tagged.then(|| {
+            let micros = self.started.elapsed().as_micros() as i32;
+            self.last_tag = micros.max(self.last_tag.wrapping_add(1));
+            self.last_tag
+        });
+        if let Some(tag) = tag {
+            frame.push(new_event(EV_MSC, MSC_TIMESTAMP, tag));
+        }
+        frame.push(new_event(EV_SYN, SYN_REPORT, 0));
+
+        let sent_wall = SystemTime::now();
+        let sent = Instant::now();
+        device.emit_batch(&frame)?;
+        self.pending.push_back(Pending {
+            tag,
+            sent,
+            sent_wall,
+            write: sent.elapsed(),
+        });
+        Ok(())
+    }
+
+    /// Read back whatever arrives within `timeout` until nothing sent is
+    /// outstanding. Returns how many frames were matched.
+    pub fn collect(&mut self, timeout: Duration) -> Result<usize> {
+        let deadline = Instant::now() + timeout;
+        let mut matched = 0;
+        while !self.pending.is_empty() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Some(frame) = self.reader.read_frame(remaining)? else {
+                break;
+            };
+            let received = Instant::now();
+            let tag = frame
+                .iter()
+                .find(|event| event.type_ == EV_MSC && event.code == MSC_TIMESTAMP)
+                .map(|event| event.value);
+            let index = match tag {
+                Some(tag) => self.pending.iter().position(|p| p.tag == Some(tag)),
+                None => (!self.pending.is_empty()).then_some(0),
+            };
+            let Some(index) = index else {
+                continue;
+            };
+            // Anything sent before a frame that made it was lost.
+            self.lost += index;
+            self.pending.drain(..index);
+            let Some(pending) = self.pending.pop_front() else {
+                break;
+            };
+            let kernel = frame.last().map(|event| {
+                UNIX_EPOCH
+                    + Duration::new(event.time.tv_sec as u64, event.time.tv_usec as u32 * 1000)
+            });
+            self.traces.push(FrameTrace {
+                tag: pending.tag,
+                sent: pending.sent - self.started,
+                write: pending.write,
+                to_kernel: kernel.and_then(|kernel| kernel.duration_since(pending.sent_wall).ok()),
+                delivered: received - pending.sent,
+            });
+            matched += 1;
+        }
+        Ok(matched)
+    }
+
+    /// Every frame traced so far.
+    pub fn traces(&self) -> &[FrameTrace] {
+        &self.traces
+    }
+
+    /// Frames that were sent but never read back.
+    pub fn lost(&self) -> usize {
+        self.lost
+    }
+
+    /// The traces as JSON, see to_json.
+    pub fn to_json(&self) -> String {
+        to_json(&self.traces)
+    }
+
+    /// The traces as folded stacks, see to_folded.
+    pub fn to_folded(&self) -> String {
+        to_folded(&self.traces)
+    }
+}
+
+/// Traces as a JSON array, durations in microseconds.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use uinput_rs::latency::{FrameTrace, to_folded, to_json};
+///
+/// let trace = FrameTrace {
+///     tag: Some(42),
+///     sent: Duration::from_micros(42),
+///     write: Duration::from_micros(3),
+///     to_kernel: Some(Duration::from_micros(5)),
+///     delivered: Duration::from_micros(20),
+/// };
+/// assert_eq!(
+///     to_json(&[trace]),
+///     r#"[{"tag":42,"sent_us":42,"write_us":3,"to_kernel_us":5,"delivered_us":20}]"#
+/// );
+/// assert_eq!(to_folded(&[trace, trace]), "inject;write 6\ninject;kernel 4\ninject;kernel;deliver 30\n");
+/// ```
+pub fn to_json(traces: &[FrameTrace]) -> String {
+    let micros = |duration: Option<Duration>| match duration {
+        Some(duration) => duration.as_micros().to_string(),
+        None => "null".to_owned(),
+    };
+    let mut out = String::from("[");
+    for (i, trace) in traces.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let _ = write!(
+            out,
+            r#"{{"tag":{},"sent_us":{},"write_us":{},"to_kernel_us":{},"delivered_us":{}}}"#,
+            trace.tag.map_or("null".to_owned(), |tag| tag.to_string()),
+            trace.sent.as_micros(),
+            trace.write.as_micros(),
+            micros(trace.to_kernel),
+            trace.delivered.as_micros(),
+        );
+    }
+    out.push(']');
+    out
+}
+
+/// Traces as folded stacks for flamegraph.pl or inferno, microseconds
+/// summed over every frame: the write, the rest of the way into the
+/// kernel and from the kernel to the reader.
+pub fn to_folded(traces: &[FrameTrace]) -> String {
+    let sum = |stage: fn(&FrameTrace) -> Option<Duration>| -> u128 {
+        traces.iter().filter_map(stage).map(|d| d.as_micros()).sum()
+    };
+    let write = sum(|trace| Some(trace.write));
+    let kernel = sum(|trace| trace.to_kernel.map(|to| to.saturating_sub(trace.write)));
+    let deliver = sum(FrameTrace::kernel_to_reader);
+    format!("inject;write {write}\ninject;kernel {kernel}\ninject;kernel;deliver {deliver}\n")
+}
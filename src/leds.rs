@@ -0,0 +1,26 @@
+use crate::key_codes::{LED_CAPSL, LED_NUML, LED_SCROLLL};
+
+/// Lock keys as the host sees them, from the LEDs it last set on the
+/// device. Everything reads as off until the host sets an LED, which
+/// usually happens as soon as a compositor or the console picks the
+/// device up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LockState {
+    /// Caps Lock.
+    pub caps: bool,
+    /// Num Lock.
+    pub num: bool,
+    /// Scroll Lock.
+    pub scroll: bool,
+}
+
+impl LockState {
+    pub(crate) fn from_bits(bits: u16) -> Self {
+        let on = |led: u16| bits & (1 << led) != 0;
+        Self {
+            caps: on(LED_CAPSL),
+            num: on(LED_NUML),
+            scroll: on(LED_SCROLLL),
+        }
+    }
+}
@@ -3,15 +3,139 @@
 // Expose these for convenience
 pub use libc::{input_event, input_id, timeval, uinput_user_dev};
 
+mod backend;
+mod broadcast;
+mod buffered;
+mod builder;
+mod capabilities;
+#[cfg(feature = "chaos")]
+mod chaos;
 mod device;
-/// Some key codes for convenience.
+mod display;
+mod error;
+mod guard;
+mod hooks;
+mod hygiene;
+/// Event codes by kind, with ALL lists and category predicates
 pub mod key_codes;
 /// Some key tuples for simple event enabling
 pub mod key_events;
 /// Some key types for convenience.
 pub mod key_types;
+mod keystate;
+mod leds;
+#[cfg(feature = "nix")]
+mod nix_sys;
+mod retry;
+mod ring;
+mod scope;
+mod selftest;
+mod stats;
 mod traits;
+mod wheel;
+pub use backend::*;
+pub use broadcast::*;
+pub use buffered::*;
+pub use builder::*;
+pub use capabilities::Capabilities;
+#[cfg(feature = "chaos")]
+pub use chaos::*;
 pub use device::*;
+pub use display::*;
+pub use error::Error;
+pub use guard::InputGuard;
+pub use hooks::HookId;
+pub use hygiene::*;
+pub use keystate::{DuplicatePolicy, StateSnapshot};
+pub use leds::LockState;
+/// Privileged helper that hands /dev/uinput devices to unprivileged processes
+pub mod broker;
+/// libinput style calibration matrices for absolute coordinates
+pub mod calibration;
+/// Stopping long running playback
+pub mod cancel;
+/// Clipboard access through wl-copy or xclip
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+/// Several nodes under one identity, like wireless receivers
+pub mod composite;
+/// Daemon building blocks
+#[cfg(feature = "daemon")]
+pub mod daemon;
 /// Random device presets
 pub mod devices;
+/// Reading events back from evdev nodes
+pub mod evdev;
+/// Force feedback effect decoding and playback
+pub mod ff;
+/// Gamepad with normalized axes and rumble
+pub mod gamepad;
+/// HID usage to evdev code mapping
+pub mod hid;
+/// Keyboard with rollover modes and raw scancodes
+pub mod keyboard;
+/// X11 keysym to evdev key code mapping
+pub mod keysyms;
+/// Injection to evdev latency traces
+pub mod latency;
+/// Media and brightness keys with hold-to-repeat
+pub mod media;
+/// Multitouch slots, tracking IDs, contact frames and touchscreens
+pub mod mt;
+/// Code names to values and back
+pub mod names;
+/// Pausing playback and recording from another thread
+pub mod pause;
+/// Realistic phys strings for common buses
+pub mod phys;
+/// Composable event transformers for the emit and remap paths
+pub mod pipeline;
+/// Replayable plans of actions across several devices
+pub mod plan;
+/// Timed replay of recorded events
+pub mod playback;
+/// Mice and absolute pointers with calibration and monitor layouts
+pub mod pointer;
+/// Dropping root once the devices are created
+pub mod privileges;
+/// QEMU QMP input-send-event translation
+#[cfg(feature = "qmp")]
+pub mod qmp;
+/// Realtime priority and CPU affinity helpers
+pub mod realtime;
+/// Reloading remaps when their config file changes
+pub mod reload;
+/// Key remapping and keyd config import
+pub mod remap;
+/// Detecting sandboxes and picking a way to inject input
+pub mod sandbox;
+/// AT set 2 scancodes for MSC_RAW
+pub mod scancodes;
+/// Canned power button, sleep button and lid scenarios
+pub mod scenarios;
+/// SDL GameController database identities for virtual gamepads
+pub mod sdl;
+/// Cleaning up devices on SIGINT and SIGTERM
+pub mod signals;
+/// Stress testing helpers for software that consumes input devices
+pub mod stress;
+/// Pen tablet proximity, hover and tool switching
+pub mod stylus;
+/// Assertions on what a device actually sends, for integration tests
+pub mod testkit;
+/// Drift free timing helpers
+pub mod timing;
+/// Builder and device that track enabled event types in the type system
+pub mod typed;
+/// udev rules for running without root
+pub mod udev;
+/// HID level virtual devices through /dev/uhid
+pub mod uhid;
+/// Tearing devices down when the owner hangs
+pub mod watchdog;
+pub use retry::RetryPolicy;
+pub use ring::*;
+pub use scope::{Scope, scope};
+pub use selftest::selftest;
+pub use stats::DeviceStats;
 pub use traits::*;
@@ -0,0 +1,203 @@
+use std::{io::Result, time::Duration};
+
+use crate::{
+    Device, SYN_REPORT,
+    device::new_event,
+    devices,
+    key_codes::{
+        KEY_BRIGHTNESSDOWN, KEY_BRIGHTNESSUP, KEY_MUTE, KEY_NEXTSONG, KEY_PLAYPAUSE,
+        KEY_PREVIOUSSONG, KEY_VOLUMEDOWN, KEY_VOLUMEUP,
+    },
+    key_types::{EV_KEY, EV_SYN},
+    timing::{Clock, Pacing, Scheduler},
+};
+
+/// Autorepeat cadence for held keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    /// Time from the press to the first repeat.
+    pub delay: Duration,
+    /// Time between repeats.
+    pub period: Duration,
+}
+
+impl Default for Repeat {
+    /// The kernel's defaults, 250 ms and 33 ms.
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(250),
+            period: Duration::from_millis(33),
+        }
+    }
+}
+
+/// Press `code`, send autorepeats (value 2) at `repeat`'s cadence and
+/// release it `duration` after the press, timed by `clock`. Each event is
+/// its own frame.
+pub fn hold_with_clock(
+    device: &Device,
+    code: u16,
+    duration: Duration,
+    repeat: Repeat,
+    clock: &impl Clock,
+) -> Result<()> {
+    let frame = |value| {
+        device.emit_batch(&[
+            new_event(EV_KEY, code, value),
+            new_event(EV_SYN, SYN_REPORT, 0),
+        ])
+    };
+    frame(1)?;
+    let start = clock.elapsed();
+    let mut next = repeat.delay;
+    while next < duration {
+        clock.wait_until(start + next, Pacing::Sleep)?;
+        frame(2)?;
+        next += repeat.period.max(Duration::from_millis(1));
+    }
+    clock.wait_until(start + duration, Pacing::Sleep)?;
+    frame(0)
+}
+
+/// Media and brightness keys, tapped or held. Held keys repeat the way a
+/// kernel keyboard driver would, since toolkits treat a stream of repeats
+/// differently from separate taps (volume OSDs speed up, for one).
+///
+/// The repeats are sent from here, so leave EV_REP off the device or the
+/// kernel repeats as well.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::MEDIA_KEY_EVENTS,
+///     key_codes::KEY_VOLUMEUP,
+///     media::MediaKeys,
+///     timing::ManualClock,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let device = Device::with_backend(mock.clone(), &MEDIA_KEY_EVENTS, &UInputUserDevice::default()).unwrap();
+/// let media = MediaKeys::from_device(device);
+///
+/// media.hold_with_clock(KEY_VOLUMEUP, Duration::from_secs(1), &ManualClock::instant()).unwrap();
+/// let values: Vec<i32> = mock.events().iter().filter(|e| e.code == KEY_VOLUMEUP).map(|e| e.value).collect();
+/// assert_eq!(values.first(), Some(&1));
+/// assert_eq!(values.last(), Some(&0));
+/// // 250 ms delay, then every 33 ms until the second is up.
+/// assert_eq!(values.iter().filter(|&&v| v == 2).count(), 23);
+/// ```
+#[derive(Debug)]
+pub struct MediaKeys {
+    device: Device,
+    repeat: Repeat,
+}
+
+impl MediaKeys {
+    /// Create a devices::media_keys.
+    pub fn new(name: &str) -> Result<Self> {
+        Ok(Self::from_device(devices::media_keys(name)?))
+    }
+
+    /// Wrap a device with the keys you're going to use.
+    pub fn from_device(device: Device) -> Self {
+        Self {
+            device,
+            repeat: Repeat::default(),
+        }
+    }
+
+    /// Change the repeat cadence for held keys.
+    pub fn set_repeat(&mut self, repeat: Repeat) {
+        self.repeat = repeat;
+    }
+
+    /// Press and release a key.
+    pub fn tap(&self, code: u16) -> Result<()> {
+        self.device.emit(EV_KEY, code, 1)?;
+        self.device.emit(EV_SYN, SYN_REPORT, 0)?;
+        self.device.emit(EV_KEY, code, 0)?;
+        self.device.emit(EV_SYN, SYN_REPORT, 0)
+    }
+
+    /// Hold a key for `duration`, with autorepeat. Blocks until it's let go.
+    pub fn hold(&self, code: u16, duration: Duration) -> Result<()> {
+        self.hold_with_clock(code, duration, &Scheduler::start())
+    }
+
+    /// hold with another clock, ManualClock in tests.
+    pub fn hold_with_clock(&self, code: u16, duration: Duration, clock: &impl Clock) -> Result<()> {
+        hold_with_clock(&self.device, code, duration, self.repeat, clock)
+    }
+
+    /// Tap volume up.
+    pub fn volume_up(&self) -> Result<()> {
+        self.tap(KEY_VOLUMEUP)
+    }
+
+    /// Hold volume up.
+    pub fn volume_up_hold(&self, duration: Duration) -> Result<()> {
+        self.hold(KEY_VOLUMEUP, duration)
+    }
+
+    /// Tap volume down.
+    pub fn volume_down(&self) -> Result<()> {
+        self.tap(KEY_VOLUMEDOWN)
+    }
+
+    /// Hold volume down.
+    pub fn volume_down_hold(&self, duration: Duration) -> Result<()> {
+        self.hold(KEY_VOLUMEDOWN, duration)
+    }
+
+    /// Tap mute.
+    pub fn mute(&self) -> Result<()> {
+        self.tap(KEY_MUTE)
+    }
+
+    /// Tap brightness up.
+    pub fn brightness_up(&self) -> Result<()> {
+        self.tap(KEY_BRIGHTNESSUP)
+    }
+
+    /// Hold brightness up.
+    pub fn brightness_up_hold(&self, duration: Duration) -> Result<()> {
+        self.hold(KEY_BRIGHTNESSUP, duration)
+    }
+
+    /// Tap brightness down.
+    pub fn brightness_down(&self) -> Result<()> {
+        self.tap(KEY_BRIGHTNESSDOWN)
+    }
+
+    /// Hold brightness down.
+    pub fn brightness_down_hold(&self, duration: Duration) -> Result<()> {
+        self.hold(KEY_BRIGHTNESSDOWN, duration)
+    }
+
+    /// Tap play/pause.
+    pub fn play_pause(&self) -> Result<()> {
+        self.tap(KEY_PLAYPAUSE)
+    }
+
+    /// Tap next track.
+    pub fn next(&self) -> Result<()> {
+        self.tap(KEY_NEXTSONG)
+    }
+
+    /// Tap previous track.
+    pub fn previous(&self) -> Result<()> {
+        self.tap(KEY_PREVIOUSSONG)
+    }
+
+    /// The wrapped device.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Unwrap the device.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+}
@@ -0,0 +1,971 @@
+use std::{
+    io::{self, Result},
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use libc::input_event;
+
+use crate::{
+    Device, SYN_REPORT,
+    calibration::Calibration,
+    cancel::{self, CancelToken},
+    device::new_event,
+    devices,
+    key_codes::{
+        ABS_MT_ORIENTATION, ABS_MT_POSITION_X, ABS_MT_POSITION_Y, ABS_MT_PRESSURE, ABS_MT_SLOT,
+        ABS_MT_TOOL_TYPE, ABS_MT_TOUCH_MAJOR, ABS_MT_TOUCH_MINOR, ABS_MT_TRACKING_ID, ABS_X, ABS_Y,
+        BTN_LEFT, BTN_TOOL_DOUBLETAP, BTN_TOOL_FINGER, BTN_TOOL_QUADTAP, BTN_TOOL_QUINTTAP,
+        BTN_TOOL_TRIPLETAP, BTN_TOUCH, MT_TOOL_FINGER, MT_TOOL_PALM,
+    },
+    key_types::{EV_ABS, EV_KEY, EV_SYN},
+    timing::{Clock, Pacing, Scheduler},
+};
+
+/// Tracking ID generator for ABS_MT_TRACKING_ID.
+///
+/// IDs count up from 0 and wrap after max. Wrapping never hands out -1
+/// (which means "lifted") or an ID that's still active, so a long session
+/// can't end up with two contacts sharing an ID.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::mt::TrackingIds;
+///
+/// let mut ids = TrackingIds::with_max(2);
+/// let a = ids.allocate().unwrap();
+/// let b = ids.allocate().unwrap();
+/// ids.release(a);
+/// let c = ids.allocate().unwrap(); // 2
+/// let d = ids.allocate().unwrap(); // wraps to 0, free again since a was released
+///
+/// assert_eq!((a, b, c, d), (0, 1, 2, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrackingIds {
+    next: i32,
+    max: i32,
+    active: Vec<i32>,
+}
+
+impl Default for TrackingIds {
+    fn default() -> Self {
+        Self::with_max(65535)
+    }
+}
+
+impl TrackingIds {
+    /// IDs from 0 to 65535, the range devices::touchscreen advertises.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// IDs from 0 to max. Match the device's ABS_MT_TRACKING_ID absmax.
+    pub fn with_max(max: i32) -> Self {
+        Self {
+            next: 0,
+            max: max.max(0),
+            active: Vec::new(),
+        }
+    }
+
+    /// A fresh ID, or None when every ID in the range is active.
+    pub fn allocate(&mut self) -> Option<i32> {
+        for _ in 0..=self.max {
+            let id = self.next;
+            self.next = if id >= self.max { 0 } else { id + 1 };
+            if !self.active.contains(&id) {
+                self.active.push(id);
+                return Some(id);
+            }
+        }
+        None
+    }
+
+    /// The contact with this ID lifted. Its ID can be handed out again
+    /// once the counter wraps around to it.
+    pub fn release(&mut self, id: i32) {
+        self.active.retain(|&active| active != id);
+    }
+
+    /// IDs currently handed out.
+    pub fn active(&self) -> &[i32] {
+        &self.active
+    }
+}
+
+/// Which free slot a new contact gets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReusePolicy {
+    /// The lowest free slot, like most touch controllers.
+    #[default]
+    Lowest,
+    /// The slot that's been free the longest. Makes bugs that keep stale
+    /// per-slot state show up instead of being papered over.
+    LeastRecentlyUsed,
+}
+
+/// Tracks which ABS_MT_SLOTs hold a contact and with which tracking ID.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::mt::{ReusePolicy, SlotPool, TrackingIds};
+///
+/// let mut ids = TrackingIds::new();
+/// let mut slots = SlotPool::new(2).policy(ReusePolicy::LeastRecentlyUsed);
+///
+/// let first = slots.acquire(ids.allocate().unwrap()).unwrap();
+/// slots.acquire(ids.allocate().unwrap()).unwrap();
+/// assert!(slots.acquire(99).is_none()); // full
+///
+/// ids.release(slots.release(first).unwrap());
+/// assert_eq!(slots.acquire(ids.allocate().unwrap()), Some(first));
+/// assert_eq!(slots.active(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SlotPool {
+    slots: Vec<Option<i32>>,
+    released_at: Vec<u64>,
+    releases: u64,
+    policy: ReusePolicy,
+}
+
+impl SlotPool {
+    /// A pool of count slots, ABS_MT_SLOT absmax + 1.
+    pub fn new(count: usize) -> Self {
+        Self {
+            slots: vec![None; count],
+            released_at: vec![0; count],
+            releases: 0,
+            policy: ReusePolicy::default(),
+        }
+    }
+
+    /// How free slots are picked.
+    pub fn policy(mut self, policy: ReusePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Put a contact with tracking_id into a free slot.
+    /// None when every slot is taken.
+    pub fn acquire(&mut self, tracking_id: i32) -> Option<usize> {
+        let free = (0..self.slots.len()).filter(|&slot| self.slots[slot].is_none());
+        let slot = match self.policy {
+            ReusePolicy::Lowest => free.min()?,
+            ReusePolicy::LeastRecentlyUsed => free.min_by_key(|&slot| self.released_at[slot])?,
+        };
+        self.slots[slot] = Some(tracking_id);
+        Some(slot)
+    }
+
+    /// Free a slot, returning the tracking ID it held.
+    pub fn release(&mut self, slot: usize) -> Option<i32> {
+        let id = self.slots.get_mut(slot)?.take()?;
+        self.releases += 1;
+        self.released_at[slot] = self.releases;
+        Some(id)
+    }
+
+    /// Tracking ID in a slot, None when it's free.
+    pub fn tracking_id(&self, slot: usize) -> Option<i32> {
+        self.slots.get(slot).copied().flatten()
+    }
+
+    /// Slot holding the contact with this tracking ID.
+    pub fn slot_of(&self, tracking_id: i32) -> Option<usize> {
+        self.slots.iter().position(|&id| id == Some(tracking_id))
+    }
+
+    /// Number of slots holding a contact.
+    pub fn active(&self) -> usize {
+        self.slots.iter().filter(|id| id.is_some()).count()
+    }
+
+    /// Total number of slots.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+/// What touches the surface. Sizes and pressure are fractions of the
+/// device's absmax for that axis, so one profile fits any device.
+///
+/// Axes the device doesn't have are left out of the frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactProfile {
+    /// ABS_MT_TOOL_TYPE value, MT_TOOL_*.
+    pub tool_type: i32,
+    /// BTN_TOOL_* code held while the contact is down.
+    pub btn_tool: u16,
+    /// ABS_MT_TOUCH_MAJOR as a fraction of its absmax.
+    pub major: f32,
+    /// ABS_MT_TOUCH_MINOR as a fraction of its absmax.
+    pub minor: f32,
+    /// ABS_MT_PRESSURE as a fraction of its absmax.
+    pub pressure: f32,
+    /// ABS_MT_ORIENTATION from -1.0 to 1.0, mapped onto absmin..=absmax.
+    /// 0.0 is the major axis along Y, ±1.0 is a quarter turn either way.
+    pub orientation: f32,
+}
+
+impl ContactProfile {
+    /// A fingertip: small, round-ish and light.
+    pub const FINGER: Self = Self {
+        tool_type: MT_TOOL_FINGER,
+        btn_tool: BTN_TOOL_FINGER,
+        major: 0.12,
+        minor: 0.10,
+        pressure: 0.4,
+        orientation: 0.0,
+    };
+
+    /// A thumb: larger and more elongated than a finger, still a finger to
+    /// the kernel. libinput treats these specially near the bottom edge.
+    pub const THUMB: Self = Self {
+        tool_type: MT_TOOL_FINGER,
+        btn_tool: BTN_TOOL_FINGER,
+        major: 0.30,
+        minor: 0.18,
+        pressure: 0.6,
+        orientation: 0.3,
+    };
+
+    /// A resting palm: huge and heavy, with MT_TOOL_PALM like firmware that
+    /// does its own palm detection.
+    pub const PALM: Self = Self {
+        tool_type: MT_TOOL_PALM,
+        btn_tool: BTN_TOOL_FINGER,
+        major: 0.85,
+        minor: 0.60,
+        pressure: 0.9,
+        orientation: 0.0,
+    };
+}
+
+impl Default for ContactProfile {
+    fn default() -> Self {
+        Self::FINGER
+    }
+}
+
+/// A fraction of an axis range, rounded.
+fn scaled(device: &Device, code: u16, fraction: f32) -> i32 {
+    let (min, max) = device.abs_range(code);
+    min + ((max - min) as f32 * fraction.clamp(0.0, 1.0)).round() as i32
+}
+
+/// -1.0..=1.0 onto an axis range, rounded.
+fn centered(device: &Device, code: u16, fraction: f32) -> i32 {
+    scaled(device, code, (fraction.clamp(-1.0, 1.0) + 1.0) / 2.0)
+}
+
+fn has_abs(device: &Device, code: u16) -> bool {
+    device.capabilities().contains(EV_ABS, code)
+}
+
+/// Writes type B multitouch frames: slot selection, tracking IDs, contact
+/// profiles, BTN_TOUCH and single touch emulation. Holds only the
+/// bookkeeping, the device is passed in.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::TOUCHSCREEN_EVENTS,
+///     key_codes::{ABS_MT_TOUCH_MAJOR, ABS_MT_TRACKING_ID},
+///     key_events::ABS_MT_TOUCH_MAJOR_EVENT,
+///     mt::{ContactProfile, Touch},
+/// };
+///
+/// let mock = MockBackend::new();
+/// let mut info = UInputUserDevice::default();
+/// info.absinfo(ABS_MT_TOUCH_MAJOR_EVENT, 0, 100, 0, 0);
+/// let device = Device::with_backend(mock.clone(), &TOUCHSCREEN_EVENTS, &info).unwrap();
+///
+/// let mut touch = Touch::new(10);
+/// let palm = touch.down(&device, 500, 500, &ContactProfile::PALM).unwrap();
+/// touch.up(&device, palm).unwrap();
+///
+/// let frames = mock.frames();
+/// assert!(frames[0].iter().any(|e| e.code == ABS_MT_TOUCH_MAJOR && e.value == 85));
+/// assert!(frames[1].iter().any(|e| e.code == ABS_MT_TRACKING_ID && e.value == -1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Touch {
+    slots: SlotPool,
+    ids: TrackingIds,
+    profiles: Vec<ContactProfile>,
+    positions: Vec<(i32, i32)>,
+    held_tool: Option<u16>,
+}
+
+impl Touch {
+    /// Bookkeeping for a device with this many slots.
+    pub fn new(slots: usize) -> Self {
+        Self::with_pool(SlotPool::new(slots), TrackingIds::new())
+    }
+
+    /// Bring your own pool and ID generator, e.g. for a different reuse
+    /// policy or a smaller tracking ID range.
+    pub fn with_pool(slots: SlotPool, ids: TrackingIds) -> Self {
+        let count = slots.capacity();
+        Self {
+            slots,
+            ids,
+            profiles: vec![ContactProfile::default(); count],
+            positions: vec![(0, 0); count],
+            held_tool: None,
+        }
+    }
+
+    /// Number of contacts down.
+    pub fn active(&self) -> usize {
+        self.slots.active()
+    }
+
+    /// Slot bookkeeping, read only.
+    pub fn slots(&self) -> &SlotPool {
+        &self.slots
+    }
+
+    /// Put a contact down and send the frame. Returns its slot. If the frame
+    /// can't be sent the contact isn't kept, so the slot and ID are free
+    /// again.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend,
+    ///     devices::{TOUCHSCREEN_EVENTS, touchscreen_info},
+    ///     mt::{ContactProfile, Touch},
+    /// };
+    ///
+    /// // Destroyed when the scope ended, so every write to it fails.
+    /// let info = touchscreen_info("screen", 1000, 1000, 255, 255, 255);
+    /// let gone = uinput_rs::scope(|devices| {
+    ///     Ok(devices.adopt(Device::with_backend(MockBackend::new(), &TOUCHSCREEN_EVENTS, &info)?))
+    /// })
+    /// .unwrap();
+    ///
+    /// let mut touch = Touch::new(1);
+    /// assert!(touch.down(&gone, 500, 500, &ContactProfile::default()).is_err());
+    /// assert_eq!(touch.active(), 0);
+    /// assert_eq!(touch.tool(), None);
+    /// ```
+    pub fn down(
+        &mut self,
+        device: &Device,
+        x: i32,
+        y: i32,
+        profile: &ContactProfile,
+    ) -> Result<usize> {
+        let id = self
+            .ids
+            .allocate()
+            .ok_or_else(|| io::Error::other("out of tracking IDs"))?;
+        let Some(slot) = self.slots.acquire(id) else {
+            self.ids.release(id);
+            return Err(io::Error::other(format!(
+                "all {} slots are in use",
+                self.slots.capacity()
+            )));
+        };
+        self.profiles[slot] = *profile;
+        self.positions[slot] = (x, y);
+
+        let mut frame = vec![
+            new_event(EV_ABS, ABS_MT_SLOT, slot as i32),
+            new_event(EV_ABS, ABS_MT_TRACKING_ID, id),
+        ];
+        self.push_contact(device, &mut frame, slot, true);
+        if self.active() == 1 {
+            frame.push(new_event(EV_KEY, BTN_TOUCH, 1));
+        }
+        let held_tool = self.held_tool;
+        self.push_tool(device, &mut frame);
+        if let Err(err) = self.finish(device, frame) {
+            // The kernel never saw the contact, so don't keep it.
+            self.slots.release(slot);
+            self.ids.release(id);
+            self.held_tool = held_tool;
+            return Err(err);
+        }
+        Ok(slot)
+    }
+
+    /// Move a contact and send the frame.
+    pub fn move_to(&mut self, device: &Device, slot: usize, x: i32, y: i32) -> Result<()> {
+        self.check(slot)?;
+        self.positions[slot] = (x, y);
+        let mut frame = vec![new_event(EV_ABS, ABS_MT_SLOT, slot as i32)];
+        self.push_contact(device, &mut frame, slot, false);
+        self.finish(device, frame)
+    }
+
+    /// Move several contacts, (slot, x, y) each, in one frame, so they
+    /// don't arrive as separate motions.
+    pub fn move_all(&mut self, device: &Device, moves: &[(usize, i32, i32)]) -> Result<()> {
+        for &(slot, _, _) in moves {
+            self.check(slot)?;
+        }
+        let mut frame = Vec::with_capacity(moves.len() * 3 + 1);
+        for &(slot, x, y) in moves {
+            self.positions[slot] = (x, y);
+            frame.push(new_event(EV_ABS, ABS_MT_SLOT, slot as i32));
+            self.push_contact(device, &mut frame, slot, false);
+        }
+        self.finish(device, frame)
+    }
+
+    /// Turn a contact and send the frame. orientation works like
+    /// ContactProfile::orientation.
+    pub fn rotate(&mut self, device: &Device, slot: usize, orientation: f32) -> Result<()> {
+        self.check(slot)?;
+        self.profiles[slot].orientation = orientation;
+        let mut frame = vec![new_event(EV_ABS, ABS_MT_SLOT, slot as i32)];
+        if has_abs(device, ABS_MT_ORIENTATION) {
+            frame.push(new_event(
+                EV_ABS,
+                ABS_MT_ORIENTATION,
+                centered(device, ABS_MT_ORIENTATION, orientation),
+            ));
+        }
+        self.finish(device, frame)
+    }
+
+    /// Lift a contact and send the frame.
+    pub fn up(&mut self, device: &Device, slot: usize) -> Result<()> {
+        self.check(slot)?;
+        if let Some(id) = self.slots.release(slot) {
+            self.ids.release(id);
+        }
+        let mut frame = vec![
+            new_event(EV_ABS, ABS_MT_SLOT, slot as i32),
+            new_event(EV_ABS, ABS_MT_TRACKING_ID, -1),
+        ];
+        if self.active() == 0 {
+            frame.push(new_event(EV_KEY, BTN_TOUCH, 0));
+        }
+        self.push_tool(device, &mut frame);
+        self.finish(device, frame)
+    }
+
+    /// BTN_TOOL_* for the current finger count. One contact uses its
+    /// profile's tool, more use DOUBLETAP up to QUINTTAP.
+    pub fn tool(&self) -> Option<u16> {
+        match self.active() {
+            0 => None,
+            1 => {
+                let slot = self.first_active()?;
+                Some(self.profiles[slot].btn_tool)
+            }
+            2 => Some(BTN_TOOL_DOUBLETAP),
+            3 => Some(BTN_TOOL_TRIPLETAP),
+            4 => Some(BTN_TOOL_QUADTAP),
+            _ => Some(BTN_TOOL_QUINTTAP),
+        }
+    }
+
+    /// Swap the held BTN_TOOL_* when the finger count changed. Codes the
+    /// device doesn't have are skipped.
+    fn push_tool(&mut self, device: &Device, frame: &mut Vec<input_event>) {
+        let tool = self.tool();
+        if tool == self.held_tool {
+            return;
+        }
+        let has_key = |code| device.capabilities().contains(EV_KEY, code);
+        if let Some(old) = self.held_tool.filter(|&code| has_key(code)) {
+            frame.push(new_event(EV_KEY, old, 0));
+        }
+        if let Some(new) = tool.filter(|&code| has_key(code)) {
+            frame.push(new_event(EV_KEY, new, 1));
+        }
+        self.held_tool = tool;
+    }
+
+    fn first_active(&self) -> Option<usize> {
+        (0..self.slots.capacity()).find(|&slot| self.slots.tracking_id(slot).is_some())
+    }
+
+    fn check(&self, slot: usize) -> Result<()> {
+        match self.slots.tracking_id(slot) {
+            Some(_) => Ok(()),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("slot {slot} has no contact"),
+            )),
+        }
+    }
+
+    /// Position, and on touch down the profile axes too.
+    fn push_contact(&self, device: &Device, frame: &mut Vec<input_event>, slot: usize, new: bool) {
+        let (x, y) = self.positions[slot];
+        frame.push(new_event(EV_ABS, ABS_MT_POSITION_X, x));
+        frame.push(new_event(EV_ABS, ABS_MT_POSITION_Y, y));
+        if !new {
+            return;
+        }
+        let profile = &self.profiles[slot];
+        if has_abs(device, ABS_MT_TOOL_TYPE) {
+            frame.push(new_event(EV_ABS, ABS_MT_TOOL_TYPE, profile.tool_type));
+        }
+        for (code, fraction) in [
+            (ABS_MT_TOUCH_MAJOR, profile.major),
+            (ABS_MT_TOUCH_MINOR, profile.minor),
+            (ABS_MT_PRESSURE, profile.pressure),
+        ] {
+            if has_abs(device, code) {
+                frame.push(new_event(EV_ABS, code, scaled(device, code, fraction)));
+            }
+        }
+        if has_abs(device, ABS_MT_ORIENTATION) {
+            let orientation = centered(device, ABS_MT_ORIENTATION, profile.orientation);
+            frame.push(new_event(EV_ABS, ABS_MT_ORIENTATION, orientation));
+        }
+    }
+
+    /// Single touch emulation follows the lowest active slot, then SYN.
+    fn finish(&self, device: &Device, mut frame: Vec<input_event>) -> Result<()> {
+        if let Some(slot) = self.first_active() {
+            let (x, y) = self.positions[slot];
+            if has_abs(device, ABS_X) {
+                frame.push(new_event(EV_ABS, ABS_X, x));
+            }
+            if has_abs(device, ABS_Y) {
+                frame.push(new_event(EV_ABS, ABS_Y, y));
+            }
+        }
+        frame.push(new_event(EV_SYN, SYN_REPORT, 0));
+        device.emit_batch(&frame)
+    }
+}
+
+/// A touchpad that keeps BTN_TOUCH and BTN_TOOL_FINGER/DOUBLETAP/
+/// TRIPLETAP/QUADTAP/QUINTTAP in step with the contacts, which libinput
+/// relies on to count fingers for gestures and tapping.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::TOUCHPAD_EVENTS,
+///     key_codes::{BTN_TOOL_DOUBLETAP, BTN_TOOL_FINGER},
+///     mt::TouchPad,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let device = Device::with_backend(mock.clone(), &TOUCHPAD_EVENTS, &UInputUserDevice::default()).unwrap();
+/// let mut pad = TouchPad::from_device(device, 5);
+///
+/// let first = pad.down(100, 100).unwrap();
+/// pad.down(200, 100).unwrap();
+/// pad.up(first).unwrap();
+///
+/// let tools: Vec<(u16, i32)> = mock.events().iter()
+///     .filter(|e| e.code == BTN_TOOL_FINGER || e.code == BTN_TOOL_DOUBLETAP)
+///     .map(|e| (e.code, e.value))
+///     .collect();
+/// assert_eq!(tools, [
+///     (BTN_TOOL_FINGER, 1),
+///     (BTN_TOOL_FINGER, 0), (BTN_TOOL_DOUBLETAP, 1),
+///     (BTN_TOOL_DOUBLETAP, 0), (BTN_TOOL_FINGER, 1),
+/// ]);
+/// ```
+#[derive(Debug)]
+pub struct TouchPad {
+    device: Device,
+    touch: Touch,
+}
+
+impl TouchPad {
+    /// Create a devices::touchpad.
+    pub fn new(name: &str, max_x: i32, max_y: i32, width_mm: f32, height_mm: f32) -> Result<Self> {
+        Ok(Self::from_device(
+            devices::touchpad(name, max_x, max_y, width_mm, height_mm)?,
+            5,
+        ))
+    }
+
+    /// Wrap a device with this many slots (ABS_MT_SLOT absmax + 1).
+    pub fn from_device(device: Device, slots: usize) -> Self {
+        Self {
+            device,
+            touch: Touch::new(slots),
+        }
+    }
+
+    /// Put a finger down. Returns its slot.
+    pub fn down(&mut self, x: i32, y: i32) -> Result<usize> {
+        self.touch.down(&self.device, x, y, &ContactProfile::FINGER)
+    }
+
+    /// Put a thumb, palm or anything else down.
+    pub fn down_with(&mut self, x: i32, y: i32, profile: &ContactProfile) -> Result<usize> {
+        self.touch.down(&self.device, x, y, profile)
+    }
+
+    /// Move a contact.
+    pub fn move_to(&mut self, slot: usize, x: i32, y: i32) -> Result<()> {
+        self.touch.move_to(&self.device, slot, x, y)
+    }
+
+    /// Turn a contact, see ContactProfile::orientation.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     devices::TOUCHPAD_EVENTS,
+    ///     key_codes::ABS_MT_ORIENTATION,
+    ///     key_events::ABS_MT_ORIENTATION_EVENT,
+    ///     mt::TouchPad,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut info = UInputUserDevice::default();
+    /// info.absinfo(ABS_MT_ORIENTATION_EVENT, -90, 90, 0, 0);
+    /// let device = Device::with_backend(mock.clone(), &TOUCHPAD_EVENTS, &info).unwrap();
+    /// let mut pad = TouchPad::from_device(device, 5);
+    ///
+    /// let finger = pad.down(100, 100).unwrap();
+    /// pad.rotate(finger, -0.5).unwrap();
+    ///
+    /// let orientation: Vec<i32> = mock.events().iter()
+    ///     .filter(|e| e.code == ABS_MT_ORIENTATION)
+    ///     .map(|e| e.value)
+    ///     .collect();
+    /// assert_eq!(orientation, [0, -45]);
+    /// ```
+    pub fn rotate(&mut self, slot: usize, orientation: f32) -> Result<()> {
+        self.touch.rotate(&self.device, slot, orientation)
+    }
+
+    /// Lift a contact.
+    pub fn up(&mut self, slot: usize) -> Result<()> {
+        self.touch.up(&self.device, slot)
+    }
+
+    /// Press and release the pad's button.
+    pub fn click(&self) -> Result<()> {
+        for value in [1, 0] {
+            self.device.emit(EV_KEY, BTN_LEFT, value)?;
+            self.device.sync()?;
+        }
+        Ok(())
+    }
+
+    /// Number of contacts down.
+    pub fn finger_count(&self) -> usize {
+        self.touch.active()
+    }
+
+    /// The underlying device.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Take the device back.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+}
+
+/// How often gestures send a frame, about 120Hz like most touchscreens.
+const GESTURE_INTERVAL: Duration = Duration::from_millis(8);
+
+/// A touchscreen that hands out a Contact per finger. Coordinates are in
+/// device units and go through the calibration before they're sent.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend,
+///     devices::{TOUCHSCREEN_EVENTS, touchscreen_info},
+///     key_codes::{ABS_MT_POSITION_X, ABS_MT_TRACKING_ID},
+///     mt::TouchScreen,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let info = touchscreen_info("screen", 1000, 1000, 255, 255, 255);
+/// let device = Device::with_backend(mock.clone(), &TOUCHSCREEN_EVENTS, &info).unwrap();
+/// let screen = TouchScreen::from_device(device);
+///
+/// let thumb = screen.down(100, 900).unwrap();
+/// let finger = screen.down(500, 500).unwrap();
+/// finger.move_to(600, 500).unwrap();
+/// finger.lift().unwrap();
+/// assert_eq!(screen.contacts(), 1);
+/// drop(thumb); // dropping a contact lifts it too
+/// assert_eq!(screen.contacts(), 0);
+///
+/// let lifts = mock.events().iter().filter(|e| e.code == ABS_MT_TRACKING_ID && e.value == -1).count();
+/// assert_eq!(lifts, 2);
+/// assert!(mock.events().iter().any(|e| e.code == ABS_MT_POSITION_X && e.value == 600));
+/// ```
+#[derive(Debug)]
+pub struct TouchScreen {
+    device: Device,
+    touch: Mutex<Touch>,
+    calibration: Calibration,
+    cancel: Option<CancelToken>,
+}
+
+impl TouchScreen {
+    /// Create a devices::touchscreen with 0..=255 pressure and contact
+    /// sizes.
+    pub fn new(name: &str, max_x: i32, max_y: i32) -> Result<Self> {
+        Ok(Self::from_device(devices::touchscreen(
+            name, max_x, max_y, 255, 255, 255,
+        )?))
+    }
+
+    /// Wrap a multitouch device. The slot count comes from ABS_MT_SLOT.
+    pub fn from_device(device: Device) -> Self {
+        let (min, max) = device.abs_range(ABS_MT_SLOT);
+        let slots = (max - min + 1).max(1) as usize;
+        Self {
+            device,
+            touch: Mutex::new(Touch::new(slots)),
+            calibration: Calibration::IDENTITY,
+            cancel: None,
+        }
+    }
+
+    /// The calibration, the identity unless changed.
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Run every coordinate through `calibration` before it's emitted.
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// Stop gestures between frames once `token` is cancelled, lifting
+    /// their fingers. None to always finish them.
+    pub fn set_cancel_token(&mut self, token: Option<CancelToken>) {
+        self.cancel = token;
+    }
+
+    /// Put a finger down.
+    pub fn down(&self, x: i32, y: i32) -> Result<Contact<'_>> {
+        self.down_with(x, y, &ContactProfile::FINGER)
+    }
+
+    /// Put a thumb, palm or anything else down.
+    pub fn down_with(&self, x: i32, y: i32, profile: &ContactProfile) -> Result<Contact<'_>> {
+        let (x, y) = self.calibrate(x, y);
+        let slot = self.touch().down(&self.device, x, y, profile)?;
+        Ok(Contact { screen: self, slot })
+    }
+
+    /// Number of contacts down.
+    pub fn contacts(&self) -> usize {
+        self.touch().active()
+    }
+
+    /// Touch and lift right away.
+    pub fn tap(&self, x: i32, y: i32) -> Result<()> {
+        self.down(x, y)?.lift()
+    }
+
+    /// Touch and hold for `hold` before lifting.
+    pub fn long_press(&self, x: i32, y: i32, hold: Duration) -> Result<()> {
+        self.long_press_with_clock(x, y, hold, &Scheduler::start())
+    }
+
+    /// long_press with another clock, ManualClock in tests.
+    pub fn long_press_with_clock(
+        &self,
+        x: i32,
+        y: i32,
+        hold: Duration,
+        clock: &impl Clock,
+    ) -> Result<()> {
+        let contact = self.down(x, y)?;
+        let offset = clock.elapsed() + hold;
+        cancel::wait_until(clock, offset, Pacing::Sleep, self.cancel.as_ref())?;
+        contact.lift()
+    }
+
+    /// Drag one finger from `from` to `to` over `duration`.
+    pub fn swipe(&self, from: (i32, i32), to: (i32, i32), duration: Duration) -> Result<()> {
+        self.swipe_with_clock(from, to, duration, &Scheduler::start())
+    }
+
+    /// swipe with another clock, ManualClock in tests.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    /// use uinput_rs::{
+    ///     Device, MockBackend,
+    ///     devices::{TOUCHSCREEN_EVENTS, touchscreen_info},
+    ///     key_codes::ABS_MT_POSITION_Y,
+    ///     mt::TouchScreen,
+    ///     timing::ManualClock,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let info = touchscreen_info("screen", 1000, 1000, 255, 255, 255);
+    /// let device = Device::with_backend(mock.clone(), &TOUCHSCREEN_EVENTS, &info).unwrap();
+    /// let screen = TouchScreen::from_device(device);
+    ///
+    /// screen.swipe_with_clock((500, 800), (500, 200), Duration::from_millis(80), &ManualClock::instant()).unwrap();
+    /// let ys: Vec<i32> = mock.events().iter().filter(|e| e.code == ABS_MT_POSITION_Y).map(|e| e.value).collect();
+    /// assert_eq!(ys.len(), 11);
+    /// assert_eq!((ys[0], ys[10]), (800, 200));
+    /// ```
+    pub fn swipe_with_clock(
+        &self,
+        from: (i32, i32),
+        to: (i32, i32),
+        duration: Duration,
+        clock: &impl Clock,
+    ) -> Result<()> {
+        let contact = self.down(from.0, from.1)?;
+        self.animate(duration, clock, |t| {
+            contact.move_to(lerp(from.0, to.0, t), lerp(from.1, to.1, t))
+        })?;
+        contact.lift()
+    }
+
+    /// Two fingers either side of `center`, horizontally, going from
+    /// `from` to `to` apart over `duration`. Closer is pinch in, further
+    /// apart zooms.
+    pub fn pinch(&self, center: (i32, i32), from: i32, to: i32, duration: Duration) -> Result<()> {
+        self.pinch_with_clock(center, from, to, duration, &Scheduler::start())
+    }
+
+    /// pinch with another clock, ManualClock in tests.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    /// use uinput_rs::{
+    ///     Device, MockBackend,
+    ///     devices::{TOUCHSCREEN_EVENTS, touchscreen_info},
+    ///     key_codes::ABS_MT_POSITION_X,
+    ///     mt::TouchScreen,
+    ///     timing::ManualClock,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let info = touchscreen_info("screen", 1000, 1000, 255, 255, 255);
+    /// let device = Device::with_backend(mock.clone(), &TOUCHSCREEN_EVENTS, &info).unwrap();
+    /// let screen = TouchScreen::from_device(device);
+    ///
+    /// screen.pinch_with_clock((500, 500), 100, 600, Duration::from_millis(160), &ManualClock::instant()).unwrap();
+    /// let xs: Vec<i32> = mock.events().iter().filter(|e| e.code == ABS_MT_POSITION_X).map(|e| e.value).collect();
+    /// assert_eq!(&xs[..2], [450, 550]);
+    /// assert_eq!(&xs[xs.len() - 2..], [200, 800]);
+    /// assert_eq!(screen.contacts(), 0);
+    ///
+    /// // Both fingers move in the same frame each step.
+    /// let frames = mock.frames();
+    /// let step = &frames[2];
+    /// assert_eq!(step.iter().filter(|e| e.code == ABS_MT_POSITION_X).count(), 2);
+    /// ```
+    pub fn pinch_with_clock(
+        &self,
+        center: (i32, i32),
+        from: i32,
+        to: i32,
+        duration: Duration,
+        clock: &impl Clock,
+    ) -> Result<()> {
+        let (x, y) = center;
+        let left = self.down(x - from / 2, y)?;
+        let right = self.down(x + from / 2, y)?;
+        self.animate(duration, clock, |t| {
+            let half = lerp(from, to, t) / 2;
+            let (left_x, left_y) = self.calibrate(x - half, y);
+            let (right_x, right_y) = self.calibrate(x + half, y);
+            self.touch().move_all(
+                &self.device,
+                &[(left.slot, left_x, left_y), (right.slot, right_x, right_y)],
+            )
+        })?;
+        left.lift()?;
+        right.lift()
+    }
+
+    /// The underlying device.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Take the device back.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+
+    /// Call `step` with 0..=1 spread over `duration`, one frame per
+    /// GESTURE_INTERVAL. Contacts the gesture holds lift when it fails.
+    fn animate(
+        &self,
+        duration: Duration,
+        clock: &impl Clock,
+        mut step: impl FnMut(f32) -> Result<()>,
+    ) -> Result<()> {
+        let steps = (duration.as_nanos() / GESTURE_INTERVAL.as_nanos()).max(1) as u32;
+        let start = clock.elapsed();
+        for i in 1..=steps {
+            let t = i as f32 / steps as f32;
+            let offset = start + duration.mul_f32(t);
+            cancel::wait_until(clock, offset, Pacing::Sleep, self.cancel.as_ref())?;
+            step(t)?;
+        }
+        Ok(())
+    }
+
+    fn touch(&self) -> MutexGuard<'_, Touch> {
+        self.touch.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn calibrate(&self, x: i32, y: i32) -> (i32, i32) {
+        let x_range = self.device.abs_range(ABS_MT_POSITION_X);
+        let y_range = self.device.abs_range(ABS_MT_POSITION_Y);
+        self.calibration.apply_in(x, y, x_range, y_range)
+    }
+}
+
+/// One finger on a TouchScreen. Dropping it lifts the finger.
+#[derive(Debug)]
+pub struct Contact<'a> {
+    screen: &'a TouchScreen,
+    slot: usize,
+}
+
+impl Contact<'_> {
+    /// The ABS_MT_SLOT the contact is in.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// Move the finger.
+    pub fn move_to(&self, x: i32, y: i32) -> Result<()> {
+        let (x, y) = self.screen.calibrate(x, y);
+        self.screen
+            .touch()
+            .move_to(&self.screen.device, self.slot, x, y)
+    }
+
+    /// Lift the finger.
+    pub fn lift(self) -> Result<()> {
+        let result = self.screen.touch().up(&self.screen.device, self.slot);
+        std::mem::forget(self);
+        result
+    }
+}
+
+impl Drop for Contact<'_> {
+    fn drop(&mut self) {
+        let _ = self.screen.touch().up(&self.screen.device, self.slot);
+    }
+}
+
+fn lerp(from: i32, to: i32, t: f32) -> i32 {
+    from + ((to - from) as f32 * t).round() as i32
+}
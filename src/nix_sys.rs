@@ -0,0 +1,56 @@
+//! Syscalls through nix's checked wrappers, for the `nix` feature.
+
+use std::{fs::File, io::Result, os::fd::AsRawFd};
+
+use crate::{
+    UI_DEV_CREATE, UI_DEV_DESTROY, UI_SET_ABSBIT, UI_SET_EVBIT, UI_SET_FFBIT, UI_SET_KEYBIT,
+    UI_SET_LEDBIT, UI_SET_MSCBIT, UI_SET_PROPBIT, UI_SET_RELBIT, UI_SET_SNDBIT, UI_SET_SWBIT,
+};
+
+nix::ioctl_none!(ui_dev_create, b'U', 1);
+nix::ioctl_none!(ui_dev_destroy, b'U', 2);
+nix::ioctl_write_int!(ui_set_evbit, b'U', 100);
+nix::ioctl_write_int!(ui_set_keybit, b'U', 101);
+nix::ioctl_write_int!(ui_set_relbit, b'U', 102);
+nix::ioctl_write_int!(ui_set_absbit, b'U', 103);
+nix::ioctl_write_int!(ui_set_mscbit, b'U', 104);
+nix::ioctl_write_int!(ui_set_ledbit, b'U', 105);
+nix::ioctl_write_int!(ui_set_sndbit, b'U', 106);
+nix::ioctl_write_int!(ui_set_ffbit, b'U', 107);
+nix::ioctl_write_int!(ui_set_swbit, b'U', 109);
+nix::ioctl_write_int!(ui_set_propbit, b'U', 110);
+
+pub(crate) fn write(file: &File, buf: &[u8]) -> Result<usize> {
+    Ok(nix::unistd::write(file, buf)?)
+}
+
+/// # Safety
+/// Same as UinputBackend::ioctl.
+pub(crate) unsafe fn ioctl(file: &File, request: u64, arg: u64) -> Result<()> {
+    let fd = file.as_raw_fd();
+    let int = arg as nix::sys::ioctl::ioctl_param_type;
+    // The generated wrappers fix the request number and argument type, so
+    // only the fd has to be valid, and the File guarantees that. The rest
+    // rely on the caller's pointer.
+    let ret = unsafe {
+        match request {
+            UI_DEV_CREATE => ui_dev_create(fd),
+            UI_DEV_DESTROY => ui_dev_destroy(fd),
+            UI_SET_EVBIT => ui_set_evbit(fd, int),
+            UI_SET_KEYBIT => ui_set_keybit(fd, int),
+            UI_SET_RELBIT => ui_set_relbit(fd, int),
+            UI_SET_ABSBIT => ui_set_absbit(fd, int),
+            UI_SET_MSCBIT => ui_set_mscbit(fd, int),
+            UI_SET_LEDBIT => ui_set_ledbit(fd, int),
+            UI_SET_SNDBIT => ui_set_sndbit(fd, int),
+            UI_SET_FFBIT => ui_set_ffbit(fd, int),
+            UI_SET_SWBIT => ui_set_swbit(fd, int),
+            UI_SET_PROPBIT => ui_set_propbit(fd, int),
+            // Requests that take pointers (sysname, absinfo, FF uploads) have
+            // no fixed shape here, so they go through as is.
+            _ => nix::errno::Errno::result(libc::ioctl(fd, request as _, arg)),
+        }
+    };
+    ret?;
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+/// A shared pause switch. Clone it into whatever should stop, like
+/// playback or an FfRecorder, and flip it from a hotkey handler, a signal
+/// thread or anything else. Those stop at the end of the current frame,
+/// not in the middle of one.
+///
+/// Example:
+/// ```rust
+/// use std::{thread, time::Duration};
+/// use uinput_rs::pause::PauseToken;
+///
+/// let token = PauseToken::new();
+/// token.pause();
+///
+/// let waiter = token.clone();
+/// let worker = thread::spawn(move || waiter.wait_while_paused());
+/// thread::sleep(Duration::from_millis(10));
+/// token.resume();
+/// worker.join().unwrap();
+/// assert!(!token.is_paused());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PauseToken {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl PauseToken {
+    /// A token that isn't paused.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn set(&self, paused: bool) {
+        let (lock, resumed) = &*self.inner;
+        *lock.lock().unwrap_or_else(|err| err.into_inner()) = paused;
+        if !paused {
+            resumed.notify_all();
+        }
+    }
+
+    /// Pause everything holding a clone.
+    pub fn pause(&self) {
+        self.set(true);
+    }
+
+    /// Let everything go on.
+    pub fn resume(&self) {
+        self.set(false);
+    }
+
+    /// Pause if running, resume if paused. Returns whether it's paused now.
+    pub fn toggle(&self) -> bool {
+        let (lock, resumed) = &*self.inner;
+        let mut paused = lock.lock().unwrap_or_else(|err| err.into_inner());
+        *paused = !*paused;
+        if !*paused {
+            resumed.notify_all();
+        }
+        *paused
+    }
+
+    /// Whether it's paused.
+    pub fn is_paused(&self) -> bool {
+        *self.inner.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Block until resumed. Returns right away when not paused.
+    pub fn wait_while_paused(&self) {
+        let (lock, resumed) = &*self.inner;
+        let paused = lock.lock().unwrap_or_else(|err| err.into_inner());
+        drop(
+            resumed
+                .wait_while(paused, |paused| *paused)
+                .unwrap_or_else(|err| err.into_inner()),
+        );
+    }
+
+    /// Block until resumed or `timeout` passes. Returns whether it's still
+    /// paused.
+    pub fn wait_while_paused_timeout(&self, timeout: Duration) -> bool {
+        let (lock, resumed) = &*self.inner;
+        let paused = lock.lock().unwrap_or_else(|err| err.into_inner());
+        let (paused, _) = resumed
+            .wait_timeout_while(paused, timeout, |paused| *paused)
+            .unwrap_or_else(|err| err.into_inner());
+        *paused
+    }
+}
@@ -0,0 +1,62 @@
+use crate::key_codes::{BUS_BLUETOOTH, BUS_I2C, BUS_I8042, BUS_USB};
+
+/// A USB interface behind a PCI host controller, like
+/// "usb-0000:00:14.0-1/input0". `port` is the hub path, "1" or "1.2".
+pub fn usb(controller: &str, port: &str, interface: u8) -> String {
+    format!("usb-{controller}-{port}/input{interface}")
+}
+
+/// A PS/2 device behind the i8042 controller. Port 0 is the keyboard,
+/// 1 the mouse: "isa0060/serio0/input0".
+pub fn i8042(port: u8) -> String {
+    format!("isa0060/serio{port}/input0")
+}
+
+/// A Bluetooth HID device, whose phys is the host adapter's address.
+pub fn bluetooth(adapter: [u8; 6]) -> String {
+    adapter
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// An I2C HID device by its ACPI name, like "i2c-ELAN0001:00".
+pub fn i2c(acpi_name: &str) -> String {
+    format!("i2c-{acpi_name}")
+}
+
+/// A plausible phys for the n-th device on a bus, matching the bustype in
+/// input_id: USB devices on the usual xHCI controller, PS/2 keyboard
+/// then mouse, Bluetooth on a made up adapter and I2C touchpads. Other
+/// buses get an empty string, which leaves phys unset like uinput does.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     key_codes::{BUS_I8042, BUS_USB},
+///     key_events::KEY_A_EVENT,
+///     phys,
+/// };
+///
+/// assert_eq!(phys::for_bus(BUS_USB, 0), "usb-0000:00:14.0-1/input0");
+/// assert_eq!(phys::for_bus(BUS_I8042, 0), "isa0060/serio0/input0");
+///
+/// let mut info = UInputUserDevice::with_name("AT Translated Set 2 keyboard");
+/// info.id.bustype = BUS_I8042;
+/// info.phys = phys::for_bus(info.id.bustype, 0);
+///
+/// let mock = MockBackend::new();
+/// let _device = Device::with_backend(mock.clone(), &[KEY_A_EVENT], &info).unwrap();
+/// assert_eq!(mock.phys().unwrap(), "isa0060/serio0/input0");
+/// ```
+pub fn for_bus(bustype: u16, index: u8) -> String {
+    match bustype {
+        BUS_USB => usb("0000:00:14.0", &(index as u16 + 1).to_string(), 0),
+        BUS_I8042 => i8042(index),
+        BUS_BLUETOOTH => bluetooth([0x00, 0x1a, 0x7d, 0xda, 0x71, index]),
+        BUS_I2C => i2c(&format!("VRTL{:04X}:00", index as u16 + 1)),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use libc::input_event;
+
+use crate::{
+    SYN_REPORT,
+    key_codes::{ABS_MT_SLOT, ABS_MT_TOOL_Y, ABS_Y, REL_Y},
+    key_types::{EV_ABS, EV_REL, EV_SYN},
+    remap::Remap,
+};
+
+/// Turns each event into any number of events. Implement it to ship a
+/// reusable layer for Device::add_transformer, Pipeline and the remap
+/// path. Closures taking (event, out) implement it too.
+pub trait Transformer {
+    /// Push what `event` becomes to `out`, nothing to drop it.
+    fn transform(&mut self, event: input_event, out: &mut Vec<input_event>);
+
+    /// Push whatever was held back and is due by now. Only transformers
+    /// that hold events on a timer, like RateLimit, do anything here, and
+    /// only when whoever drives them calls this regularly.
+    fn poll(&mut self, _out: &mut Vec<input_event>) {}
+}
+
+impl<F: FnMut(input_event, &mut Vec<input_event>)> Transformer for F {
+    fn transform(&mut self, event: input_event, out: &mut Vec<input_event>) {
+        self(event, out)
+    }
+}
+
+impl Transformer for Remap {
+    fn transform(&mut self, event: input_event, out: &mut Vec<input_event>) {
+        out.extend(self.translate(event.type_, event.code, event.value));
+    }
+}
+
+/// Transformers run one after the other, each on what the one before let
+/// through. A pipeline is a transformer itself, so pipelines nest.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::MOUSE_EVENTS,
+///     key_codes::{REL_X, REL_Y},
+///     key_types::EV_REL,
+///     pipeline::{InvertY, Pipeline},
+/// };
+///
+/// let mock = MockBackend::new();
+/// let mut device = Device::with_backend(mock.clone(), &MOUSE_EVENTS, &UInputUserDevice::default()).unwrap();
+/// device.add_transformer(
+///     Pipeline::new()
+///         .then(InvertY::relative())
+///         .then(|mut event: uinput_rs::input_event, out: &mut Vec<_>| {
+///             if event.type_ == EV_REL && event.code == REL_X {
+///                 event.value *= 3;
+///             }
+///             out.push(event);
+///         }),
+/// );
+///
+/// device.emit(EV_REL, REL_X, 2).unwrap();
+/// device.emit(EV_REL, REL_Y, 4).unwrap();
+/// let moves: Vec<i32> = mock.events().iter().map(|e| e.value).collect();
+/// assert_eq!(moves, [6, -4]);
+/// ```
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn Transformer + Send>>,
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("stages", &self.stages.len())
+            .finish()
+    }
+}
+
+impl Pipeline {
+    /// A pipeline passing everything through.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a stage at the end.
+    pub fn then(mut self, stage: impl Transformer + Send + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Number of stages.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Whether there are no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Run a batch of events through every stage.
+    pub fn run(&mut self, events: &[input_event]) -> Vec<input_event> {
+        run_stages(&mut self.stages, events.to_vec())
+    }
+}
+
+/// Run `current` through each stage in turn.
+fn run_stages(
+    stages: &mut [Box<dyn Transformer + Send>],
+    mut current: Vec<input_event>,
+) -> Vec<input_event> {
+    let mut next = Vec::with_capacity(current.len());
+    for stage in stages {
+        for event in current.drain(..) {
+            stage.transform(event, &mut next);
+        }
+        std::mem::swap(&mut current, &mut next);
+    }
+    current
+}
+
+impl Transformer for Pipeline {
+    fn transform(&mut self, event: input_event, out: &mut Vec<input_event>) {
+        out.extend(self.run(std::slice::from_ref(&event)));
+    }
+
+    /// Polls every stage, what one releases goes through the ones after it.
+    fn poll(&mut self, out: &mut Vec<input_event>) {
+        for i in 0..self.stages.len() {
+            let mut released = Vec::new();
+            self.stages[i].poll(&mut released);
+            if !released.is_empty() {
+                out.extend(run_stages(&mut self.stages[i + 1..], released));
+            }
+        }
+    }
+}
+
+/// Snaps an ABS axis to its center while it's within `radius` of it, like
+/// the deadzone of a worn stick.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{key_codes::ABS_X, key_types::EV_ABS, pipeline::{Deadzone, Pipeline}};
+/// # use uinput_rs::{input_event, timeval};
+/// # let event = |value| input_event { time: timeval { tv_sec: 0, tv_usec: 0 }, type_: EV_ABS, code: ABS_X, value };
+///
+/// let mut pipeline = Pipeline::new().then(Deadzone::new(ABS_X, 0, 4000));
+/// let out = pipeline.run(&[event(3000), event(-3999), event(12000)]);
+/// assert_eq!(out.iter().map(|e| e.value).collect::<Vec<_>>(), [0, 0, 12000]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadzone {
+    code: u16,
+    center: i32,
+    radius: i32,
+}
+
+impl Deadzone {
+    /// A deadzone of `radius` around `center` on one ABS axis.
+    pub fn new(code: u16, center: i32, radius: i32) -> Self {
+        Self {
+            code,
+            center,
+            radius,
+        }
+    }
+}
+
+impl Transformer for Deadzone {
+    fn transform(&mut self, mut event: input_event, out: &mut Vec<input_event>) {
+        if event.type_ == EV_ABS
+            && event.code == self.code
+            && (event.value - self.center).abs() < self.radius
+        {
+            event.value = self.center;
+        }
+        out.push(event);
+    }
+}
+
+/// Flips the vertical axis: negates REL_Y, or mirrors ABS_Y in its range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvertY {
+    abs_range: Option<(i32, i32)>,
+}
+
+impl InvertY {
+    /// Negate REL_Y.
+    pub fn relative() -> Self {
+        Self { abs_range: None }
+    }
+
+    /// Mirror ABS_Y within min..=max.
+    pub fn absolute(min: i32, max: i32) -> Self {
+        Self {
+            abs_range: Some((min, max)),
+        }
+    }
+}
+
+impl Transformer for InvertY {
+    fn transform(&mut self, mut event: input_event, out: &mut Vec<input_event>) {
+        match self.abs_range {
+            None if event.type_ == EV_REL && event.code == REL_Y => event.value = -event.value,
+            Some((min, max)) if event.type_ == EV_ABS && event.code == ABS_Y => {
+                event.value = max - (event.value - min)
+            }
+            _ => {}
+        }
+        out.push(event);
+    }
+}
+
+/// Caps how often motion frames go out. A frame with nothing but REL and
+/// plain ABS axes that comes too soon after the last one is held back and
+/// merged into the next one that goes out, REL values summed and ABS
+/// values replaced. Any other frame (keys, MSC, ABS_MT_*) goes out right
+/// away and untouched, after whatever motion was held as a frame of its own.
+///
+/// Held motion goes out with the next frame, or from poll once `interval`
+/// has passed. Call poll (Device::poll_transformers on a device) every so
+/// often, or the last movement of a burst waits for whatever comes next.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use uinput_rs::{SYN_REPORT, key_codes::REL_X, key_types::{EV_REL, EV_SYN}, pipeline::{Pipeline, RateLimit, Transformer}};
+/// # use uinput_rs::{input_event, timeval};
+/// # let event = |type_, code, value| input_event { time: timeval { tv_sec: 0, tv_usec: 0 }, type_, code, value };
+///
+/// let mut pipeline = Pipeline::new().then(RateLimit::new(Duration::from_secs(60)));
+/// let mut out = Vec::new();
+/// for _ in 0..3 {
+///     out.extend(pipeline.run(&[event(EV_REL, REL_X, 5), event(EV_SYN, SYN_REPORT, 0)]));
+/// }
+/// // Only the first frame made it out, the others are waiting.
+/// assert_eq!(out.len(), 2);
+///
+/// // Once the interval is over, poll lets the held frame out.
+/// let mut pipeline = Pipeline::new().then(RateLimit::new(Duration::from_millis(50)));
+/// pipeline.run(&[event(EV_REL, REL_X, 5), event(EV_SYN, SYN_REPORT, 0)]);
+/// assert!(pipeline.run(&[event(EV_REL, REL_X, 5), event(EV_SYN, SYN_REPORT, 0)]).is_empty());
+/// std::thread::sleep(Duration::from_millis(60));
+/// let mut out = Vec::new();
+/// pipeline.poll(&mut out);
+/// assert_eq!(out.len(), 2);
+///
+/// // Multitouch and key frames are never merged, even when they come too soon.
+/// use uinput_rs::{
+///     key_codes::{ABS_MT_POSITION_X, ABS_MT_SLOT, KEY_A},
+///     key_types::{EV_ABS, EV_KEY},
+/// };
+/// let mut pipeline = Pipeline::new().then(RateLimit::new(Duration::from_secs(60)));
+/// pipeline.run(&[event(EV_REL, REL_X, 5), event(EV_SYN, SYN_REPORT, 0)]);
+/// let touch = [
+///     event(EV_ABS, ABS_MT_SLOT, 0),
+///     event(EV_ABS, ABS_MT_POSITION_X, 100),
+///     event(EV_ABS, ABS_MT_SLOT, 1),
+///     event(EV_ABS, ABS_MT_POSITION_X, 200),
+///     event(EV_SYN, SYN_REPORT, 0),
+/// ];
+/// let out = pipeline.run(&touch);
+/// assert_eq!(out.iter().map(|e| (e.code, e.value)).collect::<Vec<_>>(), touch.map(|e| (e.code, e.value)));
+///
+/// let tap = [event(EV_KEY, KEY_A, 1), event(EV_KEY, KEY_A, 0), event(EV_SYN, SYN_REPORT, 0)];
+/// let out = pipeline.run(&tap);
+/// assert_eq!(out.iter().map(|e| (e.code, e.value)).collect::<Vec<_>>(), tap.map(|e| (e.code, e.value)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    interval: Duration,
+    last: Option<Instant>,
+    frame: Vec<input_event>,
+    held: Vec<input_event>,
+}
+
+impl RateLimit {
+    /// At most one motion frame per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+            frame: Vec::new(),
+            held: Vec::new(),
+        }
+    }
+
+    /// At most `hz` motion frames per second.
+    pub fn per_second(hz: u32) -> Self {
+        Self::new(Duration::from_secs(1) / hz.max(1))
+    }
+
+    /// Whether the frame is only REL and ABS axes that can be merged,
+    /// not multitouch slots.
+    fn coalescable(&self) -> bool {
+        self.frame.iter().all(|event| match event.type_ {
+            EV_REL => true,
+            EV_ABS => !(ABS_MT_SLOT..=ABS_MT_TOOL_Y).contains(&event.code),
+            _ => false,
+        })
+    }
+
+    fn merge(&mut self) {
+        for event in self.frame.drain(..) {
+            let same = self
+                .held
+                .iter_mut()
+                .find(|held| held.type_ == event.type_ && held.code == event.code);
+            match same {
+                Some(held) if event.type_ == EV_REL => held.value += event.value,
+                Some(held) => held.value = event.value,
+                None => self.held.push(event),
+            }
+        }
+    }
+}
+
+impl Transformer for RateLimit {
+    fn transform(&mut self, event: input_event, out: &mut Vec<input_event>) {
+        if event.type_ != EV_SYN || event.code != SYN_REPORT {
+            self.frame.push(event);
+            return;
+        }
+        let now = Instant::now();
+        if self.coalescable() {
+            let too_soon = self
+                .last
+                .is_some_and(|last| now.duration_since(last) < self.interval);
+            self.merge();
+            if too_soon {
+                return;
+            }
+            out.append(&mut self.held);
+        } else {
+            // Held motion first, as its own frame, so a click lands where
+            // the pointer is.
+            if !self.held.is_empty() {
+                out.append(&mut self.held);
+                out.push(event);
+            }
+            out.append(&mut self.frame);
+        }
+        out.push(event);
+        self.last = Some(now);
+    }
+
+    fn poll(&mut self, out: &mut Vec<input_event>) {
+        let now = Instant::now();
+        let due = self
+            .last
+            .is_none_or(|last| now.duration_since(last) >= self.interval);
+        if self.held.is_empty() || !due {
+            return;
+        }
+        out.append(&mut self.held);
+        out.push(crate::device::new_event(EV_SYN, SYN_REPORT, 0));
+        self.last = Some(now);
+    }
+}
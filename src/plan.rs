@@ -0,0 +1,411 @@
+use std::{
+    io::{self, Result},
+    time::Duration,
+};
+
+use crate::{
+    Device, Error,
+    cancel::{self, CancelToken},
+    gamepad::Gamepad,
+    key_codes::{BTN_LEFT, KEY_ENTER, KEY_TAB},
+    keyboard::Keyboard,
+    mt::TouchScreen,
+    pointer::Mouse,
+    timing::{Clock, Pacing, Scheduler},
+};
+
+#[derive(Debug)]
+enum Target {
+    Keyboard(Keyboard),
+    Mouse(Mouse),
+    TouchScreen(TouchScreen),
+    Gamepad(Gamepad),
+}
+
+impl Target {
+    fn device(&self) -> &Device {
+        match self {
+            Target::Keyboard(keyboard) => keyboard.device(),
+            Target::Mouse(mouse) => mouse.device(),
+            Target::TouchScreen(screen) => screen.device(),
+            Target::Gamepad(pad) => pad.device(),
+        }
+    }
+
+    fn into_device(self) -> Device {
+        match self {
+            Target::Keyboard(keyboard) => keyboard.into_inner(),
+            Target::Mouse(mouse) => mouse.into_inner(),
+            Target::TouchScreen(screen) => screen.into_inner(),
+            Target::Gamepad(pad) => pad.into_inner(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Action {
+    Type(String),
+    Key(u16),
+    Combo(Vec<u16>),
+    Click(u16),
+    ClickAt(i32, i32),
+    MoveBy(f64, f64, Duration),
+    MoveTo(f64, f64, Duration),
+    Scroll(i32),
+    Swipe((i32, i32), (i32, i32), Duration),
+    Wait(Duration),
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    target: Option<usize>,
+    action: Action,
+}
+
+/// A plan of actions across keyboards, mice, touchscreens and gamepads,
+/// built up front and run as many times as you like. Actions go to the
+/// device added last, or the one picked with select.
+///
+/// The cancel token is checked between steps, during waits and in the
+/// middle of typing, moves and gestures. A cancelled run releases whatever
+/// every device was holding and fails with Error::Cancelled.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::{KEYBOARD_EVENTS, MOUSE_EVENTS},
+///     key_codes::{BTN_LEFT, KEY_ENTER, KEY_TAB},
+///     key_types::EV_KEY,
+///     keyboard::Keyboard,
+///     plan::Plan,
+///     pointer::Mouse,
+///     timing::ManualClock,
+/// };
+///
+/// let keys = MockBackend::new();
+/// let pointer = MockBackend::new();
+/// let info = UInputUserDevice::default();
+/// let keyboard = Keyboard::from_device(Device::with_backend(keys.clone(), &KEYBOARD_EVENTS, &info).unwrap());
+/// let mouse = Mouse::from_device(Device::with_backend(pointer.clone(), &MOUSE_EVENTS, &info).unwrap());
+///
+/// let mut login = Plan::new()
+///     .keyboard(keyboard)
+///     .type_str("user")
+///     .tab()
+///     .type_str("pass")
+///     .enter()
+///     .mouse(mouse)
+///     .wait(Duration::from_secs(1))
+///     .click_at(400.0, 300.0)
+///     .step_delay(Duration::from_millis(50));
+///
+/// let clock = ManualClock::instant();
+/// login.run_with_clock(&clock).unwrap();
+/// login.run_with_clock(&clock).unwrap(); // plans replay
+///
+/// let taps = |code| keys.events().iter().filter(|e| e.type_ == EV_KEY && e.code == code && e.value == 1).count();
+/// assert_eq!((taps(KEY_TAB), taps(KEY_ENTER)), (2, 2));
+/// assert_eq!(pointer.events().iter().filter(|e| e.code == BTN_LEFT).count(), 4);
+///
+/// let mut stalled = Plan::new().wait(Duration::from_secs(1)).speed(0.0);
+/// assert!(stalled.run_with_clock(&clock).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Plan {
+    targets: Vec<Target>,
+    current: Option<usize>,
+    steps: Vec<Step>,
+    speed: f32,
+    step_delay: Duration,
+    cancel: CancelToken,
+}
+
+impl Default for Plan {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            current: None,
+            steps: Vec::new(),
+            speed: 1.0,
+            step_delay: Duration::ZERO,
+            cancel: CancelToken::new(),
+        }
+    }
+}
+
+impl Plan {
+    /// An empty plan at normal speed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(mut self, target: Target) -> Self {
+        self.targets.push(target);
+        self.current = Some(self.targets.len() - 1);
+        self
+    }
+
+    fn push(mut self, action: Action) -> Self {
+        let target = self.current;
+        self.steps.push(Step { target, action });
+        self
+    }
+
+    /// Add a keyboard and send the following actions to it.
+    pub fn keyboard(self, keyboard: Keyboard) -> Self {
+        self.add(Target::Keyboard(keyboard))
+    }
+
+    /// Add a mouse and send the following actions to it.
+    pub fn mouse(self, mouse: Mouse) -> Self {
+        self.add(Target::Mouse(mouse))
+    }
+
+    /// Add a touchscreen and send the following actions to it.
+    pub fn touchscreen(self, screen: TouchScreen) -> Self {
+        self.add(Target::TouchScreen(screen))
+    }
+
+    /// Add a gamepad and send the following actions to it.
+    pub fn gamepad(self, pad: Gamepad) -> Self {
+        self.add(Target::Gamepad(pad))
+    }
+
+    /// Send the following actions to a device added earlier, counting from
+    /// 0 in the order they were added.
+    pub fn select(mut self, index: usize) -> Self {
+        self.current = Some(index);
+        self
+    }
+
+    /// Type text on the keyboard.
+    pub fn type_str(self, text: &str) -> Self {
+        self.push(Action::Type(text.to_owned()))
+    }
+
+    /// Tap a key, or a button on a gamepad.
+    pub fn key(self, code: u16) -> Self {
+        self.push(Action::Key(code))
+    }
+
+    /// Tap Tab.
+    pub fn tab(self) -> Self {
+        self.key(KEY_TAB)
+    }
+
+    /// Tap Enter.
+    pub fn enter(self) -> Self {
+        self.key(KEY_ENTER)
+    }
+
+    /// Press keys together, like Ctrl+Shift+T.
+    pub fn combo(self, codes: &[u16]) -> Self {
+        self.push(Action::Combo(codes.to_vec()))
+    }
+
+    /// Click a mouse button.
+    pub fn click(self, button: u16) -> Self {
+        self.push(Action::Click(button))
+    }
+
+    /// Move the mouse there and left click, or tap there on a
+    /// touchscreen. Mouse coordinates work like Mouse::move_to.
+    pub fn click_at(self, x: f64, y: f64) -> Self {
+        self.push(Action::ClickAt(x.round() as i32, y.round() as i32))
+    }
+
+    /// Move the mouse by counts over `duration`, see Mouse::move_by.
+    pub fn move_by(self, dx: f64, dy: f64, duration: Duration) -> Self {
+        self.push(Action::MoveBy(dx, dy, duration))
+    }
+
+    /// Move the mouse to a point over `duration`, see Mouse::move_to.
+    pub fn move_to(self, x: f64, y: f64, duration: Duration) -> Self {
+        self.push(Action::MoveTo(x, y, duration))
+    }
+
+    /// Scroll the mouse wheel, positive is up.
+    pub fn scroll(self, notches: i32) -> Self {
+        self.push(Action::Scroll(notches))
+    }
+
+    /// Swipe one finger across the touchscreen.
+    pub fn swipe(self, from: (i32, i32), to: (i32, i32), duration: Duration) -> Self {
+        self.push(Action::Swipe(from, to, duration))
+    }
+
+    /// Do nothing for a while.
+    pub fn wait(self, duration: Duration) -> Self {
+        self.push(Action::Wait(duration))
+    }
+
+    /// Run every wait and move this many times faster, 2.0 for double
+    /// speed. 1.0 unless changed. Running fails with InvalidInput unless
+    /// it's positive and finite.
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Wait this long after every step, scaled by speed. Nothing unless
+    /// changed.
+    pub fn step_delay(mut self, delay: Duration) -> Self {
+        self.step_delay = delay;
+        self
+    }
+
+    /// Stop when `token` is cancelled.
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// Number of steps.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Whether there's nothing to do.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Run the plan in real time. Blocks until it's done or cancelled.
+    pub fn run(&mut self) -> Result<()> {
+        self.run_with_clock(&Scheduler::start())
+    }
+
+    /// run with another clock, ManualClock in tests.
+    pub fn run_with_clock(&mut self, clock: &impl Clock) -> Result<()> {
+        self.check()?;
+        let result = self.run_steps(clock);
+        if result
+            .as_ref()
+            .is_err_and(|err| Error::from_io(err) == Some(&Error::Cancelled))
+        {
+            for target in &self.targets {
+                target.device().release_all()?;
+            }
+        }
+        result
+    }
+
+    /// Take the devices back, in the order they were added.
+    pub fn into_devices(self) -> Vec<Device> {
+        self.targets.into_iter().map(Target::into_device).collect()
+    }
+
+    /// Refuse plans that send actions to the wrong kind of device before
+    /// anything is sent.
+    fn check(&self) -> Result<()> {
+        if !(self.speed.is_finite() && self.speed > 0.0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("speed {} is not a positive number", self.speed),
+            ));
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            let target = step.target.and_then(|target| self.targets.get(target));
+            let fits = matches!(
+                (&step.action, target),
+                (Action::Wait(_), _)
+                    | (
+                        Action::Type(_) | Action::Combo(_),
+                        Some(Target::Keyboard(_))
+                    )
+                    | (
+                        Action::Key(_),
+                        Some(Target::Keyboard(_) | Target::Gamepad(_))
+                    )
+                    | (
+                        Action::Click(_)
+                            | Action::MoveBy(..)
+                            | Action::MoveTo(..)
+                            | Action::Scroll(_),
+                        Some(Target::Mouse(_)),
+                    )
+                    | (
+                        Action::ClickAt(..),
+                        Some(Target::Mouse(_) | Target::TouchScreen(_))
+                    )
+                    | (Action::Swipe(..), Some(Target::TouchScreen(_)))
+            );
+            if !fits {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "step {index} ({:?}) has no device that can do it",
+                        step.action
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn sleep(&self, clock: &impl Clock, duration: Duration) -> Result<()> {
+        let offset = clock.elapsed() + duration;
+        cancel::wait_until(clock, offset, Pacing::Sleep, Some(&self.cancel))
+    }
+
+    fn run_steps(&mut self, clock: &impl Clock) -> Result<()> {
+        // Moves and gestures stop in the middle too.
+        for target in &mut self.targets {
+            let token = Some(self.cancel.clone());
+            match target {
+                Target::Keyboard(keyboard) => keyboard.set_cancel_token(token),
+                Target::Mouse(mouse) => mouse.set_cancel_token(token),
+                Target::TouchScreen(screen) => screen.set_cancel_token(token),
+                Target::Gamepad(_) => {}
+            }
+        }
+        let speed = self.speed as f64;
+        let scale = |duration: Duration| {
+            Duration::try_from_secs_f64(duration.as_secs_f64() / speed).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{duration:?} at speed {speed} is too long to wait"),
+                )
+            })
+        };
+        let step_delay = scale(self.step_delay)?;
+        for step in self.steps.clone() {
+            self.cancel.check()?;
+            let target = step.target.map(|target| &mut self.targets[target]);
+            match (step.action, target) {
+                (Action::Wait(duration), _) => self.sleep(clock, scale(duration)?)?,
+                (Action::Type(text), Some(Target::Keyboard(keyboard))) => {
+                    keyboard.type_str(&text)?
+                }
+                (Action::Key(code), Some(Target::Keyboard(keyboard))) => keyboard.tap(code)?,
+                (Action::Key(code), Some(Target::Gamepad(pad))) => pad.tap(code)?,
+                (Action::Combo(codes), Some(Target::Keyboard(keyboard))) => {
+                    keyboard.combo(&codes)?
+                }
+                (Action::Click(button), Some(Target::Mouse(mouse))) => mouse.click(button)?,
+                (Action::ClickAt(x, y), Some(Target::Mouse(mouse))) => {
+                    mouse.move_to(x.into(), y.into(), Duration::ZERO)?;
+                    mouse.click(BTN_LEFT)?;
+                }
+                (Action::ClickAt(x, y), Some(Target::TouchScreen(screen))) => screen.tap(x, y)?,
+                (Action::MoveBy(dx, dy, duration), Some(Target::Mouse(mouse))) => {
+                    mouse.move_by_with_clock(dx, dy, scale(duration)?, clock)?
+                }
+                (Action::MoveTo(x, y, duration), Some(Target::Mouse(mouse))) => {
+                    let (from_x, from_y) = mouse.position();
+                    mouse.move_by_with_clock(x - from_x, y - from_y, scale(duration)?, clock)?
+                }
+                (Action::Scroll(notches), Some(Target::Mouse(mouse))) => mouse.scroll(notches)?,
+                (Action::Swipe(from, to, duration), Some(Target::TouchScreen(screen))) => {
+                    screen.swipe_with_clock(from, to, scale(duration)?, clock)?
+                }
+                // check() already refused everything else.
+                _ => unreachable!(),
+            }
+            self.sleep(clock, step_delay)?;
+        }
+        Ok(())
+    }
+}
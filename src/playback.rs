@@ -0,0 +1,226 @@
+use std::{io::Result, time::Duration};
+
+use libc::input_event;
+
+use crate::{
+    Device, SYN_REPORT,
+    cancel::{self, CancelToken},
+    device::new_event,
+    key_types::EV_SYN,
+    pause::PauseToken,
+    timing::{Clock, Pacing, Scheduler},
+};
+
+/// An event and when it should be sent, measured from the start of playback.
+#[derive(Debug, Clone, Copy)]
+pub struct TimedEvent {
+    /// Offset from the start of playback.
+    pub at: Duration,
+    /// The event to write. Include your own SYN_REPORTs.
+    pub event: input_event,
+}
+
+impl TimedEvent {
+    /// Creates a timed event with zeroed time. The kernel fills in the timestamp.
+    pub fn new(at: Duration, event_type: u16, code: u16, value: i32) -> Self {
+        Self {
+            at,
+            event: new_event(event_type, code, value),
+        }
+    }
+}
+
+/// Replays timed events against a device.
+///
+/// Events are expected to be sorted by offset.
+/// Events that share an offset are written together with one write.
+///
+/// Example:
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use uinput_rs::{
+///     Device, SYN_REPORT,
+///     key_events::REL_X_EVENT,
+///     key_types::{EV_REL, EV_SYN},
+///     key_codes::REL_X,
+///     playback::{Playback, TimedEvent},
+/// };
+///
+/// let device = Device::new(&[REL_X_EVENT]).unwrap();
+/// let events: Vec<TimedEvent> = (0..100)
+///     .flat_map(|i| {
+///         let at = Duration::from_millis(i * 10);
+///         [
+///             TimedEvent::new(at, EV_REL, REL_X, 5),
+///             TimedEvent::new(at, EV_SYN, SYN_REPORT, 0),
+///         ]
+///     })
+///     .collect();
+///
+/// Playback::new().play(&device, &events).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Playback {
+    pacing: Option<Pacing>,
+    pause: Option<PauseToken>,
+    cancel: Option<CancelToken>,
+}
+
+impl Playback {
+    /// Playback with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the device's pacing for this playback.
+    pub fn pacing(mut self, pacing: Pacing) -> Self {
+        self.pacing = Some(pacing);
+        self
+    }
+
+    /// Stop between frames while the token is paused. Held keys are let go
+    /// for the pause and pressed again before going on, and the rest of the
+    /// timeline moves back by however long the pause took.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    /// use uinput_rs::{
+    ///     Device, MockBackend, SYN_REPORT, UInputUserDevice,
+    ///     key_codes::KEY_LEFTSHIFT,
+    ///     key_events::KEY_LEFTSHIFT_EVENT,
+    ///     key_types::{EV_KEY, EV_SYN},
+    ///     pause::PauseToken,
+    ///     playback::{Playback, TimedEvent},
+    ///     timing::ManualClock,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut device = Device::with_backend(mock.clone(), &[KEY_LEFTSHIFT_EVENT], &UInputUserDevice::default()).unwrap();
+    /// let token = PauseToken::new();
+    /// // Someone hits the pause hotkey right after shift goes down.
+    /// let hotkey = token.clone();
+    /// device.add_hook(move |event| {
+    ///     if event.type_ == EV_KEY && event.value == 1 {
+    ///         hotkey.pause();
+    ///     }
+    ///     Some(event)
+    /// });
+    ///
+    /// let events = [
+    ///     TimedEvent::new(Duration::ZERO, EV_KEY, KEY_LEFTSHIFT, 1),
+    ///     TimedEvent::new(Duration::ZERO, EV_SYN, SYN_REPORT, 0),
+    ///     TimedEvent::new(Duration::from_millis(10), EV_KEY, KEY_LEFTSHIFT, 0),
+    ///     TimedEvent::new(Duration::from_millis(10), EV_SYN, SYN_REPORT, 0),
+    /// ];
+    /// std::thread::scope(|scope| {
+    ///     scope.spawn(|| {
+    ///         // Shift is let go for the pause, then resume.
+    ///         while mock.events().iter().filter(|e| e.type_ == EV_KEY).count() < 2 {}
+    ///         token.resume();
+    ///     });
+    ///     let playback = Playback::new().pause_token(token.clone());
+    ///     playback.play_with_clock(&device, &events, &ManualClock::instant()).unwrap();
+    /// });
+    ///
+    /// let shift: Vec<i32> = mock.events().iter().filter(|e| e.type_ == EV_KEY).map(|e| e.value).collect();
+    /// assert_eq!(shift, [1, 0, 1, 0]);
+    /// ```
+    pub fn pause_token(mut self, token: PauseToken) -> Self {
+        self.pause = Some(token);
+        self
+    }
+
+    /// Stop between frames once the token is cancelled, letting go of
+    /// every held key and failing with Error::Cancelled.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    /// use uinput_rs::{
+    ///     Device, Error, MockBackend, SYN_REPORT, UInputUserDevice,
+    ///     cancel::CancelToken,
+    ///     key_codes::KEY_LEFTSHIFT,
+    ///     key_events::KEY_LEFTSHIFT_EVENT,
+    ///     key_types::{EV_KEY, EV_SYN},
+    ///     playback::{Playback, TimedEvent},
+    ///     timing::ManualClock,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut device = Device::with_backend(mock.clone(), &[KEY_LEFTSHIFT_EVENT], &UInputUserDevice::default()).unwrap();
+    /// let token = CancelToken::new();
+    /// // Someone hits Stop right after shift goes down.
+    /// let stop = token.clone();
+    /// device.add_hook(move |event| {
+    ///     if event.type_ == EV_KEY && event.value == 1 {
+    ///         stop.cancel();
+    ///     }
+    ///     Some(event)
+    /// });
+    ///
+    /// let events = [
+    ///     TimedEvent::new(Duration::ZERO, EV_KEY, KEY_LEFTSHIFT, 1),
+    ///     TimedEvent::new(Duration::ZERO, EV_SYN, SYN_REPORT, 0),
+    ///     TimedEvent::new(Duration::from_secs(3600), EV_SYN, SYN_REPORT, 0),
+    /// ];
+    /// let playback = Playback::new().cancel_token(token);
+    /// let err = playback.play_with_clock(&device, &events, &ManualClock::instant()).unwrap_err();
+    /// assert_eq!(Error::from_io(&err), Some(&Error::Cancelled));
+    /// assert!(device.pressed_keys().is_empty());
+    /// ```
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Replay the events, blocking until the last one is written.
+    pub fn play(&self, device: &Device, events: &[TimedEvent]) -> Result<()> {
+        self.play_with_clock(device, events, &Scheduler::start())
+    }
+
+    /// Replay the events against a custom clock.
+    /// With timing::ManualClock tests run instantly and deterministically.
+    pub fn play_with_clock(
+        &self,
+        device: &Device,
+        events: &[TimedEvent],
+        clock: &impl Clock,
+    ) -> Result<()> {
+        let pacing = self.pacing.unwrap_or(device.pacing());
+        let mut batch: Vec<input_event> = Vec::new();
+        let mut shift = Duration::ZERO;
+        let mut between_frames = true;
+
+        for group in events.chunk_by(|a, b| a.at == b.at) {
+            if between_frames
+                && let Some(pause) = &self.pause
+                && pause.is_paused()
+            {
+                let started = clock.elapsed();
+                let held = device.state_snapshot();
+                device.release_all()?;
+                pause.wait_while_paused();
+                device.restore(&held)?;
+                shift += clock.elapsed().saturating_sub(started);
+            }
+            // Only stop between frames, half a frame could leave anything
+            // in the kernel's buffer.
+            let cancel = self.cancel.as_ref().filter(|_| between_frames);
+            if let Err(err) = cancel::wait_until(clock, group[0].at + shift, pacing, cancel) {
+                device.release_all()?;
+                return Err(err);
+            }
+
+            batch.clear();
+            batch.extend(group.iter().map(|timed| timed.event));
+            device.emit_batch(&batch)?;
+            between_frames = batch
+                .last()
+                .is_some_and(|event| event.type_ == EV_SYN && event.code == SYN_REPORT);
+        }
+
+        Ok(())
+    }
+}
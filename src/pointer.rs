@@ -0,0 +1,593 @@
+use std::{
+    io::{self, Result},
+    time::Duration,
+};
+
+use crate::{
+    Device, SYN_REPORT,
+    calibration::Calibration,
+    cancel::{self, CancelToken},
+    device::new_event,
+    devices,
+    key_codes::{
+        ABS_X, ABS_Y, BTN_LEFT, REL_HWHEEL, REL_HWHEEL_HI_RES, REL_WHEEL, REL_WHEEL_HI_RES, REL_X,
+        REL_Y,
+    },
+    key_types::{EV_ABS, EV_KEY, EV_REL, EV_SYN},
+    timing::{Clock, Pacing, Scheduler},
+};
+
+/// The CPI Mouse movements are written for. A move of 800 is an inch of
+/// hand movement whatever the mouse is set to.
+pub const REFERENCE_CPI: u32 = 800;
+
+/// One output of a monitor layout, in desktop pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+    /// Connector name, like "HDMI-1" or "eDP-1".
+    pub name: String,
+    /// Left edge on the desktop.
+    pub x: i32,
+    /// Top edge on the desktop.
+    pub y: i32,
+    /// Width after scaling and rotation.
+    pub width: i32,
+    /// Height after scaling and rotation.
+    pub height: i32,
+}
+
+/// Where the outputs sit on the desktop. Compositors stretch an absolute
+/// device that isn't mapped to one output across the box around all of
+/// them, gaps included, so that box is what the ABS range stands for.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::pointer::Layout;
+///
+/// // A laptop panel with a 4K monitor to its right, bottoms aligned.
+/// let layout = Layout::new()
+///     .output("eDP-1", 0, 1080, 1920, 1080)
+///     .output("HDMI-1", 1920, 0, 3840, 2160);
+/// assert_eq!(layout.bounds(), (0, 0, 5760, 2160));
+/// assert_eq!(layout.to_desktop("HDMI-1", 100, 100), Some((2020, 100)));
+/// assert_eq!(layout.to_abs(5760, 2160, (0, 32767), (0, 32767)), (32767, 32767));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Layout {
+    outputs: Vec<Output>,
+}
+
+impl Layout {
+    /// A layout without outputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an output at (x, y) on the desktop.
+    pub fn output(mut self, name: &str, x: i32, y: i32, width: i32, height: i32) -> Self {
+        self.outputs.push(Output {
+            name: name.to_owned(),
+            x,
+            y,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Every output, in the order they were added.
+    pub fn outputs(&self) -> &[Output] {
+        &self.outputs
+    }
+
+    /// An output by name.
+    pub fn get(&self, name: &str) -> Option<&Output> {
+        self.outputs.iter().find(|output| output.name == name)
+    }
+
+    /// The box around every output as (x, y, width, height).
+    pub fn bounds(&self) -> (i32, i32, i32, i32) {
+        let left = self.outputs.iter().map(|o| o.x).min().unwrap_or(0);
+        let top = self.outputs.iter().map(|o| o.y).min().unwrap_or(0);
+        let right = self
+            .outputs
+            .iter()
+            .map(|o| o.x + o.width)
+            .max()
+            .unwrap_or(0);
+        let bottom = self
+            .outputs
+            .iter()
+            .map(|o| o.y + o.height)
+            .max()
+            .unwrap_or(0);
+        (left, top, right - left, bottom - top)
+    }
+
+    /// Desktop coordinates of a point on an output.
+    pub fn to_desktop(&self, name: &str, x: i32, y: i32) -> Option<(i32, i32)> {
+        self.get(name).map(|output| (output.x + x, output.y + y))
+    }
+
+    /// Desktop coordinates in the given ABS ranges.
+    pub fn to_abs(&self, x: i32, y: i32, x_range: (i32, i32), y_range: (i32, i32)) -> (i32, i32) {
+        let (left, top, width, height) = self.bounds();
+        let scale = |value: i32, origin: i32, size: i32, (min, max): (i32, i32)| {
+            if size == 0 {
+                return min;
+            }
+            let fraction = (value - origin) as f64 / size as f64;
+            (min as f64 + fraction * (max - min) as f64)
+                .round()
+                .clamp(min as f64, max as f64) as i32
+        };
+        (
+            scale(x, left, width, x_range),
+            scale(y, top, height, y_range),
+        )
+    }
+}
+
+/// A pointer that jumps to absolute coordinates, like a graphics tablet
+/// in mouse mode or a VM's USB tablet. Coordinates are in the device's
+/// ABS_X and ABS_Y ranges and go through the calibration first.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     calibration::Calibration,
+///     devices::ABSOLUTE_EVENTS,
+///     key_codes::{ABS_X, ABS_Y},
+///     key_types::EV_ABS,
+///     pointer::AbsolutePointer,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let mut info = UInputUserDevice::default();
+/// info.absmax[ABS_X as usize] = 1920;
+/// info.absmax[ABS_Y as usize] = 1080;
+/// let device = Device::with_backend(mock.clone(), &ABSOLUTE_EVENTS, &info).unwrap();
+/// let mut pointer = AbsolutePointer::from_device(device);
+/// pointer.set_calibration(Calibration::rotate_180());
+///
+/// pointer.move_to(0, 1080).unwrap();
+/// let abs: Vec<(u16, i32)> = mock.events().iter().filter(|e| e.type_ == EV_ABS).map(|e| (e.code, e.value)).collect();
+/// assert_eq!(abs, [(ABS_X, 1920), (ABS_Y, 0)]);
+/// ```
+#[derive(Debug)]
+pub struct AbsolutePointer {
+    device: Device,
+    calibration: Calibration,
+    layout: Layout,
+}
+
+impl AbsolutePointer {
+    /// Create an absolute pointer through /dev/uinput with
+    /// devices::absolute.
+    pub fn new(name: &str, max_x: i32, max_y: i32) -> Result<Self> {
+        Ok(Self::from_device(devices::absolute(name, max_x, max_y)?))
+    }
+
+    /// Wrap a device with ABS_X, ABS_Y and the buttons you're going to
+    /// click.
+    pub fn from_device(device: Device) -> Self {
+        Self {
+            device,
+            calibration: Calibration::IDENTITY,
+            layout: Layout::default(),
+        }
+    }
+
+    /// The calibration, the identity unless changed.
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Run every coordinate through `calibration` before it's emitted.
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// The monitor layout, empty unless set.
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    /// Tell the pointer how the desktop is laid out, for the desktop and
+    /// output based moves.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     devices::ABSOLUTE_EVENTS,
+    ///     key_codes::{ABS_X, ABS_Y},
+    ///     key_types::EV_ABS,
+    ///     pointer::{AbsolutePointer, Layout},
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let mut info = UInputUserDevice::default();
+    /// info.absmax[ABS_X as usize] = 32767;
+    /// info.absmax[ABS_Y as usize] = 32767;
+    /// let device = Device::with_backend(mock.clone(), &ABSOLUTE_EVENTS, &info).unwrap();
+    /// let mut pointer = AbsolutePointer::from_device(device);
+    /// pointer.set_layout(Layout::new().output("DP-1", 0, 0, 1920, 1080).output("HDMI-1", 1920, 0, 1920, 1080));
+    ///
+    /// pointer.click_at_output("HDMI-1", 960, 540).unwrap();
+    /// let abs: Vec<(u16, i32)> = mock.events().iter().filter(|e| e.type_ == EV_ABS).map(|e| (e.code, e.value)).collect();
+    /// assert_eq!(abs, [(ABS_X, 24575), (ABS_Y, 16384)]);
+    /// assert!(pointer.click_at_output("VGA-1", 0, 0).is_err());
+    /// ```
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+    }
+
+    /// Move the pointer to desktop coordinates in the layout.
+    pub fn move_to_desktop(&self, x: i32, y: i32) -> Result<()> {
+        let (x, y) = self.layout.to_abs(
+            x,
+            y,
+            self.device.abs_range(ABS_X),
+            self.device.abs_range(ABS_Y),
+        );
+        self.move_to(x, y)
+    }
+
+    /// Move the pointer to a point on one output, relative to its top
+    /// left corner.
+    pub fn move_to_output(&self, output: &str, x: i32, y: i32) -> Result<()> {
+        let (x, y) = self.layout.to_desktop(output, x, y).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no output {output} in the layout"),
+            )
+        })?;
+        self.move_to_desktop(x, y)
+    }
+
+    /// Move the pointer to a point on one output and left click.
+    pub fn click_at_output(&self, output: &str, x: i32, y: i32) -> Result<()> {
+        self.move_to_output(output, x, y)?;
+        self.click(BTN_LEFT)
+    }
+
+    /// Move the pointer, in ABS units.
+    pub fn move_to(&self, x: i32, y: i32) -> Result<()> {
+        let (x, y) = self.calibration.apply_in(
+            x,
+            y,
+            self.device.abs_range(ABS_X),
+            self.device.abs_range(ABS_Y),
+        );
+        self.device.emit_batch(&[
+            new_event(EV_ABS, ABS_X, x),
+            new_event(EV_ABS, ABS_Y, y),
+            new_event(EV_SYN, SYN_REPORT, 0),
+        ])
+    }
+
+    /// Press and release a button where the pointer is.
+    pub fn click(&self, button: u16) -> Result<()> {
+        self.device.emit(EV_KEY, button, 1)?;
+        self.device.sync()?;
+        self.device.emit(EV_KEY, button, 0)?;
+        self.device.sync()
+    }
+
+    /// Move the pointer and left click.
+    pub fn click_at(&self, x: i32, y: i32) -> Result<()> {
+        self.move_to(x, y)?;
+        self.click(BTN_LEFT)
+    }
+
+    /// The wrapped device.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Unwrap the device.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+}
+
+/// A relative mouse with a configurable CPI (counts per inch, what mouse
+/// vendors call DPI). Movements are in counts of a REFERENCE_CPI mouse and
+/// get scaled to the set CPI, so one script can play a 400 and an 8000 CPI
+/// mouse. Fractions of a count are carried over to the next move.
+///
+/// Timed moves are spread over one report per report interval, 8ms like a
+/// 125Hz USB mouse unless changed.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::MOUSE_EVENTS,
+///     key_codes::REL_X,
+///     key_types::EV_REL,
+///     pointer::Mouse,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let device = Device::with_backend(mock.clone(), &MOUSE_EVENTS, &UInputUserDevice::default()).unwrap();
+/// let mut mouse = Mouse::from_device(device);
+/// mouse.set_cpi(400);
+///
+/// // Half a count each time, so every other move sends one.
+/// for _ in 0..4 {
+///     mouse.move_by(1.0, 0.0, Duration::ZERO).unwrap();
+/// }
+/// mouse.set_cpi(8000);
+/// mouse.move_by(1.0, 0.0, Duration::ZERO).unwrap();
+///
+/// let moves: Vec<i32> = mock.events().iter().filter(|e| e.type_ == EV_REL && e.code == REL_X).map(|e| e.value).collect();
+/// assert_eq!(moves, [1, 1, 10]);
+/// ```
+#[derive(Debug)]
+pub struct Mouse {
+    device: Device,
+    cpi: u32,
+    remainder: (f64, f64),
+    /// Where move_to thinks the pointer is, in reference counts.
+    position: (f64, f64),
+    interval: Duration,
+    cancel: Option<CancelToken>,
+}
+
+impl Mouse {
+    /// Create a mouse through /dev/uinput with devices::mouse.
+    pub fn new(name: &str) -> Result<Self> {
+        Ok(Self::from_device(devices::mouse(name)?))
+    }
+
+    /// Wrap a device with REL_X, REL_Y and the buttons you're going to
+    /// click.
+    pub fn from_device(device: Device) -> Self {
+        Self {
+            device,
+            cpi: REFERENCE_CPI,
+            remainder: (0.0, 0.0),
+            position: (0.0, 0.0),
+            interval: Duration::from_millis(8),
+            cancel: None,
+        }
+    }
+
+    /// Stop timed moves between reports once `token` is cancelled, letting
+    /// go of every button. None to always finish them.
+    pub fn set_cancel_token(&mut self, token: Option<CancelToken>) {
+        self.cancel = token;
+    }
+
+    /// The CPI, REFERENCE_CPI unless changed.
+    pub fn cpi(&self) -> u32 {
+        self.cpi
+    }
+
+    /// Change the CPI. Any leftover fraction of a count is dropped.
+    pub fn set_cpi(&mut self, cpi: u32) {
+        self.cpi = cpi.max(1);
+        self.remainder = (0.0, 0.0);
+    }
+
+    /// How often timed moves send a report, 125 unless changed.
+    pub fn set_report_rate(&mut self, hz: u32) {
+        self.interval = Duration::from_secs(1) / hz.max(1);
+    }
+
+    /// Move by counts of a REFERENCE_CPI mouse, spread evenly over
+    /// `duration`. Duration::ZERO sends it all in one report. Blocks until
+    /// the move is done.
+    pub fn move_by(&mut self, dx: f64, dy: f64, duration: Duration) -> Result<()> {
+        self.move_by_with_clock(dx, dy, duration, &Scheduler::start())
+    }
+
+    /// move_by with another clock, ManualClock in tests.
+    /// A cancelled move stops where it got to.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     devices::MOUSE_EVENTS,
+    ///     key_codes::REL_X,
+    ///     key_types::EV_REL,
+    ///     cancel::CancelToken,
+    ///     pointer::Mouse,
+    ///     timing::ManualClock,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let device = Device::with_backend(mock.clone(), &MOUSE_EVENTS, &UInputUserDevice::default()).unwrap();
+    /// let mut mouse = Mouse::from_device(device);
+    ///
+    /// mouse.move_by_with_clock(1000.0, 0.0, Duration::from_secs(1), &ManualClock::instant()).unwrap();
+    /// let moves: Vec<i32> = mock.events().iter().filter(|e| e.type_ == EV_REL && e.code == REL_X).map(|e| e.value).collect();
+    /// assert_eq!(moves.len(), 125);
+    /// assert_eq!(moves.iter().sum::<i32>(), 1000);
+    ///
+    /// let token = CancelToken::new();
+    /// mouse.set_cancel_token(Some(token.clone()));
+    /// token.cancel();
+    /// assert!(mouse.move_by_with_clock(1000.0, 0.0, Duration::from_secs(1), &ManualClock::instant()).is_err());
+    /// assert_eq!(mouse.position(), (1000.0, 0.0));
+    /// ```
+    pub fn move_by_with_clock(
+        &mut self,
+        dx: f64,
+        dy: f64,
+        duration: Duration,
+        clock: &impl Clock,
+    ) -> Result<()> {
+        let steps = (duration.as_nanos() / self.interval.as_nanos().max(1)).max(1) as u32;
+        let start = clock.elapsed();
+        let mut done = (0.0, 0.0);
+        for step in 1..=steps {
+            let t = step as f64 / steps as f64;
+            let offset = start + duration.mul_f64(t);
+            if let Err(err) = cancel::wait_until(clock, offset, Pacing::Sleep, self.cancel.as_ref())
+            {
+                self.device.release_all()?;
+                return Err(err);
+            }
+            self.nudge(dx * t - done.0, dy * t - done.1)?;
+            done = (dx * t, dy * t);
+        }
+        Ok(())
+    }
+
+    /// Move the mouse by a physical distance, see move_by.
+    pub fn move_mm(&mut self, dx: f64, dy: f64, duration: Duration) -> Result<()> {
+        let per_mm = REFERENCE_CPI as f64 / 25.4;
+        self.move_by(dx * per_mm, dy * per_mm, duration)
+    }
+
+    /// Move to a point in reference counts from where the mouse started,
+    /// or from the last set_position. Only lands on a pixel if the
+    /// compositor's pointer acceleration is off (a flat profile) and one
+    /// count is one pixel.
+    pub fn move_to(&mut self, x: f64, y: f64, duration: Duration) -> Result<()> {
+        self.move_by(x - self.position.0, y - self.position.1, duration)
+    }
+
+    /// Where move_to thinks the pointer is.
+    pub fn position(&self) -> (f64, f64) {
+        self.position
+    }
+
+    /// Tell move_to where the pointer is, like (0, 0) after shoving it
+    /// into the top left corner.
+    pub fn set_position(&mut self, x: f64, y: f64) {
+        self.position = (x, y);
+    }
+
+    fn nudge(&mut self, dx: f64, dy: f64) -> Result<()> {
+        self.position = (self.position.0 + dx, self.position.1 + dy);
+        let scale = self.cpi as f64 / REFERENCE_CPI as f64;
+        let x = self.remainder.0 + dx * scale;
+        let y = self.remainder.1 + dy * scale;
+        let (counts_x, counts_y) = (x.trunc(), y.trunc());
+        self.remainder = (x - counts_x, y - counts_y);
+
+        let mut frame = Vec::with_capacity(3);
+        if counts_x != 0.0 {
+            frame.push(new_event(EV_REL, REL_X, counts_x as i32));
+        }
+        if counts_y != 0.0 {
+            frame.push(new_event(EV_REL, REL_Y, counts_y as i32));
+        }
+        if frame.is_empty() {
+            return Ok(());
+        }
+        frame.push(new_event(EV_SYN, SYN_REPORT, 0));
+        self.device.emit_batch(&frame)
+    }
+
+    /// Press a button (BTN_LEFT, BTN_RIGHT, ...) and leave it down.
+    pub fn press(&self, button: u16) -> Result<()> {
+        self.button(button, 1)
+    }
+
+    /// Let go of a button.
+    pub fn release(&self, button: u16) -> Result<()> {
+        self.button(button, 0)
+    }
+
+    /// Press and release a button.
+    pub fn click(&self, button: u16) -> Result<()> {
+        self.press(button)?;
+        self.release(button)
+    }
+
+    /// Hold a button down while moving, then let go. The button is let go
+    /// even if the move fails.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    /// use uinput_rs::{
+    ///     Device, MockBackend, UInputUserDevice,
+    ///     devices::MOUSE_EVENTS,
+    ///     key_codes::{BTN_LEFT, BTN_RIGHT},
+    ///     pointer::Mouse,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let device = Device::with_backend(mock.clone(), &MOUSE_EVENTS, &UInputUserDevice::default()).unwrap();
+    /// let mut mouse = Mouse::from_device(device);
+    ///
+    /// mouse.press(BTN_RIGHT).unwrap();
+    /// assert!(mouse.is_pressed(BTN_RIGHT));
+    /// mouse.release(BTN_RIGHT).unwrap();
+    ///
+    /// mouse.drag(BTN_LEFT, 100.0, 50.0, Duration::ZERO).unwrap();
+    /// assert_eq!(mouse.position(), (100.0, 50.0));
+    /// assert!(mouse.pressed_buttons().is_empty());
+    /// ```
+    pub fn drag(&mut self, button: u16, dx: f64, dy: f64, duration: Duration) -> Result<()> {
+        self.press(button)?;
+        if let Err(e) = self.move_by(dx, dy, duration) {
+            let _ = self.release(button);
+            return Err(e);
+        }
+        self.release(button)
+    }
+
+    /// Whether a button is held down.
+    pub fn is_pressed(&self, button: u16) -> bool {
+        self.pressed_buttons().contains(&button)
+    }
+
+    /// Buttons held down, lowest code first.
+    pub fn pressed_buttons(&self) -> Vec<u16> {
+        self.device.pressed_keys()
+    }
+
+    fn button(&self, button: u16, value: i32) -> Result<()> {
+        self.device.emit_batch(&[
+            new_event(EV_KEY, button, value),
+            new_event(EV_SYN, SYN_REPORT, 0),
+        ])
+    }
+
+    /// Scroll the wheel by whole notches, positive is up. Sends the hi-res
+    /// counterpart too when the device has it.
+    pub fn scroll(&self, notches: i32) -> Result<()> {
+        self.wheel(REL_WHEEL, REL_WHEEL_HI_RES, notches)
+    }
+
+    /// Tilt the wheel by whole notches, positive is right.
+    pub fn scroll_horizontal(&self, notches: i32) -> Result<()> {
+        self.wheel(REL_HWHEEL, REL_HWHEEL_HI_RES, notches)
+    }
+
+    fn wheel(&self, code: u16, hi_res: u16, notches: i32) -> Result<()> {
+        if notches == 0 {
+            return Ok(());
+        }
+        let frame = [
+            new_event(EV_REL, code, notches),
+            new_event(EV_REL, hi_res, notches * 120),
+            new_event(EV_SYN, SYN_REPORT, 0),
+        ];
+        match self.device.capabilities().contains(EV_REL, hi_res) {
+            true => self.device.emit_batch(&frame),
+            false => self.device.emit_batch(&[frame[0], frame[2]]),
+        }
+    }
+
+    /// The wrapped device.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Unwrap the device.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+}
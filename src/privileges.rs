@@ -0,0 +1,100 @@
+//! Dropping root after the devices exist.
+//!
+//! A uinput device lives as long as the file descriptor it was created on,
+//! and permissions are only checked when /dev/uinput is opened. So a
+//! service can open and create everything as root, drop to a normal user
+//! and keep emitting on the same Device values. What it can't do after
+//! dropping is create new devices, so create them all first.
+//!
+//! Example:
+//! ```rust,no_run
+//! use uinput_rs::{devices, key_codes::KEY_A, key_types::EV_KEY, privileges};
+//!
+//! let keyboard = devices::keyboard("Injector").unwrap();
+//! privileges::drop_to_user("nobody").unwrap();
+//!
+//! keyboard.emit(EV_KEY, KEY_A, 1).unwrap();
+//! keyboard.sync().unwrap();
+//! ```
+
+use std::{
+    ffi::CString,
+    io::{self, Result},
+};
+
+fn check(ret: libc::c_int) -> Result<()> {
+    match ret {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// Switch the whole process to `uid` and `gid` for good: supplementary
+/// groups are cleared, then the group and the user are set. Fails if root
+/// could be regained afterwards.
+pub fn drop_to(uid: u32, gid: u32) -> Result<()> {
+    unsafe {
+        check(libc::setgroups(0, std::ptr::null()))?;
+        check(libc::setresgid(gid, gid, gid))?;
+        check(libc::setresuid(uid, uid, uid))?;
+    }
+    if uid != 0 && unsafe { libc::setuid(0) } == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "could still become root after dropping privileges",
+        ));
+    }
+    Ok(())
+}
+
+/// drop_to with the uid and primary group of a user from the passwd
+/// database.
+pub fn drop_to_user(name: &str) -> Result<()> {
+    let (uid, gid) = lookup_user(name)?;
+    drop_to(uid, gid)
+}
+
+/// drop_to the user that ran sudo, from SUDO_UID and SUDO_GID. Returns
+/// false and does nothing when not run through sudo.
+pub fn drop_to_sudo_user() -> Result<bool> {
+    let id = |var| {
+        std::env::var(var)
+            .ok()
+            .and_then(|id| id.parse::<u32>().ok())
+    };
+    match (id("SUDO_UID"), id("SUDO_GID")) {
+        (Some(uid), Some(gid)) => drop_to(uid, gid).map(|()| true),
+        _ => Ok(false),
+    }
+}
+
+/// The uid and primary gid of a user.
+pub fn lookup_user(name: &str) -> Result<(u32, u32)> {
+    let name = CString::new(name)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "user name has a null byte"))?;
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result = std::ptr::null_mut();
+    let mut buf = vec![0 as libc::c_char; 4096];
+    loop {
+        let ret = unsafe {
+            libc::getpwnam_r(
+                name.as_ptr(),
+                &mut passwd,
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut result,
+            )
+        };
+        match ret {
+            0 if result.is_null() => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no user {}", name.to_string_lossy()),
+                ));
+            }
+            0 => return Ok((passwd.pw_uid, passwd.pw_gid)),
+            libc::ERANGE => buf.resize(buf.len() * 2, 0),
+            errno => return Err(io::Error::from_raw_os_error(errno)),
+        }
+    }
+}
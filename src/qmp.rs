@@ -0,0 +1,441 @@
+use std::{
+    io::{self, BufRead, BufReader, Result, Write},
+    os::unix::net::UnixStream,
+    path::Path,
+};
+
+use libc::input_event;
+use serde_json::{Value, json};
+
+use crate::{
+    SYN_REPORT,
+    device::new_event,
+    key_codes::*,
+    key_types::{EV_ABS, EV_KEY, EV_REL, EV_SYN},
+};
+
+/// QEMU QKeyCode names and the evdev keys they stand for.
+pub const QCODES: &[(&str, u16)] = &[
+    ("1", KEY_1),
+    ("2", KEY_2),
+    ("3", KEY_3),
+    ("4", KEY_4),
+    ("5", KEY_5),
+    ("6", KEY_6),
+    ("7", KEY_7),
+    ("8", KEY_8),
+    ("9", KEY_9),
+    ("0", KEY_0),
+    ("a", KEY_A),
+    ("b", KEY_B),
+    ("c", KEY_C),
+    ("d", KEY_D),
+    ("e", KEY_E),
+    ("f", KEY_F),
+    ("g", KEY_G),
+    ("h", KEY_H),
+    ("i", KEY_I),
+    ("j", KEY_J),
+    ("k", KEY_K),
+    ("l", KEY_L),
+    ("m", KEY_M),
+    ("n", KEY_N),
+    ("o", KEY_O),
+    ("p", KEY_P),
+    ("q", KEY_Q),
+    ("r", KEY_R),
+    ("s", KEY_S),
+    ("t", KEY_T),
+    ("u", KEY_U),
+    ("v", KEY_V),
+    ("w", KEY_W),
+    ("x", KEY_X),
+    ("y", KEY_Y),
+    ("z", KEY_Z),
+    ("f1", KEY_F1),
+    ("f2", KEY_F2),
+    ("f3", KEY_F3),
+    ("f4", KEY_F4),
+    ("f5", KEY_F5),
+    ("f6", KEY_F6),
+    ("f7", KEY_F7),
+    ("f8", KEY_F8),
+    ("f9", KEY_F9),
+    ("f10", KEY_F10),
+    ("f11", KEY_F11),
+    ("f12", KEY_F12),
+    ("f13", KEY_F13),
+    ("f14", KEY_F14),
+    ("f15", KEY_F15),
+    ("f16", KEY_F16),
+    ("f17", KEY_F17),
+    ("f18", KEY_F18),
+    ("f19", KEY_F19),
+    ("f20", KEY_F20),
+    ("f21", KEY_F21),
+    ("f22", KEY_F22),
+    ("f23", KEY_F23),
+    ("f24", KEY_F24),
+    ("esc", KEY_ESC),
+    ("minus", KEY_MINUS),
+    ("equal", KEY_EQUAL),
+    ("backspace", KEY_BACKSPACE),
+    ("tab", KEY_TAB),
+    ("bracket_left", KEY_LEFTBRACE),
+    ("bracket_right", KEY_RIGHTBRACE),
+    ("ret", KEY_ENTER),
+    ("ctrl", KEY_LEFTCTRL),
+    ("semicolon", KEY_SEMICOLON),
+    ("apostrophe", KEY_APOSTROPHE),
+    ("grave_accent", KEY_GRAVE),
+    ("shift", KEY_LEFTSHIFT),
+    ("backslash", KEY_BACKSLASH),
+    ("comma", KEY_COMMA),
+    ("dot", KEY_DOT),
+    ("slash", KEY_SLASH),
+    ("shift_r", KEY_RIGHTSHIFT),
+    ("kp_multiply", KEY_KPASTERISK),
+    ("alt", KEY_LEFTALT),
+    ("spc", KEY_SPACE),
+    ("caps_lock", KEY_CAPSLOCK),
+    ("num_lock", KEY_NUMLOCK),
+    ("scroll_lock", KEY_SCROLLLOCK),
+    ("kp_0", KEY_KP0),
+    ("kp_1", KEY_KP1),
+    ("kp_2", KEY_KP2),
+    ("kp_3", KEY_KP3),
+    ("kp_4", KEY_KP4),
+    ("kp_5", KEY_KP5),
+    ("kp_6", KEY_KP6),
+    ("kp_7", KEY_KP7),
+    ("kp_8", KEY_KP8),
+    ("kp_9", KEY_KP9),
+    ("kp_subtract", KEY_KPMINUS),
+    ("kp_add", KEY_KPPLUS),
+    ("kp_decimal", KEY_KPDOT),
+    ("less", KEY_102ND),
+    ("kp_enter", KEY_KPENTER),
+    ("ctrl_r", KEY_RIGHTCTRL),
+    ("kp_divide", KEY_KPSLASH),
+    ("print", KEY_SYSRQ),
+    ("alt_r", KEY_RIGHTALT),
+    ("home", KEY_HOME),
+    ("up", KEY_UP),
+    ("pgup", KEY_PAGEUP),
+    ("left", KEY_LEFT),
+    ("right", KEY_RIGHT),
+    ("end", KEY_END),
+    ("down", KEY_DOWN),
+    ("pgdn", KEY_PAGEDOWN),
+    ("insert", KEY_INSERT),
+    ("delete", KEY_DELETE),
+    ("meta_l", KEY_LEFTMETA),
+    ("meta_r", KEY_RIGHTMETA),
+    ("menu", KEY_MENU),
+    ("compose", KEY_COMPOSE),
+    ("pause", KEY_PAUSE),
+    ("kp_equals", KEY_KPEQUAL),
+    ("kp_comma", KEY_KPCOMMA),
+    ("ro", KEY_RO),
+    ("yen", KEY_YEN),
+    ("henkan", KEY_HENKAN),
+    ("muhenkan", KEY_MUHENKAN),
+    ("katakanahiragana", KEY_KATAKANAHIRAGANA),
+    ("hiragana", KEY_HIRAGANA),
+    ("lang1", KEY_HANGEUL),
+    ("lang2", KEY_HANJA),
+    ("audiomute", KEY_MUTE),
+    ("volumedown", KEY_VOLUMEDOWN),
+    ("volumeup", KEY_VOLUMEUP),
+    ("power", KEY_POWER),
+    ("sleep", KEY_SLEEP),
+    ("wake", KEY_WAKEUP),
+    ("audionext", KEY_NEXTSONG),
+    ("audioprev", KEY_PREVIOUSSONG),
+    ("audiostop", KEY_STOPCD),
+    ("audioplay", KEY_PLAYPAUSE),
+    ("mediaselect", KEY_MEDIA),
+    ("mail", KEY_MAIL),
+    ("calculator", KEY_CALC),
+    ("computer", KEY_COMPUTER),
+    ("ac_home", KEY_HOMEPAGE),
+    ("ac_back", KEY_BACK),
+    ("ac_forward", KEY_FORWARD),
+    ("ac_refresh", KEY_REFRESH),
+    ("ac_bookmarks", KEY_BOOKMARKS),
+    ("stop", KEY_STOP),
+    ("again", KEY_AGAIN),
+    ("props", KEY_PROPS),
+    ("undo", KEY_UNDO),
+    ("front", KEY_FRONT),
+    ("copy", KEY_COPY),
+    ("open", KEY_OPEN),
+    ("paste", KEY_PASTE),
+    ("find", KEY_FIND),
+    ("cut", KEY_CUT),
+    ("help", KEY_HELP),
+];
+
+/// QMP InputButton names.
+const BUTTONS: &[(&str, u16)] = &[
+    ("left", BTN_LEFT),
+    ("right", BTN_RIGHT),
+    ("middle", BTN_MIDDLE),
+    ("side", BTN_SIDE),
+    ("extra", BTN_EXTRA),
+];
+
+/// QMP InputAxis names.
+const AXES: &[(&str, u16)] = &[("x", 0), ("y", 1)];
+
+fn name_of(table: &[(&'static str, u16)], code: u16) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(_, c)| *c == code)
+        .map(|(name, _)| *name)
+}
+
+fn code_of(table: &[(&str, u16)], name: &str) -> Option<u16> {
+    table
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, code)| *code)
+}
+
+/// QEMU qcode of an evdev key.
+pub fn qcode(code: u16) -> Option<&'static str> {
+    name_of(QCODES, code)
+}
+
+/// Evdev key of a QEMU qcode.
+pub fn from_qcode(name: &str) -> Option<u16> {
+    code_of(QCODES, name)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn button(name: &str, down: bool) -> Value {
+    json!({ "type": "btn", "data": { "down": down, "button": name } })
+}
+
+/// Translate a frame into QMP InputEvent objects. SYNs are dropped, QMP
+/// commands are frames of their own.
+///
+/// Wheel motion becomes wheel-up/down (left/right) clicks, one per
+/// detent. QEMU scales abs axes to 0..=32767, so create the device with
+/// that range when driving both.
+pub fn to_qmp_events(frame: &[input_event]) -> Result<Vec<Value>> {
+    let mut out = Vec::new();
+    for event in frame {
+        let unsupported = || {
+            invalid(format!(
+                "type {:#x} code {:#x} has no QMP equivalent",
+                event.type_, event.code
+            ))
+        };
+        match event.type_ {
+            EV_SYN => {}
+            EV_KEY => {
+                let down = event.value != 0;
+                if let Some(name) = name_of(BUTTONS, event.code) {
+                    out.push(button(name, down));
+                } else {
+                    let name = qcode(event.code).ok_or_else(unsupported)?;
+                    out.push(json!({
+                        "type": "key",
+                        "data": { "down": down, "key": { "type": "qcode", "data": name } },
+                    }));
+                }
+            }
+            EV_REL if event.code == REL_WHEEL || event.code == REL_HWHEEL => {
+                let name = match (event.code == REL_WHEEL, event.value > 0) {
+                    (true, true) => "wheel-up",
+                    (true, false) => "wheel-down",
+                    (false, true) => "wheel-right",
+                    (false, false) => "wheel-left",
+                };
+                for _ in 0..event.value.unsigned_abs() {
+                    out.push(button(name, true));
+                    out.push(button(name, false));
+                }
+            }
+            EV_REL | EV_ABS => {
+                let axis = name_of(AXES, event.code).ok_or_else(unsupported)?;
+                let kind = if event.type_ == EV_REL { "rel" } else { "abs" };
+                out.push(json!({
+                    "type": kind,
+                    "data": { "axis": axis, "value": event.value },
+                }));
+            }
+            _ => return Err(unsupported()),
+        }
+    }
+    Ok(out)
+}
+
+/// A complete `input-send-event` command for a frame. device and head
+/// pick the QEMU input device, leave device None for the default one.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{key_codes::KEY_A, key_types::EV_KEY, qmp};
+///
+/// let frame = [libc::input_event { time: libc::timeval { tv_sec: 0, tv_usec: 0 }, type_: EV_KEY, code: KEY_A, value: 1 }];
+/// let command = qmp::command(&frame, None).unwrap();
+///
+/// assert_eq!(command["execute"], "input-send-event");
+/// assert_eq!(command["arguments"]["events"][0]["data"]["key"]["data"], "a");
+/// assert_eq!(qmp::from_command(&command).unwrap()[0].code, KEY_A);
+/// ```
+pub fn command(frame: &[input_event], device: Option<&str>) -> Result<Value> {
+    let mut arguments = json!({ "events": to_qmp_events(frame)? });
+    if let Some(device) = device {
+        arguments["device"] = json!(device);
+    }
+    Ok(json!({ "execute": "input-send-event", "arguments": arguments }))
+}
+
+/// Translate QMP InputEvent objects back into events, without a SYN.
+/// Wheel button releases carry no motion and are skipped.
+pub fn from_qmp_events(events: &[Value]) -> Result<Vec<input_event>> {
+    let mut out = Vec::new();
+    for event in events {
+        let malformed = || invalid(format!("malformed QMP input event {event}"));
+        let data = &event["data"];
+        match event["type"].as_str() {
+            Some("key") => {
+                let key = &data["key"];
+                if key["type"] != "qcode" {
+                    return Err(invalid(format!("only qcode keys are supported, got {key}")));
+                }
+                let name = key["data"].as_str().ok_or_else(malformed)?;
+                let code =
+                    from_qcode(name).ok_or_else(|| invalid(format!("unknown qcode {name}")))?;
+                let down = data["down"].as_bool().ok_or_else(malformed)?;
+                out.push(new_event(EV_KEY, code, down as i32));
+            }
+            Some("btn") => {
+                let name = data["button"].as_str().ok_or_else(malformed)?;
+                let down = data["down"].as_bool().ok_or_else(malformed)?;
+                let wheel = match name {
+                    "wheel-up" => Some((REL_WHEEL, 1)),
+                    "wheel-down" => Some((REL_WHEEL, -1)),
+                    "wheel-right" => Some((REL_HWHEEL, 1)),
+                    "wheel-left" => Some((REL_HWHEEL, -1)),
+                    _ => None,
+                };
+                match wheel {
+                    Some((code, value)) if down => out.push(new_event(EV_REL, code, value)),
+                    Some(_) => {}
+                    None => {
+                        let code = code_of(BUTTONS, name)
+                            .ok_or_else(|| invalid(format!("unknown button {name}")))?;
+                        out.push(new_event(EV_KEY, code, down as i32));
+                    }
+                }
+            }
+            Some(kind @ ("rel" | "abs")) => {
+                let axis = data["axis"].as_str().ok_or_else(malformed)?;
+                let code =
+                    code_of(AXES, axis).ok_or_else(|| invalid(format!("unknown axis {axis}")))?;
+                let value = data["value"].as_i64().ok_or_else(malformed)? as i32;
+                let event_type = if kind == "rel" { EV_REL } else { EV_ABS };
+                out.push(new_event(event_type, code, value));
+            }
+            _ => return Err(malformed()),
+        }
+    }
+    Ok(out)
+}
+
+/// The frame an `input-send-event` command describes, ending in a
+/// SYN_REPORT so it can go straight to Device::emit_batch.
+pub fn from_command(command: &Value) -> Result<Vec<input_event>> {
+    if command["execute"] != "input-send-event" {
+        return Err(invalid(format!(
+            "not an input-send-event command: {command}"
+        )));
+    }
+    let events = command["arguments"]["events"]
+        .as_array()
+        .ok_or_else(|| invalid(format!("command without events: {command}")))?;
+    let mut frame = from_qmp_events(events)?;
+    frame.push(new_event(EV_SYN, SYN_REPORT, 0));
+    Ok(frame)
+}
+
+/// Minimal QMP client for a VM's `-qmp unix:path,server` socket, for
+/// driving a VM with the same frames a local device would get.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::{key_codes::KEY_A, key_types::EV_KEY, qmp::QmpClient};
+///
+/// let event = |value| libc::input_event {
+///     time: libc::timeval { tv_sec: 0, tv_usec: 0 },
+///     type_: EV_KEY,
+///     code: KEY_A,
+///     value,
+/// };
+///
+/// let mut vm = QmpClient::connect("/tmp/vm-qmp.sock").unwrap();
+/// vm.send_frame(&[event(1)]).unwrap();
+/// vm.send_frame(&[event(0)]).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct QmpClient {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl QmpClient {
+    /// Connect and negotiate capabilities.
+    pub fn connect(path: impl AsRef<Path>) -> Result<Self> {
+        let writer = UnixStream::connect(path)?;
+        let mut client = Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+        };
+        let greeting = client.read()?;
+        if greeting.get("QMP").is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("not a QMP greeting: {greeting}"),
+            ));
+        }
+        client.execute(&json!({ "execute": "qmp_capabilities" }))?;
+        Ok(client)
+    }
+
+    /// Run a command and return its "return" value. Asynchronous events
+    /// that arrive in between are skipped.
+    pub fn execute(&mut self, command: &Value) -> Result<Value> {
+        let mut line = command.to_string();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        loop {
+            let mut reply = self.read()?;
+            if let Some(error) = reply.get("error") {
+                return Err(io::Error::other(format!("QMP error: {error}")));
+            }
+            if let Some(value) = reply.get_mut("return") {
+                return Ok(value.take());
+            }
+        }
+    }
+
+    /// Send a frame to the VM's default input device.
+    pub fn send_frame(&mut self, frame: &[input_event]) -> Result<()> {
+        self.execute(&command(frame, None)?).map(drop)
+    }
+
+    fn read(&mut self) -> Result<Value> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        serde_json::from_str(&line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
@@ -0,0 +1,111 @@
+use std::io::{self, Result};
+
+/// Realtime scheduling policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedPolicy {
+    /// SCHED_FIFO: runs until it blocks or something with higher priority shows up.
+    Fifo,
+    /// SCHED_RR: like Fifo but threads with the same priority take turns.
+    RoundRobin,
+}
+
+/// Scheduling settings for a latency sensitive thread.
+/// Everything is opt-in. The default changes nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadTuning {
+    /// Realtime policy and priority (1..=99).
+    pub realtime: Option<(SchedPolicy, i32)>,
+    /// CPUs the thread is allowed to run on. Empty means leave it alone.
+    pub cpus: Vec<usize>,
+}
+
+/// What apply() actually managed to change.
+/// Missing permissions aren't errors, they just show up as false here.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TuningReport {
+    /// The realtime policy was set.
+    pub realtime: bool,
+    /// The CPU affinity was set.
+    pub affinity: bool,
+}
+
+impl ThreadTuning {
+    /// Apply the settings to the calling thread.
+    ///
+    /// Example:
+    /// ```rust
+    /// use uinput_rs::realtime::{SchedPolicy, ThreadTuning};
+    ///
+    /// let tuning = ThreadTuning {
+    ///     realtime: Some((SchedPolicy::Fifo, 10)),
+    ///     cpus: vec![0],
+    /// };
+    /// // Works with or without CAP_SYS_NICE, the report says what happened.
+    /// let report = tuning.apply().unwrap();
+    /// println!("realtime: {}, pinned: {}", report.realtime, report.affinity);
+    /// ```
+    pub fn apply(&self) -> Result<TuningReport> {
+        let mut report = TuningReport::default();
+        if let Some((policy, priority)) = self.realtime {
+            report.realtime = set_realtime(policy, priority)?;
+        }
+        if !self.cpus.is_empty() {
+            report.affinity = set_affinity(&self.cpus)?;
+        }
+        Ok(report)
+    }
+}
+
+fn is_permission_error(err: &io::Error) -> bool {
+    matches!(err.raw_os_error(), Some(libc::EPERM) | Some(libc::EACCES))
+}
+
+/// Set a realtime policy on the calling thread.
+/// Returns Ok(false) instead of an error when the process lacks CAP_SYS_NICE
+/// (or an RLIMIT_RTPRIO allowance), so callers can carry on without it.
+pub fn set_realtime(policy: SchedPolicy, priority: i32) -> Result<bool> {
+    let policy = match policy {
+        SchedPolicy::Fifo => libc::SCHED_FIFO,
+        SchedPolicy::RoundRobin => libc::SCHED_RR,
+    };
+    let param = libc::sched_param {
+        sched_priority: priority,
+    };
+
+    // pid 0 means the calling thread.
+    if unsafe { libc::sched_setscheduler(0, policy, &param) } < 0 {
+        let err = io::Error::last_os_error();
+        return if is_permission_error(&err) {
+            Ok(false)
+        } else {
+            Err(err)
+        };
+    }
+    Ok(true)
+}
+
+/// Pin the calling thread to the given CPUs.
+/// Returns Ok(false) when the kernel refuses for permission reasons
+/// (for example inside a restricted cpuset).
+pub fn set_affinity(cpus: &[usize]) -> Result<bool> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("CPU {cpu} is out of range"),
+            ));
+        }
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+
+    if unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) } < 0 {
+        let err = io::Error::last_os_error();
+        return if is_permission_error(&err) {
+            Ok(false)
+        } else {
+            Err(err)
+        };
+    }
+    Ok(true)
+}
@@ -0,0 +1,245 @@
+use std::{
+    collections::BTreeMap,
+    ffi::{CString, OsString},
+    fs::File,
+    io::{self, Read, Result},
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd},
+        unix::ffi::{OsStrExt, OsStringExt},
+    },
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use libc::input_event;
+
+use crate::{
+    key_types::EV_KEY,
+    pipeline::Transformer,
+    remap::{KeydConfig, Remap, parse_keyd},
+};
+
+/// Watches one file with inotify. The directory is watched rather than
+/// the file, so editors that save by writing a new file and renaming it
+/// over the old one are caught too.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    inotify: File,
+    path: PathBuf,
+    name: OsString,
+}
+
+impl ConfigWatcher {
+    /// Start watching `path`. The file doesn't have to exist yet, its
+    /// directory does.
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?
+            .to_owned();
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let dir = CString::new(dir.as_os_str().as_bytes())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path has a null byte"))?;
+
+        let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let inotify = File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+        let mask = libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO;
+        if unsafe { libc::inotify_add_watch(fd, dir.as_ptr(), mask) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            inotify,
+            path,
+            name,
+        })
+    }
+
+    /// The watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file changed since the last call. Doesn't block.
+    pub fn changed(&mut self) -> Result<bool> {
+        let mut changed = false;
+        let mut buf = [0u8; 4096];
+        loop {
+            let len = match self.inotify.read(&mut buf) {
+                Ok(len) => len,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(changed),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            let header = std::mem::size_of::<libc::inotify_event>();
+            let mut offset = 0;
+            while offset + header <= len {
+                let event: libc::inotify_event =
+                    unsafe { std::ptr::read_unaligned(buf[offset..].as_ptr().cast()) };
+                let name = &buf[offset + header..offset + header + event.len as usize];
+                let name = &name[..name.iter().position(|&b| b == 0).unwrap_or(name.len())];
+                changed |= OsString::from_vec(name.to_vec()) == self.name;
+                offset += header + event.len as usize;
+            }
+        }
+    }
+
+    /// Wait up to `timeout` for the file to change.
+    pub fn wait(&mut self, timeout: Duration) -> Result<bool> {
+        let mut pollfd = libc::pollfd {
+            fd: self.inotify.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout = timeout.as_millis().min(i32::MAX as u128) as i32;
+        loop {
+            let ret = unsafe { libc::poll(&mut pollfd, 1, timeout) };
+            if ret == 0 {
+                return Ok(false);
+            }
+            if ret > 0 {
+                return self.changed();
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+}
+
+/// Swaps the remap of a LiveRemap from anywhere.
+#[derive(Debug, Clone)]
+pub struct RemapHandle {
+    remap: Arc<Mutex<Remap>>,
+}
+
+impl RemapHandle {
+    /// Use `remap` from the next press on.
+    pub fn replace(&self, remap: Remap) {
+        *self.remap.lock().unwrap_or_else(|err| err.into_inner()) = remap;
+    }
+
+    /// Parse a keyd config and use its [main] section. A config that
+    /// doesn't parse leaves the current remap alone.
+    pub fn load_keyd(&self, path: impl AsRef<Path>) -> Result<KeydConfig> {
+        let config = parse_keyd(&std::fs::read_to_string(path)?)?;
+        self.replace(config.remap.clone());
+        Ok(config)
+    }
+}
+
+/// A Remap that can be swapped while keys are down. Every key remembers
+/// what it turned into when it was pressed, and its repeats and release
+/// go out the same way, so changing the mapping under a held key doesn't
+/// leave anything stuck.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use uinput_rs::{
+///     key_codes::{KEY_CAPSLOCK, KEY_ESC, KEY_LEFTCTRL},
+///     key_types::EV_KEY,
+///     pipeline::Pipeline,
+///     reload::{ConfigWatcher, LiveRemap},
+/// };
+/// # use uinput_rs::{input_event, timeval};
+/// # let key = |code, value| input_event { time: timeval { tv_sec: 0, tv_usec: 0 }, type_: EV_KEY, code, value };
+///
+/// let dir = std::env::temp_dir().join(format!("uinput-rs-reload-{}", std::process::id()));
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("default.conf");
+/// std::fs::write(&path, "[main]\ncapslock = esc\n").unwrap();
+///
+/// let live = LiveRemap::new();
+/// let handle = live.handle();
+/// handle.load_keyd(&path).unwrap();
+/// let mut watcher = ConfigWatcher::new(&path).unwrap();
+/// let mut pipeline = Pipeline::new().then(live);
+///
+/// let down = pipeline.run(&[key(KEY_CAPSLOCK, 1)]);
+/// assert_eq!(down[0].code, KEY_ESC);
+///
+/// // The keymap changes while capslock is held.
+/// std::fs::write(&path, "[main]\ncapslock = leftcontrol\n").unwrap();
+/// assert!(watcher.wait(Duration::from_secs(5)).unwrap());
+/// handle.load_keyd(watcher.path()).unwrap();
+///
+/// let up = pipeline.run(&[key(KEY_CAPSLOCK, 0)]);
+/// assert_eq!((up[0].code, up[0].value), (KEY_ESC, 0));
+/// let next = pipeline.run(&[key(KEY_CAPSLOCK, 1)]);
+/// assert_eq!(next[0].code, KEY_LEFTCTRL);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct LiveRemap {
+    remap: Arc<Mutex<Remap>>,
+    /// What each held key turned into when it went down.
+    held: BTreeMap<u16, Vec<input_event>>,
+}
+
+impl LiveRemap {
+    /// Nothing remapped until a handle swaps something in.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with `remap`.
+    pub fn with_remap(remap: Remap) -> Self {
+        Self {
+            remap: Arc::new(Mutex::new(remap)),
+            held: BTreeMap::new(),
+        }
+    }
+
+    /// A handle for swapping the remap, for example from a ConfigWatcher
+    /// thread.
+    pub fn handle(&self) -> RemapHandle {
+        RemapHandle {
+            remap: self.remap.clone(),
+        }
+    }
+}
+
+impl Transformer for LiveRemap {
+    fn transform(&mut self, event: input_event, out: &mut Vec<input_event>) {
+        if event.type_ != EV_KEY {
+            out.push(event);
+            return;
+        }
+        match (event.value, self.held.get(&event.code)) {
+            (0, Some(_)) => {
+                let pressed = self.held.remove(&event.code).unwrap_or_default();
+                out.extend(
+                    pressed
+                        .into_iter()
+                        .rev()
+                        .map(|event| input_event { value: 0, ..event }),
+                );
+            }
+            (2, Some(pressed)) => {
+                // Only the key itself repeats, not the modifiers of a chord.
+                out.extend(
+                    pressed
+                        .last()
+                        .map(|&event| input_event { value: 2, ..event }),
+                );
+            }
+            _ => {
+                let remap = self.remap.lock().unwrap_or_else(|err| err.into_inner());
+                let translated = remap.translate(event.type_, event.code, event.value);
+                if event.value == 1 {
+                    self.held.insert(event.code, translated.clone());
+                }
+                out.extend(translated);
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Result},
+};
+
+use libc::input_event;
+
+use crate::{
+    Device,
+    device::new_event,
+    key_codes::{
+        KEY_ESC, KEY_LEFTALT, KEY_LEFTCTRL, KEY_LEFTMETA, KEY_LEFTSHIFT, KEY_RIGHTALT,
+        KEY_RIGHTCTRL,
+    },
+    key_types::EV_KEY,
+    names,
+};
+
+/// What a remapped key does instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Acts as another key.
+    Key(u16),
+    /// Holds the modifiers while the key is pressed, like C-c.
+    Chord {
+        /// Modifier keys, pressed in order and released in reverse.
+        modifiers: Vec<u16>,
+        /// The key itself.
+        key: u16,
+    },
+    /// Does nothing.
+    Disabled,
+}
+
+/// Key to action table. Keys without an entry pass through unchanged.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{key_codes::{KEY_CAPSLOCK, KEY_ESC}, key_types::EV_KEY, remap::{Action, Remap}};
+///
+/// let mut remap = Remap::new();
+/// remap.insert(KEY_CAPSLOCK, Action::Key(KEY_ESC));
+///
+/// let out = remap.translate(EV_KEY, KEY_CAPSLOCK, 1);
+/// assert_eq!((out[0].code, out[0].value), (KEY_ESC, 1));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Remap {
+    map: BTreeMap<u16, Action>,
+}
+
+impl Remap {
+    /// Nothing remapped.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remap a key. Returns the previous action, if any.
+    pub fn insert(&mut self, from: u16, action: Action) -> Option<Action> {
+        self.map.insert(from, action)
+    }
+
+    /// What a key is remapped to.
+    pub fn get(&self, from: u16) -> Option<&Action> {
+        self.map.get(&from)
+    }
+
+    /// Every remapped key, lowest code first.
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &Action)> {
+        self.map.iter().map(|(&from, action)| (from, action))
+    }
+
+    /// The events an incoming event turns into. SYN_REPORTs are not added.
+    pub fn translate(&self, event_type: u16, code: u16, value: i32) -> Vec<input_event> {
+        let passthrough = || vec![new_event(event_type, code, value)];
+        if event_type != EV_KEY {
+            return passthrough();
+        }
+        match self.map.get(&code) {
+            None => passthrough(),
+            Some(Action::Disabled) => Vec::new(),
+            Some(&Action::Key(to)) => vec![new_event(EV_KEY, to, value)],
+            Some(Action::Chord { modifiers, key }) => {
+                let key = new_event(EV_KEY, *key, value);
+                match value {
+                    1 => modifiers
+                        .iter()
+                        .map(|&m| new_event(EV_KEY, m, 1))
+                        .chain([key])
+                        .collect(),
+                    0 => [key]
+                        .into_iter()
+                        .chain(modifiers.iter().rev().map(|&m| new_event(EV_KEY, m, 0)))
+                        .collect(),
+                    _ => vec![key],
+                }
+            }
+        }
+    }
+
+    /// Translate an event and emit the result on device. Remember to sync.
+    pub fn apply(&self, device: &Device, event_type: u16, code: u16, value: i32) -> Result<()> {
+        device.emit_batch(&self.translate(event_type, code, value))
+    }
+}
+
+/// A keyd config, as far as this crate understands it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeydConfig {
+    /// Lines of the [ids] section.
+    pub ids: Vec<String>,
+    /// The [main] section.
+    pub remap: Remap,
+    /// Lines that were skipped because they use something unsupported
+    /// (layers, overload(), macros, other sections), with line numbers.
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// keyd's key name to a code. keyd uses lowercase evdev names with a few
+/// aliases of its own.
+fn keyd_key(name: &str) -> Option<u16> {
+    let code = match name {
+        "control" | "leftcontrol" => KEY_LEFTCTRL,
+        "rightcontrol" => KEY_RIGHTCTRL,
+        "shift" => KEY_LEFTSHIFT,
+        "alt" => KEY_LEFTALT,
+        "altgr" => KEY_RIGHTALT,
+        "meta" => KEY_LEFTMETA,
+        "escape" => KEY_ESC,
+        _ => return names::key_from_name(&format!("KEY_{}", name.to_ascii_uppercase())),
+    };
+    Some(code)
+}
+
+/// "C-S-t" style keyd key expressions.
+fn keyd_action(value: &str) -> Option<Action> {
+    if value == "noop" {
+        return Some(Action::Disabled);
+    }
+    let mut parts: Vec<&str> = value.split('-').collect();
+    let key = keyd_key(parts.pop()?)?;
+    if parts.is_empty() {
+        return Some(Action::Key(key));
+    }
+    let modifiers = parts
+        .into_iter()
+        .map(|m| match m {
+            "C" => Some(KEY_LEFTCTRL),
+            "S" => Some(KEY_LEFTSHIFT),
+            "A" => Some(KEY_LEFTALT),
+            "M" => Some(KEY_LEFTMETA),
+            "G" => Some(KEY_RIGHTALT),
+            _ => None,
+        })
+        .collect::<Option<Vec<u16>>>()?;
+    Some(Action::Chord { modifiers, key })
+}
+
+/// Parse the [ids] and [main] sections of a keyd config.
+///
+/// Plain remaps (`capslock = esc`), modifier chords (`f1 = C-t`) and `noop`
+/// are supported. Anything else, including other sections, ends up in
+/// KeydConfig::skipped rather than failing. Only a line that isn't
+/// `key = value` at all is an error.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{key_codes::{KEY_CAPSLOCK, KEY_ESC}, remap::{Action, parse_keyd}};
+///
+/// let config = parse_keyd("
+/// [ids]
+/// *
+///
+/// [main]
+/// capslock = overload(control, esc)
+/// esc = capslock
+/// ").unwrap();
+///
+/// assert_eq!(config.ids, ["*"]);
+/// assert_eq!(config.remap.get(KEY_ESC), Some(&Action::Key(KEY_CAPSLOCK)));
+/// assert_eq!(config.skipped.len(), 1);
+/// ```
+pub fn parse_keyd(text: &str) -> Result<KeydConfig> {
+    let mut config = KeydConfig::default();
+    let mut section = String::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_owned();
+            continue;
+        }
+
+        match section.as_str() {
+            "ids" => config.ids.push(line.to_owned()),
+            "main" => {
+                let (from, to) = line.split_once('=').ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {number}: expected key = value"),
+                    )
+                })?;
+                match (keyd_key(from.trim()), keyd_action(to.trim())) {
+                    (Some(from), Some(action)) => {
+                        config.remap.insert(from, action);
+                    }
+                    _ => config.skipped.push((number, line.to_owned())),
+                }
+            }
+            _ => config.skipped.push((number, line.to_owned())),
+        }
+    }
+    Ok(config)
+}
@@ -0,0 +1,70 @@
+use std::{
+    io::{self, Result},
+    thread::sleep,
+    time::Duration,
+};
+
+/// What the emit path does when a write fails with a transient error
+/// (EINTR or EAGAIN, usually momentary kernel buffer pressure).
+///
+/// Other errors, like the device being gone, are always returned as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetryPolicy {
+    /// Return the error right away.
+    #[default]
+    FailFast,
+    /// Try again up to `attempts` more times, sleeping `backoff` before the
+    /// first retry and doubling it for each one after that.
+    Retry {
+        /// Retries after the first failed write.
+        attempts: u32,
+        /// Sleep before the first retry.
+        backoff: Duration,
+    },
+    /// Pretend the write succeeded and count the events as dropped in the
+    /// device stats.
+    DropAndCount,
+}
+
+/// Result of a write that went through a retry policy.
+pub(crate) enum Outcome {
+    Written,
+    Dropped,
+}
+
+fn is_transient(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+    )
+}
+
+impl RetryPolicy {
+    /// Run write under this policy.
+    pub(crate) fn run(&self, mut write: impl FnMut() -> Result<()>) -> Result<Outcome> {
+        let err = match write() {
+            Ok(()) => return Ok(Outcome::Written),
+            Err(err) if is_transient(&err) => err,
+            Err(err) => return Err(err),
+        };
+
+        match *self {
+            RetryPolicy::FailFast => Err(err),
+            RetryPolicy::DropAndCount => Ok(Outcome::Dropped),
+            RetryPolicy::Retry { attempts, backoff } => {
+                let mut last = err;
+                let mut delay = backoff;
+                for _ in 0..attempts {
+                    sleep(delay);
+                    delay = delay.saturating_mul(2);
+                    match write() {
+                        Ok(()) => return Ok(Outcome::Written),
+                        Err(err) if is_transient(&err) => last = err,
+                        Err(err) => return Err(err),
+                    }
+                }
+                Err(last)
+            }
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    cell::UnsafeCell,
+    io::{self, Result},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use libc::input_event;
+
+use crate::{
+    Device, SYN_REPORT, device::new_event, key_types::EV_SYN, pause::PauseToken,
+    realtime::ThreadTuning,
+};
+
+/// What the producer does when the ring is full.
+///
+/// There's no policy that overwrites the oldest events. Only the consumer
+/// moves the ring's tail, which is what keeps it free of locks and CAS
+/// loops; a producer overwriting queued events would have to move it too
+/// and race the consumer reading them. Spin is the backpressure option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Give the event back to the caller and count it as dropped.
+    DropNewest,
+    /// Busy wait until the consumer makes room.
+    /// Never sleeps or allocates, but the producer stalls while the ring is full.
+    Spin,
+}
+
+/// Settings for the injector thread.
+#[derive(Debug, Clone)]
+pub struct RingConfig {
+    /// Number of events the ring can hold. Rounded up to a power of two.
+    pub capacity: usize,
+    /// What to do when the ring is full.
+    pub overflow: OverflowPolicy,
+    /// How long the consumer sleeps when it finds the ring empty.
+    /// The producer wakes it early on every sync.
+    pub idle: Duration,
+    /// Realtime priority and CPU pinning for the injector thread.
+    /// Applied best effort: without CAP_SYS_NICE the thread just runs normally.
+    pub tuning: ThreadTuning,
+    /// Stop writing between frames while paused. Like Playback, held keys
+    /// are let go for the pause and pressed again before going on. Events
+    /// keep queueing meanwhile, so the ring can fill up.
+    pub pause: Option<PauseToken>,
+}
+
+impl Default for RingConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 4096,
+            overflow: OverflowPolicy::DropNewest,
+            idle: Duration::from_millis(1),
+            tuning: ThreadTuning::default(),
+            pause: None,
+        }
+    }
+}
+
+struct Ring {
+    slots: Box<[UnsafeCell<input_event>]>,
+    mask: usize,
+    /// Next slot the producer writes. Only the producer stores to this.
+    head: AtomicUsize,
+    /// Next slot the consumer reads. Only the consumer stores to this.
+    tail: AtomicUsize,
+    closed: AtomicBool,
+    dropped: AtomicU64,
+    write_errors: AtomicU64,
+}
+
+// Slots are only written by the single producer before publishing them with
+// head, and only read by the single consumer before releasing them with tail.
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(2).next_power_of_two();
+        let slots = (0..capacity)
+            .map(|_| UnsafeCell::new(new_event(0, 0, 0)))
+            .collect();
+        Self {
+            slots,
+            mask: capacity - 1,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            closed: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+            write_errors: AtomicU64::new(0),
+        }
+    }
+
+    fn try_push(&self, event: input_event) -> bool {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head.wrapping_sub(tail) > self.mask {
+            return false;
+        }
+        unsafe { *self.slots[head & self.mask].get() = event };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    /// Copies as many events as fit into out and returns how many were copied.
+    fn pop_into(&self, out: &mut [input_event]) -> usize {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        let count = head.wrapping_sub(tail).min(out.len());
+        for (i, slot) in out.iter_mut().take(count).enumerate() {
+            *slot = unsafe { *self.slots[tail.wrapping_add(i) & self.mask].get() };
+        }
+        self.tail.store(tail.wrapping_add(count), Ordering::Release);
+        count
+    }
+}
+
+/// Producer half of the ring injector.
+/// Pushing never allocates and, with OverflowPolicy::DropNewest, never waits.
+/// It takes &mut self, so there's only ever one thread pushing; move the
+/// producer to the thread that feeds it rather than sharing it.
+///
+/// Dropping the producer lets the injector thread write what is left and stop.
+pub struct RingProducer {
+    ring: Arc<Ring>,
+    overflow: OverflowPolicy,
+    consumer: thread::Thread,
+}
+
+impl RingProducer {
+    /// Queue a single event.
+    /// Returns the event back if the ring is full and the policy is DropNewest.
+    pub fn push(&mut self, event: input_event) -> std::result::Result<(), input_event> {
+        loop {
+            if self.ring.try_push(event) {
+                return Ok(());
+            }
+            match self.overflow {
+                OverflowPolicy::DropNewest => {
+                    self.ring.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Err(event);
+                }
+                OverflowPolicy::Spin => std::hint::spin_loop(),
+            }
+        }
+    }
+
+    /// Same as push but builds the event for you.
+    pub fn emit(
+        &mut self,
+        event_type: u16,
+        code: u16,
+        value: i32,
+    ) -> std::result::Result<(), input_event> {
+        self.push(new_event(event_type, code, value))
+    }
+
+    /// Same as emit but using the key code format.
+    pub fn emit_key_code(
+        &mut self,
+        key_code: (u64, u64),
+        value: i32,
+    ) -> std::result::Result<(), input_event> {
+        self.emit(key_code.0 as u16, key_code.1 as u16, value)
+    }
+
+    /// Queue a SYN_REPORT and wake the injector thread.
+    pub fn sync(&mut self) -> std::result::Result<(), input_event> {
+        let result = self.emit(EV_SYN, SYN_REPORT, 0);
+        self.consumer.unpark();
+        result
+    }
+
+    /// Number of events dropped because the ring was full.
+    pub fn dropped(&self) -> u64 {
+        self.ring.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for RingProducer {
+    fn drop(&mut self) {
+        self.ring.closed.store(true, Ordering::Release);
+        self.consumer.unpark();
+    }
+}
+
+/// Handle to the thread that drains the ring into the device.
+pub struct RingInjector {
+    ring: Arc<Ring>,
+    thread: JoinHandle<Device>,
+}
+
+impl RingInjector {
+    /// Move the device to a dedicated writer thread and return the producer
+    /// half together with a handle to that thread.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use uinput_rs::{Device, RingConfig, RingInjector, key_events::REL_X_EVENT};
+    ///
+    /// let device = Device::new(&[REL_X_EVENT]).unwrap();
+    /// let (mut producer, injector) = RingInjector::spawn(device, RingConfig::default()).unwrap();
+    ///
+    /// for _ in 0..1000 {
+    ///     let _ = producer.emit_key_code(REL_X_EVENT, 1);
+    ///     let _ = producer.sync();
+    /// }
+    ///
+    /// drop(producer);
+    /// let device = injector.join().unwrap();
+    /// ```
+    ///
+    /// Pausing the injector:
+    /// ```rust
+    /// use std::{thread, time::Duration};
+    /// use uinput_rs::{
+    ///     Device, MockBackend, RingConfig, RingInjector, UInputUserDevice,
+    ///     key_events::REL_X_EVENT, pause::PauseToken,
+    /// };
+    ///
+    /// let mock = MockBackend::new();
+    /// let device = Device::with_backend(mock.clone(), &[REL_X_EVENT], &UInputUserDevice::default()).unwrap();
+    /// let token = PauseToken::new();
+    /// let config = RingConfig { pause: Some(token.clone()), ..RingConfig::default() };
+    /// let (mut producer, injector) = RingInjector::spawn(device, config).unwrap();
+    ///
+    /// token.pause();
+    /// producer.emit_key_code(REL_X_EVENT, 1).unwrap();
+    /// producer.sync().unwrap();
+    /// thread::sleep(Duration::from_millis(20));
+    /// assert!(mock.events().is_empty());
+    ///
+    /// token.resume();
+    /// drop(producer);
+    /// injector.join().unwrap();
+    /// assert_eq!(mock.events().len(), 2);
+    /// ```
+    pub fn spawn(device: Device, config: RingConfig) -> Result<(RingProducer, RingInjector)> {
+        let ring = Arc::new(Ring::new(config.capacity));
+        let consumer_ring = ring.clone();
+        let idle = config.idle;
+        let tuning = config.tuning;
+        let pause = config.pause;
+
+        let thread = thread::Builder::new()
+            .name("uinput-ring".into())
+            .spawn(move || {
+                let _ = tuning.apply();
+                consume(device, &consumer_ring, idle, pause)
+            })?;
+
+        let producer = RingProducer {
+            ring: ring.clone(),
+            overflow: config.overflow,
+            consumer: thread.thread().clone(),
+        };
+
+        Ok((producer, RingInjector { ring, thread }))
+    }
+
+    /// Number of batched writes that failed on the injector thread.
+    pub fn write_errors(&self) -> u64 {
+        self.ring.write_errors.load(Ordering::Relaxed)
+    }
+
+    /// Wait for the thread to drain the ring and give the device back.
+    /// Drop the producer first, otherwise this waits forever.
+    pub fn join(self) -> Result<Device> {
+        self.thread
+            .join()
+            .map_err(|_| io::Error::other("ring injector thread panicked"))
+    }
+}
+
+fn consume(device: Device, ring: &Ring, idle: Duration, pause: Option<PauseToken>) -> Device {
+    let mut batch = vec![new_event(0, 0, 0); ring.slots.len()];
+    let mut between_frames = true;
+    loop {
+        // Read closed before draining so nothing pushed before closing is lost.
+        let closed = ring.closed.load(Ordering::Acquire);
+        let count = ring.pop_into(&mut batch);
+        if count > 0 {
+            if between_frames
+                && let Some(pause) = &pause
+                && pause.is_paused()
+            {
+                let held = device.state_snapshot();
+                let released = device.release_all();
+                pause.wait_while_paused();
+                if released.and_then(|_| device.restore(&held)).is_err() {
+                    ring.write_errors.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            if device.emit_batch(&batch[..count]).is_err() {
+                ring.write_errors.fetch_add(1, Ordering::Relaxed);
+            }
+            // Batches can end mid frame, only pause at a frame's end.
+            between_frames =
+                batch[count - 1].type_ == EV_SYN && batch[count - 1].code == SYN_REPORT;
+        } else if closed {
+            return device;
+        } else {
+            thread::park_timeout(idle);
+        }
+    }
+}
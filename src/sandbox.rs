@@ -0,0 +1,174 @@
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::backend::{UINPUT_PATHS, selinux_denied};
+
+/// What the process runs inside.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sandbox {
+    /// Nothing we can tell.
+    None,
+    /// A Flatpak app, with its id if known.
+    Flatpak(Option<String>),
+    /// A Snap, with its name if known.
+    Snap(Option<String>),
+    /// Docker, Podman or similar.
+    Container,
+}
+
+/// Whether /dev/uinput can be opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Opened read/write.
+    Available,
+    /// No uinput node, the module isn't loaded or the sandbox hides /dev.
+    Missing,
+    /// The node is there but the permissions say no.
+    PermissionDenied,
+    /// The permissions say yes but SELinux says no.
+    SelinuxDenied,
+}
+
+/// How the app should inject input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// Create devices through /dev/uinput.
+    Uinput,
+    /// Ask the compositor through the RemoteDesktop portal or a libei
+    /// socket. This crate doesn't speak libei, pair it with a libei binding.
+    Portal,
+    /// Nothing will work without the user changing something.
+    Unavailable,
+}
+
+/// What the process can use to inject input, from Probe::run.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::sandbox::{Access, Probe, Route, Sandbox};
+///
+/// let probe = Probe {
+///     sandbox: Sandbox::Flatpak(Some("org.example.Macros".into())),
+///     uinput: Access::Missing,
+///     ei_socket: None,
+///     session_bus: true,
+/// };
+/// assert_eq!(probe.route(), Route::Portal);
+/// assert!(probe.guidance().contains("RemoteDesktop"));
+///
+/// // Outside a sandbox a session bus doesn't help, permissions do.
+/// let desktop = Probe { sandbox: Sandbox::None, uinput: Access::PermissionDenied, ..probe };
+/// assert_eq!(desktop.route(), Route::Unavailable);
+/// assert!(desktop.guidance().contains("udev"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Probe {
+    /// The sandbox, if any.
+    pub sandbox: Sandbox,
+    /// Whether /dev/uinput opens.
+    pub uinput: Access,
+    /// LIBEI_SOCKET, a libei socket the compositor handed over directly.
+    pub ei_socket: Option<PathBuf>,
+    /// Whether a session bus is around to reach the portal on. The portal
+    /// itself isn't called, so this only says it's worth trying.
+    pub session_bus: bool,
+}
+
+impl Probe {
+    /// Look at the environment and try opening /dev/uinput. Opening it
+    /// doesn't create anything.
+    pub fn run() -> Self {
+        Self {
+            sandbox: detect_sandbox(),
+            uinput: uinput_access(),
+            ei_socket: std::env::var_os("LIBEI_SOCKET").map(PathBuf::from),
+            session_bus: std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some()
+                || std::env::var_os("XDG_RUNTIME_DIR")
+                    .is_some_and(|dir| Path::new(&dir).join("bus").exists()),
+        }
+    }
+
+    /// Whether a libei connection is likely to be had.
+    pub fn portal_available(&self) -> bool {
+        self.ei_socket.is_some() || self.session_bus
+    }
+
+    /// The route to take. Flatpak and Snap apps prefer the portal, since
+    /// that's what the sandbox expects them to use. Everything else uses
+    /// uinput, or gets told how to fix its permissions: this crate has no
+    /// portal backend to fall back on.
+    pub fn route(&self) -> Route {
+        let sandboxed = matches!(self.sandbox, Sandbox::Flatpak(_) | Sandbox::Snap(_));
+        match (sandboxed && self.portal_available(), self.uinput) {
+            (true, _) => Route::Portal,
+            (false, Access::Available) => Route::Uinput,
+            (false, _) => Route::Unavailable,
+        }
+    }
+
+    /// A sentence or two for the user on what's going on and what to do.
+    pub fn guidance(&self) -> String {
+        match (self.route(), self.uinput) {
+            (Route::Uinput, _) => "/dev/uinput is available".to_owned(),
+            (Route::Portal, _) => format!(
+                "{}: use the RemoteDesktop portal (ConnectToEIS) or LIBEI_SOCKET with libei",
+                self.sandbox
+            ),
+            (Route::Unavailable, Access::Missing) => {
+                "/dev/uinput is missing: load the uinput module (modprobe uinput) or expose the device to the sandbox".to_owned()
+            }
+            (Route::Unavailable, Access::PermissionDenied) => {
+                "/dev/uinput is not writable: add a udev rule giving your user or the input group access".to_owned()
+            }
+            (Route::Unavailable, Access::SelinuxDenied) => {
+                "SELinux denies /dev/uinput: allow the domain access to uinput_device".to_owned()
+            }
+            (Route::Unavailable, Access::Available) => unreachable!("uinput would be the route"),
+        }
+    }
+}
+
+impl fmt::Display for Sandbox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sandbox::None => f.write_str("not sandboxed"),
+            Sandbox::Flatpak(Some(id)) => write!(f, "Flatpak app {id}"),
+            Sandbox::Flatpak(None) => f.write_str("Flatpak"),
+            Sandbox::Snap(Some(name)) => write!(f, "Snap {name}"),
+            Sandbox::Snap(None) => f.write_str("Snap"),
+            Sandbox::Container => f.write_str("container"),
+        }
+    }
+}
+
+/// The sandbox this process is in.
+pub fn detect_sandbox() -> Sandbox {
+    let var = |name| std::env::var(name).ok();
+    if Path::new("/.flatpak-info").exists() || var("FLATPAK_ID").is_some() {
+        Sandbox::Flatpak(var("FLATPAK_ID"))
+    } else if var("SNAP").is_some() {
+        Sandbox::Snap(var("SNAP_NAME"))
+    } else if Path::new("/.dockerenv").exists() || Path::new("/run/.containerenv").exists() {
+        Sandbox::Container
+    } else {
+        Sandbox::None
+    }
+}
+
+/// Whether /dev/uinput (or /dev/input/uinput) opens read/write.
+pub fn uinput_access() -> Access {
+    let mut access = Access::Missing;
+    for path in UINPUT_PATHS {
+        match OpenOptions::new().read(true).write(true).open(path) {
+            Ok(_) => return Access::Available,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) if selinux_denied(path, &err) => access = Access::SelinuxDenied,
+            Err(_) => access = Access::PermissionDenied,
+        }
+    }
+    access
+}
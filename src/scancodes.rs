@@ -0,0 +1,181 @@
+use crate::key_codes::*;
+
+/// Keys and their AT set 2 make codes, the bytes a PS/2 keyboard sends
+/// when the key goes down.
+pub const SET2: &[(u16, &[u8])] = &[
+    (KEY_ESC, &[0x76]),
+    (KEY_F1, &[0x05]),
+    (KEY_F2, &[0x06]),
+    (KEY_F3, &[0x04]),
+    (KEY_F4, &[0x0c]),
+    (KEY_F5, &[0x03]),
+    (KEY_F6, &[0x0b]),
+    (KEY_F7, &[0x83]),
+    (KEY_F8, &[0x0a]),
+    (KEY_F9, &[0x01]),
+    (KEY_F10, &[0x09]),
+    (KEY_F11, &[0x78]),
+    (KEY_F12, &[0x07]),
+    (KEY_GRAVE, &[0x0e]),
+    (KEY_1, &[0x16]),
+    (KEY_2, &[0x1e]),
+    (KEY_3, &[0x26]),
+    (KEY_4, &[0x25]),
+    (KEY_5, &[0x2e]),
+    (KEY_6, &[0x36]),
+    (KEY_7, &[0x3d]),
+    (KEY_8, &[0x3e]),
+    (KEY_9, &[0x46]),
+    (KEY_0, &[0x45]),
+    (KEY_MINUS, &[0x4e]),
+    (KEY_EQUAL, &[0x55]),
+    (KEY_BACKSPACE, &[0x66]),
+    (KEY_TAB, &[0x0d]),
+    (KEY_Q, &[0x15]),
+    (KEY_W, &[0x1d]),
+    (KEY_E, &[0x24]),
+    (KEY_R, &[0x2d]),
+    (KEY_T, &[0x2c]),
+    (KEY_Y, &[0x35]),
+    (KEY_U, &[0x3c]),
+    (KEY_I, &[0x43]),
+    (KEY_O, &[0x44]),
+    (KEY_P, &[0x4d]),
+    (KEY_LEFTBRACE, &[0x54]),
+    (KEY_RIGHTBRACE, &[0x5b]),
+    (KEY_BACKSLASH, &[0x5d]),
+    (KEY_CAPSLOCK, &[0x58]),
+    (KEY_A, &[0x1c]),
+    (KEY_S, &[0x1b]),
+    (KEY_D, &[0x23]),
+    (KEY_F, &[0x2b]),
+    (KEY_G, &[0x34]),
+    (KEY_H, &[0x33]),
+    (KEY_J, &[0x3b]),
+    (KEY_K, &[0x42]),
+    (KEY_L, &[0x4b]),
+    (KEY_SEMICOLON, &[0x4c]),
+    (KEY_APOSTROPHE, &[0x52]),
+    (KEY_ENTER, &[0x5a]),
+    (KEY_LEFTSHIFT, &[0x12]),
+    (KEY_102ND, &[0x61]),
+    (KEY_Z, &[0x1a]),
+    (KEY_X, &[0x22]),
+    (KEY_C, &[0x21]),
+    (KEY_V, &[0x2a]),
+    (KEY_B, &[0x32]),
+    (KEY_N, &[0x31]),
+    (KEY_M, &[0x3a]),
+    (KEY_COMMA, &[0x41]),
+    (KEY_DOT, &[0x49]),
+    (KEY_SLASH, &[0x4a]),
+    (KEY_RIGHTSHIFT, &[0x59]),
+    (KEY_LEFTCTRL, &[0x14]),
+    (KEY_LEFTMETA, &[0xe0, 0x1f]),
+    (KEY_LEFTALT, &[0x11]),
+    (KEY_SPACE, &[0x29]),
+    (KEY_RIGHTALT, &[0xe0, 0x11]),
+    (KEY_RIGHTMETA, &[0xe0, 0x27]),
+    (KEY_COMPOSE, &[0xe0, 0x2f]),
+    (KEY_RIGHTCTRL, &[0xe0, 0x14]),
+    (KEY_SYSRQ, &[0xe0, 0x12, 0xe0, 0x7c]),
+    (KEY_SCROLLLOCK, &[0x7e]),
+    (KEY_PAUSE, &[0xe1, 0x14, 0x77, 0xe1, 0xf0, 0x14, 0xf0, 0x77]),
+    (KEY_INSERT, &[0xe0, 0x70]),
+    (KEY_HOME, &[0xe0, 0x6c]),
+    (KEY_PAGEUP, &[0xe0, 0x7d]),
+    (KEY_DELETE, &[0xe0, 0x71]),
+    (KEY_END, &[0xe0, 0x69]),
+    (KEY_PAGEDOWN, &[0xe0, 0x7a]),
+    (KEY_UP, &[0xe0, 0x75]),
+    (KEY_LEFT, &[0xe0, 0x6b]),
+    (KEY_DOWN, &[0xe0, 0x72]),
+    (KEY_RIGHT, &[0xe0, 0x74]),
+    (KEY_NUMLOCK, &[0x77]),
+    (KEY_KPSLASH, &[0xe0, 0x4a]),
+    (KEY_KPASTERISK, &[0x7c]),
+    (KEY_KPMINUS, &[0x7b]),
+    (KEY_KP7, &[0x6c]),
+    (KEY_KP8, &[0x75]),
+    (KEY_KP9, &[0x7d]),
+    (KEY_KPPLUS, &[0x79]),
+    (KEY_KP4, &[0x6b]),
+    (KEY_KP5, &[0x73]),
+    (KEY_KP6, &[0x74]),
+    (KEY_KP1, &[0x69]),
+    (KEY_KP2, &[0x72]),
+    (KEY_KP3, &[0x7a]),
+    (KEY_KP0, &[0x70]),
+    (KEY_KPDOT, &[0x71]),
+    (KEY_KPENTER, &[0xe0, 0x5a]),
+    (KEY_RO, &[0x51]),
+    (KEY_KATAKANAHIRAGANA, &[0x13]),
+    (KEY_YEN, &[0x6a]),
+    (KEY_HENKAN, &[0x64]),
+    (KEY_MUHENKAN, &[0x67]),
+    (KEY_HANGEUL, &[0xf2]),
+    (KEY_HANJA, &[0xf1]),
+    (KEY_MUTE, &[0xe0, 0x23]),
+    (KEY_VOLUMEDOWN, &[0xe0, 0x21]),
+    (KEY_VOLUMEUP, &[0xe0, 0x32]),
+    (KEY_PLAYPAUSE, &[0xe0, 0x34]),
+    (KEY_STOPCD, &[0xe0, 0x3b]),
+    (KEY_PREVIOUSSONG, &[0xe0, 0x15]),
+    (KEY_NEXTSONG, &[0xe0, 0x4d]),
+    (KEY_MEDIA, &[0xe0, 0x50]),
+    (KEY_CALC, &[0xe0, 0x2b]),
+    (KEY_MAIL, &[0xe0, 0x48]),
+    (KEY_COMPUTER, &[0xe0, 0x40]),
+    (KEY_HOMEPAGE, &[0xe0, 0x3a]),
+    (KEY_SEARCH, &[0xe0, 0x10]),
+    (KEY_BACK, &[0xe0, 0x38]),
+    (KEY_FORWARD, &[0xe0, 0x30]),
+    (KEY_REFRESH, &[0xe0, 0x20]),
+    (KEY_BOOKMARKS, &[0xe0, 0x18]),
+    (KEY_POWER, &[0xe0, 0x37]),
+    (KEY_SLEEP, &[0xe0, 0x3f]),
+    (KEY_WAKEUP, &[0xe0, 0x5e]),
+];
+
+/// Set 2 make code of a key.
+pub fn set2_make(code: u16) -> Option<&'static [u8]> {
+    SET2.iter()
+        .find(|(key, _)| *key == code)
+        .map(|(_, bytes)| *bytes)
+}
+
+/// Set 2 break code of a key, sent when it goes up: F0 before the last
+/// byte, so 1C becomes F0 1C and E0 75 becomes E0 F0 75. Print Screen
+/// breaks both of its halves in reverse, and Pause sends nothing at all.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{key_codes::{KEY_A, KEY_PAUSE, KEY_SYSRQ, KEY_UP}, scancodes::set2_break};
+///
+/// assert_eq!(set2_break(KEY_A).unwrap(), [0xf0, 0x1c]);
+/// assert_eq!(set2_break(KEY_UP).unwrap(), [0xe0, 0xf0, 0x75]);
+/// assert_eq!(set2_break(KEY_SYSRQ).unwrap(), [0xe0, 0xf0, 0x7c, 0xe0, 0xf0, 0x12]);
+/// assert!(set2_break(KEY_PAUSE).unwrap().is_empty());
+/// ```
+pub fn set2_break(code: u16) -> Option<Vec<u8>> {
+    let make = set2_make(code)?;
+    Some(match code {
+        KEY_PAUSE => Vec::new(),
+        KEY_SYSRQ => vec![0xe0, 0xf0, 0x7c, 0xe0, 0xf0, 0x12],
+        _ => {
+            let (last, prefix) = make.split_last()?;
+            let mut bytes = prefix.to_vec();
+            bytes.extend([0xf0, *last]);
+            bytes
+        }
+    })
+}
+
+/// Bytes for an EV_KEY value: make for presses and autorepeat (keyboards
+/// repeat the make code), break for releases.
+pub fn set2_bytes(code: u16, value: i32) -> Option<Vec<u8>> {
+    match value {
+        0 => set2_break(code),
+        _ => set2_make(code).map(<[u8]>::to_vec),
+    }
+}
@@ -0,0 +1,96 @@
+use std::{io::Result, thread::sleep, time::Duration};
+
+use crate::{
+    Device, SYN_REPORT,
+    key_codes::{KEY_POWER, KEY_SLEEP, SW_LID},
+    key_types::{EV_KEY, EV_SW, EV_SYN},
+};
+
+/// How long press_power_short holds the button.
+pub const SHORT_PRESS: Duration = Duration::from_millis(100);
+/// How long press_power_long holds the button: past the 5 seconds after
+/// which logind runs HandlePowerKeyLongPress instead of HandlePowerKey.
+pub const LONG_PRESS: Duration = Duration::from_millis(5500);
+
+fn frame(device: &Device, event_type: u16, code: u16, value: i32) -> Result<()> {
+    device.emit(event_type, code, value)?;
+    device.emit(EV_SYN, SYN_REPORT, 0)
+}
+
+/// Hold KEY_POWER for `hold`, then let go.
+pub fn press_power(device: &Device, hold: Duration) -> Result<()> {
+    frame(device, EV_KEY, KEY_POWER, 1)?;
+    sleep(hold);
+    frame(device, EV_KEY, KEY_POWER, 0)
+}
+
+/// A quick tap of the power button, what logind's HandlePowerKey is for.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::POWER_BUTTON_EVENTS,
+///     key_codes::KEY_POWER,
+///     scenarios,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let power = Device::with_backend(mock.clone(), &POWER_BUTTON_EVENTS, &UInputUserDevice::default()).unwrap();
+/// scenarios::press_power_short(&power).unwrap();
+///
+/// let presses: Vec<i32> = mock.events().iter().filter(|e| e.code == KEY_POWER).map(|e| e.value).collect();
+/// assert_eq!(presses, [1, 0]);
+/// ```
+pub fn press_power_short(device: &Device) -> Result<()> {
+    press_power(device, SHORT_PRESS)
+}
+
+/// Hold the power button for LONG_PRESS. Blocks for over five seconds.
+pub fn press_power_long(device: &Device) -> Result<()> {
+    press_power(device, LONG_PRESS)
+}
+
+/// A quick tap of KEY_SLEEP, what logind's HandleSuspendKey is for.
+pub fn press_sleep(device: &Device) -> Result<()> {
+    frame(device, EV_KEY, KEY_SLEEP, 1)?;
+    sleep(SHORT_PRESS);
+    frame(device, EV_KEY, KEY_SLEEP, 0)
+}
+
+/// Close the lid (SW_LID 1). logind runs HandleLidSwitch, or one of its
+/// docked/external power variants.
+pub fn lid_close(device: &Device) -> Result<()> {
+    frame(device, EV_SW, SW_LID, 1)
+}
+
+/// Open the lid (SW_LID 0).
+pub fn lid_open(device: &Device) -> Result<()> {
+    frame(device, EV_SW, SW_LID, 0)
+}
+
+/// Close the lid, wait `delay`, and open it again. Give logind a moment
+/// to suspend for the open to count as a resume.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::LID_SWITCH_EVENTS,
+///     key_types::EV_SW,
+///     scenarios,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let lid = Device::with_backend(mock.clone(), &LID_SWITCH_EVENTS, &UInputUserDevice::default()).unwrap();
+/// scenarios::lid_close_open(&lid, Duration::from_millis(10)).unwrap();
+///
+/// let states: Vec<i32> = mock.events().iter().filter(|e| e.type_ == EV_SW).map(|e| e.value).collect();
+/// assert_eq!(states, [1, 0]);
+/// ```
+pub fn lid_close_open(device: &Device, delay: Duration) -> Result<()> {
+    lid_close(device)?;
+    sleep(delay);
+    lid_open(device)
+}
@@ -0,0 +1,124 @@
+use std::{
+    io::Result,
+    sync::{Arc, Mutex},
+};
+
+use crate::{Device, UInputUserDevice, devices};
+
+/// The devices created inside a scope call. See scope.
+#[derive(Debug, Default)]
+pub struct Scope {
+    devices: Mutex<Vec<Arc<Device>>>,
+}
+
+impl Scope {
+    /// Put a device under the scope's care.
+    pub fn adopt(&self, device: Device) -> Arc<Device> {
+        let device = Arc::new(device);
+        self.devices
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(device.clone());
+        device
+    }
+
+    /// Create a device with the given events and description.
+    pub fn device(&self, events: &[(u64, u64)], info: &UInputUserDevice) -> Result<Arc<Device>> {
+        Ok(self.adopt(Device::new_custom(events, info)?))
+    }
+
+    /// Create a keyboard with devices::keyboard.
+    pub fn keyboard(&self, name: &str) -> Result<Arc<Device>> {
+        Ok(self.adopt(devices::keyboard(name)?))
+    }
+
+    /// Create a mouse with devices::mouse.
+    pub fn mouse(&self, name: &str) -> Result<Arc<Device>> {
+        Ok(self.adopt(devices::mouse(name)?))
+    }
+
+    /// Number of devices created so far.
+    pub fn len(&self) -> usize {
+        self.devices
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    /// Whether no devices were created.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let devices = self
+            .devices
+            .get_mut()
+            .unwrap_or_else(|err| err.into_inner());
+        for device in devices.drain(..) {
+            // Clones that escaped the scope would keep the device alive, so
+            // tear it down now.
+            device.teardown();
+        }
+    }
+}
+
+/// Run `f` with a Scope to create devices in. When `f` returns, fails or
+/// panics, every device created in the scope gets its keys released and
+/// is destroyed. The scope can be shared with std::thread::scope threads.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     key_codes::KEY_A, key_events::KEY_A_EVENT, key_types::EV_KEY,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let result = std::panic::catch_unwind(|| {
+///     uinput_rs::scope(|devices| {
+///         let keyboard = devices.adopt(Device::with_backend(mock.clone(), &[KEY_A_EVENT], &UInputUserDevice::default())?);
+///         std::thread::scope(|threads| {
+///             threads.spawn(|| {
+///                 keyboard.emit(EV_KEY, KEY_A, 1).unwrap();
+///                 keyboard.sync().unwrap();
+///             });
+///         });
+///         assert!(keyboard.pressed_keys().is_empty(), "the script blew up with A held");
+///         Ok(())
+///     })
+/// });
+///
+/// assert!(result.is_err());
+/// assert_eq!(mock.events().iter().filter(|e| e.type_ == EV_KEY).map(|e| e.value).collect::<Vec<_>>(), [1, 0]);
+/// assert!(mock.destroyed());
+/// ```
+///
+/// A device that escapes the scope can't be used after it:
+/// ```rust
+/// use uinput_rs::{Device, MockBackend, UInputUserDevice, key_codes::KEY_A, key_events::KEY_A_EVENT, key_types::EV_KEY};
+///
+/// let escaped = uinput_rs::scope(|devices| {
+///     Ok(devices.adopt(Device::with_backend(MockBackend::new(), &[KEY_A_EVENT], &UInputUserDevice::default())?))
+/// })
+/// .unwrap();
+/// assert!(escaped.emit(EV_KEY, KEY_A, 1).is_err());
+/// ```
+///
+/// With real devices:
+/// ```rust,no_run
+/// use uinput_rs::{key_codes::KEY_A, key_types::EV_KEY};
+///
+/// uinput_rs::scope(|devices| {
+///     let keyboard = devices.keyboard("Scripted keyboard")?;
+///     keyboard.emit(EV_KEY, KEY_A, 1)?;
+///     keyboard.sync()
+/// })
+/// .unwrap();
+/// ```
+pub fn scope<R>(f: impl FnOnce(&Scope) -> Result<R>) -> Result<R> {
+    let scope = Scope::default();
+    f(&scope)
+}
@@ -0,0 +1,163 @@
+use std::{fmt::Write, io::Result};
+
+use libc::input_id;
+
+use crate::{
+    Device,
+    devices::{GAMEPAD_EVENTS, gamepad_with_id},
+    key_codes::{
+        ABS_HAT0X, ABS_HAT3Y, ABS_RX, ABS_RY, ABS_RZ, ABS_X, ABS_Y, ABS_Z, BTN_A, BTN_B,
+        BTN_JOYSTICK, BTN_MODE, BTN_SELECT, BTN_START, BTN_THUMBL, BTN_THUMBR, BTN_TL, BTN_TR,
+        BTN_X, BTN_Y,
+    },
+    key_types::{EV_ABS, EV_KEY},
+};
+
+/// A controller identity SDL has a database entry for.
+#[derive(Debug, Clone, Copy)]
+pub struct SdlIdentity {
+    /// Name the real driver reports.
+    pub name: &'static str,
+    /// Bus, vendor, product and version of the real controller.
+    pub id: input_id,
+}
+
+const fn usb(name: &'static str, vendor: u16, product: u16, version: u16) -> SdlIdentity {
+    SdlIdentity {
+        name,
+        id: input_id {
+            bustype: 0x03,
+            vendor,
+            product,
+            version,
+        },
+    }
+}
+
+/// Wired Xbox 360 controller.
+pub const XBOX_360: SdlIdentity = usb("Microsoft X-Box 360 pad", 0x045e, 0x028e, 0x0114);
+/// Xbox One S controller over USB.
+pub const XBOX_ONE_S: SdlIdentity = usb("Microsoft X-Box One S pad", 0x045e, 0x02ea, 0x0301);
+/// Xbox Series X|S controller over USB.
+pub const XBOX_SERIES: SdlIdentity = usb(
+    "Microsoft Xbox Series S|X Controller",
+    0x045e,
+    0x0b12,
+    0x0509,
+);
+
+/// Every identity this module knows.
+pub const IDENTITIES: &[SdlIdentity] = &[XBOX_360, XBOX_ONE_S, XBOX_SERIES];
+
+/// The SDL2 joystick GUID of an input_id, as SDL prints it
+/// (32 lowercase hex digits, little endian fields, no CRC).
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::sdl;
+///
+/// assert_eq!(sdl::guid(&sdl::XBOX_360.id), "030000005e0400008e02000014010000");
+/// ```
+pub fn guid(id: &input_id) -> String {
+    let words = [id.bustype, 0, id.vendor, 0, id.product, 0, id.version, 0];
+    let mut guid = String::with_capacity(32);
+    for word in words {
+        for byte in word.to_le_bytes() {
+            let _ = write!(guid, "{byte:02x}");
+        }
+    }
+    guid
+}
+
+/// Finds a known identity by its SDL GUID. Case insensitive.
+pub fn identity_by_guid(guid: &str) -> Option<&'static SdlIdentity> {
+    IDENTITIES
+        .iter()
+        .find(|identity| self::guid(&identity.id).eq_ignore_ascii_case(guid))
+}
+
+/// Logical SDL controls and the codes the gamepad preset sends for them.
+const CONTROLS: &[(&str, u16, u16)] = &[
+    ("a", EV_KEY, BTN_A),
+    ("b", EV_KEY, BTN_B),
+    ("x", EV_KEY, BTN_X),
+    ("y", EV_KEY, BTN_Y),
+    ("back", EV_KEY, BTN_SELECT),
+    ("guide", EV_KEY, BTN_MODE),
+    ("start", EV_KEY, BTN_START),
+    ("leftstick", EV_KEY, BTN_THUMBL),
+    ("rightstick", EV_KEY, BTN_THUMBR),
+    ("leftshoulder", EV_KEY, BTN_TL),
+    ("rightshoulder", EV_KEY, BTN_TR),
+    ("leftx", EV_ABS, ABS_X),
+    ("lefty", EV_ABS, ABS_Y),
+    ("rightx", EV_ABS, ABS_RX),
+    ("righty", EV_ABS, ABS_RY),
+    ("lefttrigger", EV_ABS, ABS_Z),
+    ("righttrigger", EV_ABS, ABS_RZ),
+];
+
+/// SDL's button index of a key code on a device with these events.
+/// SDL counts from BTN_JOYSTICK up to KEY_MAX, then wraps to the codes
+/// below BTN_JOYSTICK.
+fn button_index(events: &[(u64, u64)], code: u16) -> Option<usize> {
+    let mut keys: Vec<u16> = events
+        .iter()
+        .filter(|&&(t, _)| t as u16 == EV_KEY)
+        .map(|&(_, c)| c as u16)
+        .collect();
+    keys.sort_by_key(|&c| (c < BTN_JOYSTICK, c));
+    keys.iter().position(|&c| c == code)
+}
+
+/// SDL's axis index of an ABS code. Hat axes don't count, they are hats.
+fn axis_index(events: &[(u64, u64)], code: u16) -> Option<usize> {
+    let mut axes: Vec<u16> = events
+        .iter()
+        .filter(|&&(t, c)| t as u16 == EV_ABS && !(ABS_HAT0X..=ABS_HAT3Y).contains(&(c as u16)))
+        .map(|&(_, c)| c as u16)
+        .collect();
+    axes.sort();
+    axes.iter().position(|&c| c == code)
+}
+
+/// The SDL_GameControllerDB line for devices::gamepad_with_id with this
+/// identity. Feed it to SDL_GameControllerAddMapping or
+/// SDL_GAMECONTROLLERCONFIG if the game's SDL doesn't already know the GUID.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::sdl;
+///
+/// let mapping = sdl::mapping_string(&sdl::XBOX_360);
+/// assert!(mapping.starts_with("030000005e0400008e02000014010000,Microsoft X-Box 360 pad,a:b0,b:b1,"));
+/// assert!(mapping.ends_with("dpup:h0.1,dpright:h0.2,dpdown:h0.4,dpleft:h0.8,platform:Linux,"));
+/// ```
+pub fn mapping_string(identity: &SdlIdentity) -> String {
+    let mut mapping = format!("{},{},", guid(&identity.id), identity.name);
+    for &(control, event_type, code) in CONTROLS {
+        let index = match event_type {
+            EV_KEY => button_index(&GAMEPAD_EVENTS, code).map(|i| format!("b{i}")),
+            _ => axis_index(&GAMEPAD_EVENTS, code).map(|i| format!("a{i}")),
+        };
+        if let Some(index) = index {
+            let _ = write!(mapping, "{control}:{index},");
+        }
+    }
+    mapping.push_str("dpup:h0.1,dpright:h0.2,dpdown:h0.4,dpleft:h0.8,platform:Linux,");
+    mapping
+}
+
+/// Creates the gamepad preset pretending to be the given controller, so SDL
+/// picks its database entry and shows the right button labels.
+/// The d-pad is ABS_HAT0X/ABS_HAT0Y, which SDL reads as hat 0.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::sdl;
+///
+/// let pad = sdl::gamepad(&sdl::XBOX_360).unwrap();
+/// ```
+pub fn gamepad(identity: &SdlIdentity) -> Result<Device> {
+    gamepad_with_id(identity.name, identity.id)
+}
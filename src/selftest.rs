@@ -0,0 +1,51 @@
+use std::{
+    io::{self, Result},
+    time::Duration,
+};
+
+use crate::{
+    Device, evdev::EvdevReader, key_codes::KEY_A, key_events::KEY_A_EVENT, key_types::EV_KEY,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Checks the whole path end to end: creates a throwaway device, opens its
+/// evdev node, presses and releases KEY_A and verifies both frames arrive
+/// intact.
+///
+/// Needs access to /dev/uinput and /dev/input/event*. A daemon can run this
+/// at startup (or periodically) as a health check.
+///
+/// Example:
+/// ```rust,no_run
+/// uinput_rs::selftest().expect("uinput is not usable");
+/// ```
+pub fn selftest() -> Result<()> {
+    let device = Device::builder()
+        .name("uinput-rs-selftest")
+        .event(KEY_A_EVENT)
+        .build()?;
+
+    let node = device.wait_until_registered(TIMEOUT)?;
+    let mut reader = EvdevReader::open(node)?;
+
+    for value in [1, 0] {
+        device.emit(EV_KEY, KEY_A, value)?;
+        device.sync()?;
+
+        let frame = reader.read_frame(TIMEOUT)?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::TimedOut, "injected frame never arrived")
+        })?;
+        let arrived = frame
+            .iter()
+            .any(|event| event.type_ == EV_KEY && event.code == KEY_A && event.value == value);
+        if !arrived {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected KEY_A {value}, got a frame without it"),
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use std::{
+    io::{self, Result},
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicI32, Ordering},
+    },
+    thread::{self, JoinHandle},
+};
+
+#[cfg(target_os = "android")]
+use libc::__errno as errno_location;
+#[cfg(not(target_os = "android"))]
+use libc::__errno_location as errno_location;
+use libc::{SIGINT, SIGTERM, c_int};
+
+use crate::{Device, cancel::CancelToken};
+
+/// Write end of the installed cleanup's pipe, -1 when there's none.
+static PIPE: AtomicI32 = AtomicI32::new(-1);
+
+/// What the handler writes to stop the thread instead of a signal.
+const STOP: u8 = 0;
+
+extern "C" fn on_signal(signal: c_int) {
+    let fd = PIPE.load(Ordering::Relaxed);
+    if fd >= 0 {
+        let byte = signal as u8;
+        // write() is async signal safe, nothing else happens in here. It can
+        // set errno though, which the interrupted code may be about to read.
+        unsafe {
+            let errno = *errno_location();
+            libc::write(fd, &byte as *const u8 as *const _, 1);
+            *errno_location() = errno;
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Targets {
+    devices: Vec<Arc<Device>>,
+    tokens: Vec<CancelToken>,
+}
+
+impl Targets {
+    fn clean_up(&self) {
+        for token in &self.tokens {
+            token.cancel();
+        }
+        for device in &self.devices {
+            device.teardown();
+        }
+    }
+}
+
+/// Lets go of every key and destroys the devices on SIGINT or SIGTERM,
+/// then lets the signal do what it would have done, so Ctrl-C in the
+/// middle of a combo doesn't leave modifiers stuck. The handler only wakes
+/// a thread up, the cleanup runs there. Cancel tokens added to it are
+/// cancelled first.
+///
+/// Only one can be installed at a time. Dropping it puts the old handlers
+/// back.
+///
+/// If signal-hook, tokio or something else already owns the signals,
+/// don't install this, call clean_up from that handler instead.
+///
+/// Example:
+/// ```rust
+/// use std::sync::Arc;
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     cancel::CancelToken,
+///     key_codes::KEY_LEFTCTRL,
+///     key_events::KEY_LEFTCTRL_EVENT,
+///     key_types::EV_KEY,
+///     signals::SignalCleanup,
+/// };
+///
+/// let mock = MockBackend::new();
+/// let device = Arc::new(Device::with_backend(mock.clone(), &[KEY_LEFTCTRL_EVENT], &UInputUserDevice::default()).unwrap());
+/// let token = CancelToken::new();
+/// let cleanup = SignalCleanup::install(vec![device.clone()]).unwrap();
+/// cleanup.add_cancel_token(token.clone());
+/// assert!(SignalCleanup::install(vec![]).is_err());
+///
+/// device.emit(EV_KEY, KEY_LEFTCTRL, 1).unwrap();
+/// device.sync().unwrap();
+/// // What Ctrl-C would do, minus the exiting.
+/// cleanup.clean_up();
+/// assert!(device.pressed_keys().is_empty());
+/// assert!(mock.destroyed());
+/// assert!(token.is_cancelled());
+/// ```
+pub struct SignalCleanup {
+    targets: Arc<Mutex<Targets>>,
+    pipe: [c_int; 2],
+    old: [(c_int, libc::sigaction); 2],
+    thread: Option<JoinHandle<()>>,
+}
+
+impl std::fmt::Debug for SignalCleanup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalCleanup")
+            .field("targets", &self.targets)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SignalCleanup {
+    /// Install the handlers for SIGINT and SIGTERM, cleaning up `devices`.
+    /// AlreadyExists if another one is installed.
+    pub fn install(devices: Vec<Arc<Device>>) -> Result<Self> {
+        let mut pipe = [-1; 2];
+        if unsafe { libc::pipe2(pipe.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if PIPE
+            .compare_exchange(-1, pipe[1], Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            close(pipe);
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "a signal cleanup is already installed",
+            ));
+        }
+
+        let targets = Arc::new(Mutex::new(Targets {
+            devices,
+            tokens: Vec::new(),
+        }));
+        let mut old = [
+            (SIGINT, unsafe { std::mem::zeroed() }),
+            (SIGTERM, unsafe { std::mem::zeroed() }),
+        ];
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = on_signal as extern "C" fn(c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        unsafe { libc::sigemptyset(&mut action.sa_mask) };
+        for i in 0..old.len() {
+            let (signal, ref mut previous) = old[i];
+            if unsafe { libc::sigaction(signal, &action, previous) } != 0 {
+                let err = io::Error::last_os_error();
+                abandon(pipe, &old[..i]);
+                return Err(err);
+            }
+        }
+
+        let watcher = targets.clone();
+        let read_fd = pipe[0];
+        let thread = match thread::Builder::new()
+            .name("uinput-signals".into())
+            .spawn(move || watch(read_fd, &watcher, old))
+        {
+            Ok(thread) => thread,
+            Err(err) => {
+                abandon(pipe, &old);
+                return Err(err);
+            }
+        };
+        Ok(Self {
+            targets,
+            pipe,
+            old,
+            thread: Some(thread),
+        })
+    }
+
+    /// Clean up another device as well.
+    pub fn add(&self, device: Arc<Device>) {
+        self.lock().devices.push(device);
+    }
+
+    /// Cancel a token before cleaning up, so whatever is typing or playing
+    /// stops first.
+    pub fn add_cancel_token(&self, token: CancelToken) {
+        self.lock().tokens.push(token);
+    }
+
+    /// Cancel the tokens, let go of everything and destroy the devices,
+    /// without a signal.
+    pub fn clean_up(&self) {
+        self.lock().clean_up();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Targets> {
+        self.targets.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Drop for SignalCleanup {
+    fn drop(&mut self) {
+        for (signal, old) in &self.old {
+            unsafe { libc::sigaction(*signal, old, std::ptr::null_mut()) };
+        }
+        PIPE.store(-1, Ordering::SeqCst);
+        unsafe { libc::write(self.pipe[1], &STOP as *const u8 as *const _, 1) };
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        close(self.pipe);
+    }
+}
+
+fn watch(read_fd: c_int, targets: &Mutex<Targets>, old: [(c_int, libc::sigaction); 2]) {
+    let mut byte = STOP;
+    loop {
+        let read = unsafe { libc::read(read_fd, &mut byte as *mut u8 as *mut _, 1) };
+        if read < 0 && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+            continue;
+        }
+        break;
+    }
+    if byte == STOP {
+        return;
+    }
+    let signal = c_int::from(byte);
+    #[cfg(feature = "log")]
+    log::warn!("signal {signal}, releasing and destroying devices");
+    targets
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clean_up();
+    // Put back what was there for both signals, nobody reads the pipe after
+    // this, and send the signal again so the process exits (or doesn't)
+    // like it would have without us.
+    for (old_signal, old) in &old {
+        unsafe { libc::sigaction(*old_signal, old, std::ptr::null_mut()) };
+    }
+    unsafe { libc::raise(signal) };
+}
+
+/// Undo a half finished install: put back the handlers already replaced,
+/// let go of PIPE and close the pipe.
+fn abandon(pipe: [c_int; 2], replaced: &[(c_int, libc::sigaction)]) {
+    for (signal, old) in replaced {
+        unsafe { libc::sigaction(*signal, old, std::ptr::null_mut()) };
+    }
+    PIPE.store(-1, Ordering::SeqCst);
+    close(pipe);
+}
+
+fn close(pipe: [c_int; 2]) {
+    for fd in pipe {
+        unsafe { libc::close(fd) };
+    }
+}
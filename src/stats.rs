@@ -0,0 +1,115 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use libc::input_event;
+
+use crate::{SYN_REPORT, key_types::EV_SYN};
+
+/// Snapshot of a device's emit counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeviceStats {
+    /// Events written to the kernel, SYN events included.
+    pub events: u64,
+    /// SYN_REPORTs written, in other words frames sent.
+    pub frames: u64,
+    /// Bytes written to the kernel.
+    pub bytes: u64,
+    /// Writes that failed.
+    pub write_errors: u64,
+    /// Events thrown away by RetryPolicy::DropAndCount.
+    pub dropped: u64,
+    /// Average time between frames. None until two frames were sent.
+    pub average_frame_gap: Option<Duration>,
+    /// Time since the last frame was sent. None until the first one.
+    /// A long running injector can use this to notice it stalled.
+    pub since_last_frame: Option<Duration>,
+}
+
+/// Lock free counters updated on every write.
+#[derive(Debug)]
+pub(crate) struct StatsRecorder {
+    created: Instant,
+    events: AtomicU64,
+    frames: AtomicU64,
+    bytes: AtomicU64,
+    write_errors: AtomicU64,
+    dropped: AtomicU64,
+    /// Nanoseconds since created. Only meaningful once frames > 0.
+    first_frame: AtomicU64,
+    last_frame: AtomicU64,
+}
+
+impl Default for StatsRecorder {
+    fn default() -> Self {
+        Self {
+            created: Instant::now(),
+            events: AtomicU64::new(0),
+            frames: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            write_errors: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            first_frame: AtomicU64::new(0),
+            last_frame: AtomicU64::new(0),
+        }
+    }
+}
+
+impl StatsRecorder {
+    pub(crate) fn record_written(&self, events: &[input_event]) {
+        self.events
+            .fetch_add(events.len() as u64, Ordering::Relaxed);
+        self.bytes
+            .fetch_add(std::mem::size_of_val(events) as u64, Ordering::Relaxed);
+
+        let frames = events
+            .iter()
+            .filter(|event| event.type_ == EV_SYN && event.code == SYN_REPORT)
+            .count() as u64;
+        if frames > 0 {
+            let now = self.created.elapsed().as_nanos() as u64;
+            if self.frames.fetch_add(frames, Ordering::Relaxed) == 0 {
+                self.first_frame.store(now, Ordering::Relaxed);
+            }
+            self.last_frame.store(now, Ordering::Relaxed);
+        }
+    }
+
+    /// Events written so far.
+    #[cfg(feature = "log")]
+    pub(crate) fn events(&self) -> u64 {
+        self.events.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record_error(&self) {
+        self.write_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_dropped(&self, events: usize) {
+        self.dropped.fetch_add(events as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> DeviceStats {
+        let frames = self.frames.load(Ordering::Relaxed);
+        let first = self.first_frame.load(Ordering::Relaxed);
+        let last = self.last_frame.load(Ordering::Relaxed);
+
+        DeviceStats {
+            events: self.events.load(Ordering::Relaxed),
+            frames,
+            bytes: self.bytes.load(Ordering::Relaxed),
+            write_errors: self.write_errors.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            // Concurrent emits can store first after another stored an
+            // older last.
+            average_frame_gap: (frames > 1)
+                .then(|| Duration::from_nanos(last.saturating_sub(first) / (frames - 1))),
+            since_last_frame: (frames > 0).then(|| {
+                self.created
+                    .elapsed()
+                    .saturating_sub(Duration::from_nanos(last))
+            }),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    fs,
+    io::Result,
+    thread::{self, sleep},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    DeviceBuilder, NamePolicy, SYN_REPORT,
+    device::new_event,
+    key_codes::KEY_A,
+    key_events::KEY_A_EVENT,
+    key_types::{EV_KEY, EV_SYN},
+};
+
+/// How hotplug() churns devices.
+#[derive(Debug, Clone)]
+pub struct HotplugConfig {
+    /// Create/destroy cycles per thread.
+    pub cycles: usize,
+    /// Threads churning devices at the same time.
+    pub concurrency: usize,
+    /// How long each device stays around.
+    pub lifetime: Duration,
+    /// Pause between destroying a device and creating the next one.
+    pub gap: Duration,
+    /// Events enabled on every device, in the (TYPE, CODE) format.
+    pub events: Vec<(u64, u64)>,
+    /// Devices are named "<prefix>-<thread>-<cycle>".
+    pub name_prefix: String,
+}
+
+impl Default for HotplugConfig {
+    fn default() -> Self {
+        Self {
+            cycles: 100,
+            concurrency: 1,
+            lifetime: Duration::from_millis(50),
+            gap: Duration::from_millis(10),
+            events: vec![KEY_A_EVENT],
+            name_prefix: "uinput-rs-hotplug".to_owned(),
+        }
+    }
+}
+
+/// What a hotplug() run did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HotplugReport {
+    /// Devices created (and destroyed again).
+    pub created: usize,
+    /// Creations that failed.
+    pub failed: usize,
+    /// Wall time of the whole run.
+    pub elapsed: Duration,
+}
+
+/// Repeatedly creates and destroys devices to stress how consumers handle
+/// hotplug: udev event storms, seat reassignment and the like.
+///
+/// Failed creations are counted rather than stopping the run. Fails only if
+/// a thread can't be spawned.
+///
+/// Example:
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use uinput_rs::stress::{HotplugConfig, hotplug};
+///
+/// let report = hotplug(HotplugConfig {
+///     cycles: 500,
+///     concurrency: 4,
+///     lifetime: Duration::from_millis(5),
+///     ..Default::default()
+/// })
+/// .unwrap();
+/// println!("{} devices, {} failures", report.created, report.failed);
+/// ```
+pub fn hotplug(config: HotplugConfig) -> Result<HotplugReport> {
+    let start = Instant::now();
+    let config = &config;
+
+    let counts = thread::scope(|scope| -> Result<Vec<(usize, usize)>> {
+        let mut threads = Vec::new();
+        for thread in 0..config.concurrency.max(1) {
+            threads.push(
+                thread::Builder::new()
+                    .name(format!("hotplug-{thread}"))
+                    .spawn_scoped(scope, move || churn(config, thread))?,
+            );
+        }
+        Ok(threads
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or((0, config.cycles)))
+            .collect())
+    })?;
+
+    let mut report = HotplugReport {
+        elapsed: start.elapsed(),
+        ..Default::default()
+    };
+    for (created, failed) in counts {
+        report.created += created;
+        report.failed += failed;
+    }
+    Ok(report)
+}
+
+fn churn(config: &HotplugConfig, thread: usize) -> (usize, usize) {
+    let (mut created, mut failed) = (0, 0);
+    for cycle in 0..config.cycles {
+        let device = DeviceBuilder::new()
+            .name(&format!("{}-{thread}-{cycle}", config.name_prefix))
+            .name_policy(NamePolicy::Truncate)
+            .events(&config.events)
+            .build();
+        match device {
+            Ok(device) => {
+                created += 1;
+                sleep(config.lifetime);
+                drop(device);
+            }
+            Err(_) => failed += 1,
+        }
+        sleep(config.gap);
+    }
+    (created, failed)
+}
+
+/// How soak() exercises the crate.
+#[derive(Debug, Clone)]
+pub struct SoakConfig {
+    /// Total run time. Hours for a real soak.
+    pub duration: Duration,
+    /// Key presses and releases emitted on each device before it's destroyed.
+    pub frames_per_device: usize,
+    /// Take an fd/memory sample every this many cycles.
+    pub sample_every: usize,
+    /// Devices are named "<prefix>-<cycle>".
+    pub name_prefix: String,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(60 * 60),
+            frames_per_device: 100,
+            sample_every: 100,
+            name_prefix: "uinput-rs-soak".to_owned(),
+        }
+    }
+}
+
+/// Open fds and resident memory of this process at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResourceSample {
+    /// Entries in /proc/self/fd.
+    pub fds: usize,
+    /// Resident set size in bytes.
+    pub rss: u64,
+}
+
+impl ResourceSample {
+    /// Measure now.
+    pub fn now() -> Result<Self> {
+        let fds = fs::read_dir("/proc/self/fd")?.count();
+        let statm = fs::read_to_string("/proc/self/statm")?;
+        let pages: u64 = statm
+            .split_whitespace()
+            .nth(1)
+            .and_then(|pages| pages.parse().ok())
+            .unwrap_or(0);
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(0) as u64;
+        Ok(Self {
+            fds,
+            rss: pages * page_size,
+        })
+    }
+}
+
+/// What a soak() run saw.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SoakReport {
+    /// Create/emit/destroy cycles completed.
+    pub cycles: usize,
+    /// Cycles that failed somewhere.
+    pub failures: usize,
+    /// Resources before the first cycle.
+    pub start: ResourceSample,
+    /// Resources after the last cycle.
+    pub end: ResourceSample,
+    /// Most fds seen in any sample.
+    pub max_fds: usize,
+    /// Devices with the soak name prefix still registered in sysfs at the end.
+    pub lingering_devices: usize,
+}
+
+impl SoakReport {
+    /// Fds open at the end that weren't at the start.
+    pub fn leaked_fds(&self) -> usize {
+        self.end.fds.saturating_sub(self.start.fds)
+    }
+
+    /// Nothing leaked: no extra fds and no leftover devices.
+    pub fn is_clean(&self) -> bool {
+        self.leaked_fds() == 0 && self.lingering_devices == 0
+    }
+}
+
+/// Runs create/emit/destroy cycles for config.duration while keeping an eye
+/// on open fds, memory and leftover uinput devices.
+/// Meant for people embedding the crate in always on daemons.
+///
+/// Example:
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use uinput_rs::stress::{SoakConfig, soak};
+///
+/// let report = soak(SoakConfig {
+///     duration: Duration::from_secs(10),
+///     ..Default::default()
+/// })
+/// .unwrap();
+/// assert!(report.is_clean(), "{report:?}");
+/// ```
+pub fn soak(config: SoakConfig) -> Result<SoakReport> {
+    let start = Instant::now();
+    let mut report = SoakReport {
+        start: ResourceSample::now()?,
+        ..Default::default()
+    };
+    report.max_fds = report.start.fds;
+
+    while start.elapsed() < config.duration {
+        if soak_cycle(&config, report.cycles).is_err() {
+            report.failures += 1;
+        }
+        report.cycles += 1;
+        if report.cycles.is_multiple_of(config.sample_every.max(1)) {
+            report.max_fds = report.max_fds.max(ResourceSample::now()?.fds);
+        }
+    }
+
+    report.end = ResourceSample::now()?;
+    report.max_fds = report.max_fds.max(report.end.fds);
+    report.lingering_devices = registered_with_prefix(&config.name_prefix);
+    Ok(report)
+}
+
+fn soak_cycle(config: &SoakConfig, cycle: usize) -> Result<()> {
+    let device = DeviceBuilder::new()
+        .name(&format!("{}-{cycle}", config.name_prefix))
+        .name_policy(NamePolicy::Truncate)
+        .event(KEY_A_EVENT)
+        .build()?;
+    let frame = |value| {
+        [
+            new_event(EV_KEY, KEY_A, value),
+            new_event(EV_SYN, SYN_REPORT, 0),
+        ]
+    };
+    for i in 0..config.frames_per_device {
+        device.emit_batch(&frame(i.is_multiple_of(2) as i32))?;
+    }
+    device.release_all()
+}
+
+/// Virtual input devices whose name starts with prefix.
+fn registered_with_prefix(prefix: &str) -> usize {
+    let Ok(entries) = fs::read_dir("/sys/devices/virtual/input") else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| fs::read_to_string(entry.ok()?.path().join("name")).ok())
+        .filter(|name| name.starts_with(prefix))
+        .count()
+}
@@ -0,0 +1,255 @@
+use std::io::{self, Result};
+
+use crate::{
+    Device, SYN_REPORT,
+    device::new_event,
+    devices,
+    key_codes::{
+        ABS_DISTANCE, ABS_PRESSURE, ABS_TILT_X, ABS_TILT_Y, ABS_X, ABS_Y, BTN_STYLUS, BTN_STYLUS2,
+        BTN_TOOL_PEN, BTN_TOOL_RUBBER, BTN_TOUCH,
+    },
+    key_types::{EV_ABS, EV_KEY, EV_SYN},
+};
+
+/// Which end of the pen is near the surface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PenTool {
+    /// The tip, BTN_TOOL_PEN.
+    #[default]
+    Pen,
+    /// The eraser end, BTN_TOOL_RUBBER.
+    Eraser,
+}
+
+impl PenTool {
+    /// BTN_TOOL_* code of the tool.
+    pub fn code(self) -> u16 {
+        match self {
+            PenTool::Pen => BTN_TOOL_PEN,
+            PenTool::Eraser => BTN_TOOL_RUBBER,
+        }
+    }
+}
+
+/// Where the pen is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PenState {
+    /// Out of range, the tablet doesn't see it.
+    Away,
+    /// In range above the surface.
+    Hovering,
+    /// On the surface.
+    Touching,
+}
+
+/// A pen on a tablet, sending the same sequences real tablets do:
+/// proximity in with BTN_TOOL_PEN/RUBBER, hover with ABS_DISTANCE, touch
+/// with BTN_TOUCH and pressure, and proximity out. Every call sends one
+/// frame.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{
+///     Device, MockBackend, UInputUserDevice,
+///     devices::TABLET_EVENTS,
+///     key_codes::{BTN_TOOL_PEN, BTN_TOOL_RUBBER},
+///     stylus::{PenState, PenTool, Stylus},
+/// };
+///
+/// let mock = MockBackend::new();
+/// let device = Device::with_backend(mock.clone(), &TABLET_EVENTS, &UInputUserDevice::default()).unwrap();
+/// let mut pen = Stylus::from_device(device);
+///
+/// pen.proximity_in(100, 100, 10).unwrap();
+/// pen.touch(100, 100, 2048).unwrap();
+/// pen.lift(5).unwrap();
+/// pen.set_tool(PenTool::Eraser).unwrap(); // out as pen, back in as eraser
+/// assert_eq!(pen.state(), PenState::Hovering);
+/// pen.proximity_out().unwrap();
+///
+/// let tools: Vec<(u16, i32)> = mock.events().iter()
+///     .filter(|e| e.code == BTN_TOOL_PEN || e.code == BTN_TOOL_RUBBER)
+///     .map(|e| (e.code, e.value))
+///     .collect();
+/// assert_eq!(tools, [(BTN_TOOL_PEN, 1), (BTN_TOOL_PEN, 0), (BTN_TOOL_RUBBER, 1), (BTN_TOOL_RUBBER, 0)]);
+/// ```
+#[derive(Debug)]
+pub struct Stylus {
+    device: Device,
+    tool: PenTool,
+    state: PenState,
+    position: (i32, i32),
+    distance: i32,
+}
+
+impl Stylus {
+    /// Create a devices::tablet.
+    pub fn new(name: &str, max_x: i32, max_y: i32, width_mm: f32, height_mm: f32) -> Result<Self> {
+        Ok(Self::from_device(devices::tablet(
+            name, max_x, max_y, width_mm, height_mm,
+        )?))
+    }
+
+    /// Wrap a device with at least TABLET_EVENTS' pen codes.
+    pub fn from_device(device: Device) -> Self {
+        Self {
+            device,
+            tool: PenTool::default(),
+            state: PenState::Away,
+            position: (0, 0),
+            distance: 0,
+        }
+    }
+
+    /// Where the pen is.
+    pub fn state(&self) -> PenState {
+        self.state
+    }
+
+    /// The tool in use.
+    pub fn tool(&self) -> PenTool {
+        self.tool
+    }
+
+    fn frame(&self, events: &[(u16, u16, i32)]) -> Result<()> {
+        let mut frame: Vec<_> = events
+            .iter()
+            .map(|&(event_type, code, value)| new_event(event_type, code, value))
+            .collect();
+        frame.push(new_event(EV_SYN, SYN_REPORT, 0));
+        self.device.emit_batch(&frame)
+    }
+
+    fn expect(&self, state: PenState, what: &str) -> Result<()> {
+        if self.state == state {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("can't {what} while the pen is {:?}", self.state),
+            ))
+        }
+    }
+
+    /// Bring the tool into range at a hover distance.
+    pub fn proximity_in(&mut self, x: i32, y: i32, distance: i32) -> Result<()> {
+        self.expect(PenState::Away, "come into proximity")?;
+        self.frame(&[
+            (EV_KEY, self.tool.code(), 1),
+            (EV_ABS, ABS_X, x),
+            (EV_ABS, ABS_Y, y),
+            (EV_ABS, ABS_DISTANCE, distance),
+        ])?;
+        self.state = PenState::Hovering;
+        self.position = (x, y);
+        self.distance = distance;
+        Ok(())
+    }
+
+    /// Move while hovering, changing the distance too.
+    pub fn hover(&mut self, x: i32, y: i32, distance: i32) -> Result<()> {
+        self.expect(PenState::Hovering, "hover")?;
+        self.frame(&[
+            (EV_ABS, ABS_X, x),
+            (EV_ABS, ABS_Y, y),
+            (EV_ABS, ABS_DISTANCE, distance),
+        ])?;
+        self.position = (x, y);
+        self.distance = distance;
+        Ok(())
+    }
+
+    /// Put the tip (or eraser) down.
+    pub fn touch(&mut self, x: i32, y: i32, pressure: i32) -> Result<()> {
+        self.expect(PenState::Hovering, "touch")?;
+        self.frame(&[
+            (EV_ABS, ABS_X, x),
+            (EV_ABS, ABS_Y, y),
+            (EV_ABS, ABS_DISTANCE, 0),
+            (EV_ABS, ABS_PRESSURE, pressure),
+            (EV_KEY, BTN_TOUCH, 1),
+        ])?;
+        self.state = PenState::Touching;
+        self.position = (x, y);
+        self.distance = 0;
+        Ok(())
+    }
+
+    /// Move while touching.
+    pub fn draw(&mut self, x: i32, y: i32, pressure: i32) -> Result<()> {
+        self.expect(PenState::Touching, "draw")?;
+        self.frame(&[
+            (EV_ABS, ABS_X, x),
+            (EV_ABS, ABS_Y, y),
+            (EV_ABS, ABS_PRESSURE, pressure),
+        ])?;
+        self.position = (x, y);
+        Ok(())
+    }
+
+    /// Tilt the pen, in whatever state.
+    pub fn tilt(&mut self, x: i32, y: i32) -> Result<()> {
+        self.frame(&[(EV_ABS, ABS_TILT_X, x), (EV_ABS, ABS_TILT_Y, y)])
+    }
+
+    /// Lift off the surface and keep hovering at distance.
+    pub fn lift(&mut self, distance: i32) -> Result<()> {
+        self.expect(PenState::Touching, "lift")?;
+        self.frame(&[
+            (EV_ABS, ABS_PRESSURE, 0),
+            (EV_KEY, BTN_TOUCH, 0),
+            (EV_ABS, ABS_DISTANCE, distance),
+        ])?;
+        self.state = PenState::Hovering;
+        self.distance = distance;
+        Ok(())
+    }
+
+    /// Take the tool out of range. Lifts first if touching.
+    pub fn proximity_out(&mut self) -> Result<()> {
+        if self.state == PenState::Touching {
+            self.lift(self.distance)?;
+        }
+        self.expect(PenState::Hovering, "leave proximity")?;
+        self.frame(&[(EV_ABS, ABS_DISTANCE, 0), (EV_KEY, self.tool.code(), 0)])?;
+        self.state = PenState::Away;
+        Ok(())
+    }
+
+    /// Flip the pen. Like a real one it leaves proximity as one tool and
+    /// comes back as the other at the same spot.
+    pub fn set_tool(&mut self, tool: PenTool) -> Result<()> {
+        if tool == self.tool {
+            return Ok(());
+        }
+        if self.state == PenState::Away {
+            self.tool = tool;
+            return Ok(());
+        }
+        let ((x, y), distance) = (self.position, self.distance.max(1));
+        self.proximity_out()?;
+        self.tool = tool;
+        self.proximity_in(x, y, distance)
+    }
+
+    /// Press or release a barrel button, BTN_STYLUS or BTN_STYLUS2.
+    pub fn button(&self, code: u16, pressed: bool) -> Result<()> {
+        if code != BTN_STYLUS && code != BTN_STYLUS2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{code:#x} is not a stylus button"),
+            ));
+        }
+        self.frame(&[(EV_KEY, code, pressed as i32)])
+    }
+
+    /// The underlying device.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Take the device back.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+}
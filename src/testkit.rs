@@ -0,0 +1,188 @@
+use std::{
+    fmt::Write,
+    io::{self, Result},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use libc::input_event;
+
+use crate::{
+    Device, DisplayEvent, SYN_REPORT,
+    evdev::EvdevReader,
+    key_types::{EV_ABS, EV_KEY, EV_REL, EV_SYN},
+};
+
+/// Value of a key event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyValue {
+    /// 0
+    Released = 0,
+    /// 1
+    Pressed = 1,
+    /// 2, autorepeat
+    Repeated = 2,
+}
+
+/// A device paired with a reader on its own evdev node, for integration
+/// tests of remappers, macro engines and the like.
+///
+/// Example:
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use uinput_rs::{
+///     Device,
+///     key_codes::KEY_A,
+///     key_events::KEY_A_EVENT,
+///     testkit::{KeyValue::*, TestDevice},
+/// };
+///
+/// let mut test = TestDevice::new(Device::new(&[KEY_A_EVENT]).unwrap()).unwrap();
+///
+/// test.device().emit_key_code(KEY_A_EVENT, 1).unwrap();
+/// test.device().sync().unwrap();
+///
+/// test.expect()
+///     .key(KEY_A, Pressed)
+///     .then_syn()
+///     .within(Duration::from_millis(50))
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct TestDevice {
+    device: Device,
+    node: PathBuf,
+    reader: EvdevReader,
+}
+
+impl TestDevice {
+    /// Wait for the device's node (up to a second) and open it.
+    pub fn new(device: Device) -> Result<Self> {
+        let node = device.wait_until_registered(Duration::from_secs(1))?;
+        let reader = EvdevReader::open(&node)?;
+        Ok(Self {
+            device,
+            node,
+            reader,
+        })
+    }
+
+    /// The device under test.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Path of the evdev node being read.
+    pub fn node(&self) -> &PathBuf {
+        &self.node
+    }
+
+    /// The reader, for reading events by hand.
+    pub fn reader(&mut self) -> &mut EvdevReader {
+        &mut self.reader
+    }
+
+    /// Start describing events that should arrive next.
+    pub fn expect(&mut self) -> Expectation<'_> {
+        Expectation {
+            reader: &mut self.reader,
+            matchers: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Matcher {
+    event_type: u16,
+    code: u16,
+    value: Option<i32>,
+}
+
+impl Matcher {
+    fn matches(&self, event: &input_event) -> bool {
+        event.type_ == self.event_type
+            && event.code == self.code
+            && self.value.is_none_or(|value| value == event.value)
+    }
+}
+
+/// Events expected to arrive, in order. Made with TestDevice::expect.
+///
+/// Unrelated events in between (MSC_SCAN and the like) are skipped.
+/// Nothing is read until within() is called.
+#[derive(Debug)]
+#[must_use = "nothing is checked until within() is called"]
+pub struct Expectation<'a> {
+    reader: &'a mut EvdevReader,
+    matchers: Vec<Matcher>,
+}
+
+impl Expectation<'_> {
+    /// Any event. A value of None matches every value.
+    pub fn event(mut self, event_type: u16, code: u16, value: Option<i32>) -> Self {
+        self.matchers.push(Matcher {
+            event_type,
+            code,
+            value,
+        });
+        self
+    }
+
+    /// A key or button event.
+    pub fn key(self, code: u16, value: KeyValue) -> Self {
+        self.event(EV_KEY, code, Some(value as i32))
+    }
+
+    /// A relative axis event.
+    pub fn rel(self, code: u16, value: i32) -> Self {
+        self.event(EV_REL, code, Some(value))
+    }
+
+    /// An absolute axis event.
+    pub fn abs(self, code: u16, value: i32) -> Self {
+        self.event(EV_ABS, code, Some(value))
+    }
+
+    /// A SYN_REPORT.
+    pub fn then_syn(self) -> Self {
+        self.event(EV_SYN, SYN_REPORT, None)
+    }
+
+    /// Read until every expected event has arrived in order.
+    /// Fails with TimedOut, listing what was missing and what did arrive,
+    /// if that takes longer than timeout.
+    pub fn within(self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        let mut seen = Vec::new();
+        let mut next = 0;
+
+        while next < self.matchers.len() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !self.reader.wait(remaining)? {
+                return Err(self.timed_out(next, &seen));
+            }
+            let event = self.reader.read_event()?;
+            if self.matchers[next].matches(&event) {
+                next += 1;
+            }
+            seen.push(event);
+        }
+        Ok(())
+    }
+
+    fn timed_out(&self, next: usize, seen: &[input_event]) -> io::Error {
+        let missing = &self.matchers[next];
+        let mut msg = format!(
+            "expected type {:#x} code {:#x} value {:?}, got:",
+            missing.event_type, missing.code, missing.value
+        );
+        for event in seen {
+            let _ = write!(msg, " [{}]", DisplayEvent(event));
+        }
+        if seen.is_empty() {
+            msg.push_str(" nothing");
+        }
+        io::Error::new(io::ErrorKind::TimedOut, msg)
+    }
+}
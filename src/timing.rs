@@ -0,0 +1,268 @@
+use std::{
+    io::{self, Result},
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+use libc::{CLOCK_MONOTONIC, TIMER_ABSTIME, timespec};
+
+fn monotonic_now() -> timespec {
+    let mut now = timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // Can't fail with a valid clock id and pointer.
+    unsafe { libc::clock_gettime(CLOCK_MONOTONIC, &mut now) };
+    now
+}
+
+fn add(ts: timespec, offset: Duration) -> timespec {
+    let nsec = ts.tv_nsec as u64 + offset.subsec_nanos() as u64;
+    timespec {
+        tv_sec: ts.tv_sec
+            + offset.as_secs() as libc::time_t
+            + (nsec / 1_000_000_000) as libc::time_t,
+        tv_nsec: (nsec % 1_000_000_000) as _,
+    }
+}
+
+fn diff(later: timespec, earlier: timespec) -> Duration {
+    let later = Duration::new(later.tv_sec as u64, later.tv_nsec as u32);
+    let earlier = Duration::new(earlier.tv_sec as u64, earlier.tv_nsec as u32);
+    later.saturating_sub(earlier)
+}
+
+/// How to wait for a deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pacing {
+    /// Let the kernel wake us up. Cheap, but wake ups can be late by tens of
+    /// microseconds or more depending on the timer slack and load.
+    #[default]
+    Sleep,
+    /// Sleep until `spin` before the deadline and busy wait the rest.
+    /// Burns a bit of CPU for much steadier sub millisecond intervals.
+    Hybrid {
+        /// How long before the deadline to stop sleeping.
+        spin: Duration,
+    },
+    /// Busy wait the whole time.
+    Spin,
+}
+
+fn is_before(a: timespec, b: timespec) -> bool {
+    (a.tv_sec, a.tv_nsec) < (b.tv_sec, b.tv_nsec)
+}
+
+fn sub(ts: timespec, offset: Duration) -> timespec {
+    let ts = Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32).saturating_sub(offset);
+    timespec {
+        tv_sec: ts.as_secs() as libc::time_t,
+        tv_nsec: ts.subsec_nanos() as _,
+    }
+}
+
+fn sleep_until_abs(deadline: timespec) -> Result<()> {
+    loop {
+        let ret = unsafe {
+            libc::clock_nanosleep(
+                CLOCK_MONOTONIC,
+                TIMER_ABSTIME,
+                &deadline,
+                std::ptr::null_mut(),
+            )
+        };
+        match ret {
+            0 => return Ok(()),
+            // Absolute deadlines can be retried as is after a signal.
+            libc::EINTR => continue,
+            err => return Err(io::Error::from_raw_os_error(err)),
+        }
+    }
+}
+
+fn spin_until_abs(deadline: timespec) {
+    while is_before(monotonic_now(), deadline) {
+        std::hint::spin_loop();
+    }
+}
+
+/// Sleeps until absolute CLOCK_MONOTONIC deadlines measured from a fixed start.
+///
+/// Every deadline is computed from the start instead of from the previous
+/// wake up, so oversleeping once doesn't push every later event back.
+/// A 10 minute recording ends 10 minutes after it started, not 10 minutes
+/// plus the sum of every scheduler hiccup.
+#[derive(Debug, Clone, Copy)]
+pub struct Scheduler {
+    start: timespec,
+}
+
+impl Scheduler {
+    /// Start the clock now.
+    pub fn start() -> Self {
+        Self {
+            start: monotonic_now(),
+        }
+    }
+
+    /// Time since the scheduler was started.
+    pub fn elapsed(&self) -> Duration {
+        diff(monotonic_now(), self.start)
+    }
+
+    /// Sleep until `offset` after the start.
+    /// Returns immediately if the deadline already passed.
+    pub fn sleep_until(&self, offset: Duration) -> Result<()> {
+        self.wait_until(offset, Pacing::Sleep)
+    }
+
+    /// Wait until `offset` after the start using the given pacing.
+    /// Returns immediately if the deadline already passed.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use uinput_rs::timing::{Pacing, Scheduler};
+    ///
+    /// let scheduler = Scheduler::start();
+    /// let pacing = Pacing::Hybrid {
+    ///     spin: Duration::from_micros(200),
+    /// };
+    /// for i in 1..=4 {
+    ///     scheduler.wait_until(Duration::from_micros(500 * i), pacing).unwrap();
+    /// }
+    /// assert!(scheduler.elapsed() >= Duration::from_millis(2));
+    /// ```
+    pub fn wait_until(&self, offset: Duration, pacing: Pacing) -> Result<()> {
+        let deadline = add(self.start, offset);
+        match pacing {
+            Pacing::Sleep => sleep_until_abs(deadline),
+            Pacing::Hybrid { spin } => {
+                sleep_until_abs(sub(deadline, spin))?;
+                spin_until_abs(deadline);
+                Ok(())
+            }
+            Pacing::Spin => {
+                spin_until_abs(deadline);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Source of time for timed helpers like playback.
+/// Offsets are measured from when the clock started.
+pub trait Clock {
+    /// Time since the start.
+    fn elapsed(&self) -> Duration;
+
+    /// Wait until `offset` after the start.
+    /// Returns immediately if the deadline already passed.
+    fn wait_until(&self, offset: Duration, pacing: Pacing) -> Result<()>;
+}
+
+impl Clock for Scheduler {
+    fn elapsed(&self) -> Duration {
+        Scheduler::elapsed(self)
+    }
+
+    fn wait_until(&self, offset: Duration, pacing: Pacing) -> Result<()> {
+        Scheduler::wait_until(self, offset, pacing)
+    }
+}
+
+#[derive(Debug, Default)]
+struct ManualState {
+    now: Mutex<Duration>,
+    moved: Condvar,
+}
+
+/// Clock for tests that only moves when told to. Clones share the same time.
+///
+/// ManualClock::new() blocks waiters until another thread advances it.
+/// ManualClock::instant() jumps straight to every deadline, so a replay
+/// finishes immediately but in the same order and with the same timestamps.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+///
+/// use uinput_rs::{
+///     Device, MockBackend, SYN_REPORT, UInputUserDevice,
+///     key_codes::REL_X, key_events::REL_X_EVENT, key_types::{EV_REL, EV_SYN},
+///     playback::{Playback, TimedEvent},
+///     timing::{Clock, ManualClock},
+/// };
+///
+/// let mock = MockBackend::new();
+/// let device = Device::with_backend(mock.clone(), &[REL_X_EVENT], &UInputUserDevice::default()).unwrap();
+/// let at = Duration::from_secs(60);
+/// let events = [TimedEvent::new(at, EV_REL, REL_X, 5), TimedEvent::new(at, EV_SYN, SYN_REPORT, 0)];
+///
+/// let clock = ManualClock::instant();
+/// Playback::new().play_with_clock(&device, &events, &clock).unwrap();
+/// assert_eq!(clock.elapsed(), at);
+/// assert_eq!(mock.frames().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    state: Arc<ManualState>,
+    instant: bool,
+}
+
+impl ManualClock {
+    /// Starts at zero. Waiting blocks until advance() or set() passes the deadline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts at zero. Waiting moves the clock to the deadline right away.
+    pub fn instant() -> Self {
+        Self {
+            instant: true,
+            ..Self::default()
+        }
+    }
+
+    fn now(&self) -> std::sync::MutexGuard<'_, Duration> {
+        self.state.now.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Move time forward.
+    pub fn advance(&self, by: Duration) {
+        *self.now() += by;
+        self.state.moved.notify_all();
+    }
+
+    /// Jump to `offset` after the start. Time never goes backwards, so an
+    /// earlier offset does nothing.
+    pub fn set(&self, offset: Duration) {
+        let mut now = self.now();
+        *now = (*now).max(offset);
+        drop(now);
+        self.state.moved.notify_all();
+    }
+}
+
+impl Clock for ManualClock {
+    fn elapsed(&self) -> Duration {
+        *self.now()
+    }
+
+    fn wait_until(&self, offset: Duration, _pacing: Pacing) -> Result<()> {
+        if self.instant {
+            self.set(offset);
+            return Ok(());
+        }
+        let mut now = self.now();
+        while *now < offset {
+            now = self
+                .state
+                .moved
+                .wait(now)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+        Ok(())
+    }
+}
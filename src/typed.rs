@@ -0,0 +1,164 @@
+use std::{io::Result, marker::PhantomData};
+
+use libc::input_id;
+
+use crate::{
+    Device, DeviceBuilder,
+    key_types::{EV_ABS, EV_KEY, EV_REL},
+};
+
+/// Marker for an event type that was enabled.
+#[derive(Debug)]
+pub enum Enabled {}
+
+/// Marker for an event type that wasn't enabled.
+#[derive(Debug)]
+pub enum Disabled {}
+
+/// DeviceBuilder that remembers in its type which of EV_KEY, EV_REL and
+/// EV_ABS were enabled, so the built device only has emitters for those.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::{key_codes::{BTN_LEFT, REL_X, REL_Y}, typed::TypedBuilder};
+///
+/// let mouse = TypedBuilder::new()
+///     .name("typed-mouse")
+///     .key(BTN_LEFT)
+///     .rel(REL_X)
+///     .rel(REL_Y)
+///     .build()
+///     .unwrap();
+///
+/// mouse.emit_rel(REL_X, 10).unwrap();
+/// mouse.sync().unwrap();
+/// ```
+///
+/// A mouse has no absolute axes, so this doesn't compile:
+/// ```rust,compile_fail
+/// use uinput_rs::{key_codes::{ABS_X, REL_X}, typed::TypedBuilder};
+///
+/// let mouse = TypedBuilder::new().rel(REL_X).build().unwrap();
+/// mouse.emit_abs(ABS_X, 100).unwrap();
+/// ```
+pub struct TypedBuilder<K = Disabled, R = Disabled, A = Disabled> {
+    inner: DeviceBuilder,
+    _marker: PhantomData<(K, R, A)>,
+}
+
+impl Default for TypedBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypedBuilder {
+    /// Nothing enabled yet.
+    pub fn new() -> Self {
+        Self {
+            inner: DeviceBuilder::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, R, A> TypedBuilder<K, R, A> {
+    fn map<K2, R2, A2>(
+        self,
+        f: impl FnOnce(DeviceBuilder) -> DeviceBuilder,
+    ) -> TypedBuilder<K2, R2, A2> {
+        TypedBuilder {
+            inner: f(self.inner),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Human readable name of the device.
+    pub fn name(self, name: &str) -> Self {
+        self.map(|inner| inner.name(name))
+    }
+
+    /// Bus type, vendor, product and version.
+    pub fn id(self, id: input_id) -> Self {
+        self.map(|inner| inner.id(id))
+    }
+
+    /// Enable a key or button.
+    pub fn key(self, code: u16) -> TypedBuilder<Enabled, R, A> {
+        self.map(|inner| inner.key(code))
+    }
+
+    /// Enable a relative axis.
+    pub fn rel(self, code: u16) -> TypedBuilder<K, Enabled, A> {
+        self.map(|inner| inner.rel(code))
+    }
+
+    /// Enable an absolute axis with the given range.
+    pub fn abs(self, code: u16, min: i32, max: i32) -> TypedBuilder<K, R, Enabled> {
+        self.map(|inner| inner.abs(code, min, max))
+    }
+
+    /// Create the device. Same checks as DeviceBuilder::build.
+    pub fn build(self) -> Result<TypedDevice<K, R, A>> {
+        Ok(TypedDevice {
+            device: self.inner.build()?,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Device whose type says which event types it can emit.
+/// Made with TypedBuilder.
+#[derive(Debug)]
+pub struct TypedDevice<K, R, A> {
+    device: Device,
+    _marker: PhantomData<(K, R, A)>,
+}
+
+impl<K, R, A> TypedDevice<K, R, A> {
+    /// Fires all emitted events in queue.
+    pub fn sync(&self) -> Result<()> {
+        self.device.sync()
+    }
+
+    /// The untyped device, for everything else.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Give up the type level checks.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+}
+
+impl<R, A> TypedDevice<Enabled, R, A> {
+    /// Emit a key event. 1 is press, 0 release and 2 autorepeat.
+    pub fn emit_key(&self, code: u16, value: i32) -> Result<()> {
+        self.device.emit(EV_KEY, code, value)
+    }
+
+    /// Press a key. Remember to sync.
+    pub fn press(&self, code: u16) -> Result<()> {
+        self.emit_key(code, 1)
+    }
+
+    /// Release a key. Remember to sync.
+    pub fn release(&self, code: u16) -> Result<()> {
+        self.emit_key(code, 0)
+    }
+}
+
+impl<K, A> TypedDevice<K, Enabled, A> {
+    /// Emit a relative axis event.
+    pub fn emit_rel(&self, code: u16, value: i32) -> Result<()> {
+        self.device.emit(EV_REL, code, value)
+    }
+}
+
+impl<K, R> TypedDevice<K, R, Enabled> {
+    /// Emit an absolute axis event.
+    pub fn emit_abs(&self, code: u16, value: i32) -> Result<()> {
+        self.device.emit(EV_ABS, code, value)
+    }
+}
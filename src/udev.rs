@@ -0,0 +1,187 @@
+use std::{
+    fs,
+    io::{self, Result},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Where install puts rules by default.
+pub const RULES_DIR: &str = "/etc/udev/rules.d";
+
+/// Builds a udev rule that gives a group access to /dev/uinput and to the
+/// event nodes of devices created through it, so the program doesn't need
+/// to run as root.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::udev::UdevRule;
+///
+/// let rule = UdevRule::new("input").device("my virtual keyboard").uaccess().render().unwrap();
+///
+/// assert!(rule.contains(r#"KERNEL=="uinput", SUBSYSTEM=="misc", GROUP="input", MODE="0660""#));
+/// assert!(rule.contains(r#"ATTRS{name}=="my virtual keyboard""#));
+/// assert!(rule.contains(r#"TAG+="uaccess""#));
+///
+/// // Anything that could end the line or the string and sneak in another
+/// // rule is refused.
+/// assert!(UdevRule::new("input\", RUN+=\"/bin/sh").render().is_err());
+/// assert!(UdevRule::new("input").device("pad\nRUN+=\"/bin/sh\"").render().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UdevRule {
+    group: String,
+    mode: u32,
+    devices: Vec<String>,
+    uaccess: bool,
+    seat: Option<String>,
+}
+
+impl UdevRule {
+    /// Rule granting group read/write access to /dev/uinput.
+    pub fn new(group: impl Into<String>) -> Self {
+        Self {
+            group: group.into(),
+            mode: 0o660,
+            devices: Vec::new(),
+            uaccess: false,
+            seat: None,
+        }
+    }
+
+    /// Node permissions. Defaults to 0660.
+    pub fn mode(mut self, mode: u32) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Also grant access to the event nodes of devices with this name.
+    /// Can be called more than once.
+    pub fn device(mut self, name: impl Into<String>) -> Self {
+        self.devices.push(name.into());
+        self
+    }
+
+    /// Tag the nodes with uaccess, so logind gives the user of the active
+    /// session access too.
+    pub fn uaccess(mut self) -> Self {
+        self.uaccess = true;
+        self
+    }
+
+    /// Assign the created devices to a seat other than seat0.
+    pub fn seat(mut self, seat: impl Into<String>) -> Self {
+        self.seat = Some(seat.into());
+        self
+    }
+
+    fn access(&self) -> String {
+        let mut access = format!(r#"GROUP="{}", MODE="{:04o}""#, self.group, self.mode);
+        if self.uaccess {
+            access.push_str(r#", TAG+="uaccess""#);
+        }
+        access
+    }
+
+    /// The rules file contents. InvalidInput if the group or seat has a
+    /// quote or a control character, or a device name has a control
+    /// character, since those could break out of the rule.
+    pub fn render(&self) -> Result<String> {
+        check("group", &self.group, true)?;
+        if let Some(seat) = &self.seat {
+            check("seat", seat, true)?;
+        }
+        for name in &self.devices {
+            check("device name", name, false)?;
+        }
+        let access = self.access();
+        let mut rules = String::from("# Generated by uinput-rs\n");
+        rules.push_str(&format!(
+            "KERNEL==\"uinput\", SUBSYSTEM==\"misc\", {access}, OPTIONS+=\"static_node=uinput\"\n"
+        ));
+        for name in &self.devices {
+            let mut line = format!(
+                "SUBSYSTEM==\"input\", KERNEL==\"event*\", ATTRS{{name}}==\"{}\", {access}",
+                escape(name)
+            );
+            if let Some(seat) = &self.seat {
+                line.push_str(&format!(r#", TAG+="seat", ENV{{ID_SEAT}}="{seat}""#));
+            }
+            rules.push_str(&line);
+            rules.push('\n');
+        }
+        Ok(rules)
+    }
+
+    /// Write the rule to RULES_DIR/file_name (e.g. "60-myapp-uinput.rules"),
+    /// reload udev and retrigger /dev/uinput so it applies without a
+    /// reboot. Needs root. Returns the path written.
+    ///
+    /// Example:
+    /// ```rust,no_run
+    /// use uinput_rs::udev::UdevRule;
+    ///
+    /// UdevRule::new("input").uaccess().install("60-myapp-uinput.rules").unwrap();
+    /// ```
+    pub fn install(&self, file_name: &str) -> Result<PathBuf> {
+        let path = self.install_to(RULES_DIR, file_name)?;
+        reload()?;
+        Ok(path)
+    }
+
+    /// Write the rule into another rules directory without reloading,
+    /// e.g. for packaging into a staging root.
+    pub fn install_to(&self, dir: impl AsRef<Path>, file_name: &str) -> Result<PathBuf> {
+        if file_name.contains('/') || !file_name.ends_with(".rules") {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{file_name:?} is not a plain *.rules file name"),
+            ));
+        }
+        let path = dir.as_ref().join(file_name);
+        fs::write(&path, self.render()?)?;
+        Ok(path)
+    }
+}
+
+/// Refuse values that would end the line, or with `no_quotes`, the string.
+fn check(what: &str, value: &str, no_quotes: bool) -> Result<()> {
+    if value
+        .chars()
+        .any(|c| c.is_control() || (no_quotes && c == '"'))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{what} {value:?} can't go in a udev rule"),
+        ));
+    }
+    Ok(())
+}
+
+/// Escape characters that would end or break a udev match string.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Ask udev to reload its rules and reapply them to /dev/uinput and the
+/// input subsystem.
+pub fn reload() -> Result<()> {
+    udevadm(&["control", "--reload-rules"])?;
+    udevadm(&[
+        "trigger",
+        "--subsystem-match=misc",
+        "--sysname-match=uinput",
+    ])?;
+    udevadm(&["trigger", "--subsystem-match=input"])
+}
+
+fn udevadm(args: &[&str]) -> Result<()> {
+    let status = Command::new("udevadm").args(args).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "udevadm {} failed with {status}",
+            args.join(" ")
+        )))
+    }
+}
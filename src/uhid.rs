@@ -0,0 +1,218 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Result, Write},
+};
+
+const UHID_DESTROY: u32 = 1;
+const UHID_CREATE2: u32 = 11;
+const UHID_INPUT2: u32 = 12;
+
+/// Largest report descriptor or input report the kernel takes.
+pub const UHID_DATA_MAX: usize = 4096;
+
+/// sizeof(struct uhid_event): the type plus the biggest union member,
+/// uhid_create2_req. The struct is packed.
+const EVENT_SIZE: usize = 4 + 128 + 64 + 64 + 2 + 2 + 4 * 4 + UHID_DATA_MAX;
+
+/// Standard boot protocol keyboard: 8 byte reports of
+/// [modifiers, reserved, key1..key6] with LED output reports.
+pub const BOOT_KEYBOARD_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, 0x09, 0x06, 0xa1, 0x01, 0x05, 0x07, 0x19, 0xe0, 0x29, 0xe7, 0x15, 0x00, 0x25, 0x01,
+    0x75, 0x01, 0x95, 0x08, 0x81, 0x02, 0x95, 0x01, 0x75, 0x08, 0x81, 0x01, 0x95, 0x05, 0x75, 0x01,
+    0x05, 0x08, 0x19, 0x01, 0x29, 0x05, 0x91, 0x02, 0x95, 0x01, 0x75, 0x03, 0x91, 0x01, 0x95, 0x06,
+    0x75, 0x08, 0x15, 0x00, 0x25, 0x65, 0x05, 0x07, 0x19, 0x00, 0x29, 0x65, 0x81, 0x00, 0xc0,
+];
+
+/// Three button mouse with a wheel: 4 byte reports of
+/// [buttons, x, y, wheel], movement as signed bytes.
+pub const BOOT_MOUSE_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, 0x09, 0x02, 0xa1, 0x01, 0x09, 0x01, 0xa1, 0x00, 0x05, 0x09, 0x19, 0x01, 0x29, 0x03,
+    0x15, 0x00, 0x25, 0x01, 0x95, 0x03, 0x75, 0x01, 0x81, 0x02, 0x95, 0x01, 0x75, 0x05, 0x81, 0x01,
+    0x05, 0x01, 0x09, 0x30, 0x09, 0x31, 0x09, 0x38, 0x15, 0x81, 0x25, 0x7f, 0x75, 0x08, 0x95, 0x03,
+    0x81, 0x06, 0xc0, 0xc0,
+];
+
+/// Report for BOOT_KEYBOARD_DESCRIPTOR.
+/// modifiers is a bitmask (bit 0 left ctrl .. bit 7 right meta) and keys are
+/// HID usage IDs, at most six. Extra keys are ignored.
+pub fn keyboard_report(modifiers: u8, keys: &[u8]) -> [u8; 8] {
+    let mut report = [0u8; 8];
+    report[0] = modifiers;
+    for (slot, &key) in report[2..].iter_mut().zip(keys) {
+        *slot = key;
+    }
+    report
+}
+
+/// Report for BOOT_KEYBOARD_DESCRIPTOR from held evdev keys.
+/// Modifiers go in the bitmask, keys without a keyboard page usage are
+/// skipped and only the first six other keys fit.
+///
+/// Example:
+/// ```rust
+/// use uinput_rs::{key_codes::{KEY_A, KEY_LEFTSHIFT}, uhid};
+///
+/// assert_eq!(uhid::keyboard_report_from_evdev(&[KEY_LEFTSHIFT, KEY_A]), [0x02, 0, 0x04, 0, 0, 0, 0, 0]);
+/// ```
+pub fn keyboard_report_from_evdev(codes: &[u16]) -> [u8; 8] {
+    let mut modifiers = 0;
+    let mut keys = Vec::with_capacity(6);
+    for &code in codes {
+        match crate::hid::from_evdev(code) {
+            Some((crate::hid::KEYBOARD_PAGE, usage @ 0xe0..=0xe7)) => {
+                modifiers |= 1 << (usage - 0xe0)
+            }
+            Some((crate::hid::KEYBOARD_PAGE, usage)) => keys.push(usage as u8),
+            _ => {}
+        }
+    }
+    keyboard_report(modifiers, &keys)
+}
+
+/// Report for BOOT_MOUSE_DESCRIPTOR.
+/// buttons is a bitmask: bit 0 left, bit 1 right, bit 2 middle.
+pub fn mouse_report(buttons: u8, dx: i8, dy: i8, wheel: i8) -> [u8; 4] {
+    [buttons & 0b111, dx as u8, dy as u8, wheel as u8]
+}
+
+/// What a UhidDevice looks like to the HID layer.
+#[derive(Debug, Clone)]
+pub struct UhidConfig {
+    /// Device name, up to 127 bytes.
+    pub name: String,
+    /// Physical path, up to 63 bytes. Can be empty.
+    pub phys: String,
+    /// Unique id (serial), up to 63 bytes. Can be empty.
+    pub uniq: String,
+    /// BUS_* type. BUS_USB is 0x03.
+    pub bus: u16,
+    /// Vendor ID.
+    pub vendor: u32,
+    /// Product ID.
+    pub product: u32,
+    /// Hardware/firmware version.
+    pub version: u32,
+    /// HID country code, 0 if not localized.
+    pub country: u32,
+    /// The HID report descriptor.
+    pub descriptor: Vec<u8>,
+}
+
+impl Default for UhidConfig {
+    fn default() -> Self {
+        Self {
+            name: "rusty-hid-device".to_owned(),
+            phys: String::new(),
+            uniq: String::new(),
+            bus: 0x03, // BUS_USB
+            vendor: 0x1,
+            product: 0x1,
+            version: 1,
+            country: 0,
+            descriptor: BOOT_KEYBOARD_DESCRIPTOR.to_vec(),
+        }
+    }
+}
+
+/// Copies s into field, failing if it doesn't fit with a null terminator.
+fn put_str(field: &mut [u8], s: &str, what: &str) -> Result<()> {
+    if s.len() >= field.len() || s.contains('\0') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("uhid {what} is too long or contains a null byte"),
+        ));
+    }
+    field[..s.len()].copy_from_slice(s.as_bytes());
+    Ok(())
+}
+
+/// A virtual HID device on /dev/uhid.
+///
+/// Sits below uinput: the kernel parses the report descriptor and every
+/// report like it would for real hardware, so software that only talks to
+/// hidraw or recognizes real HID devices (SDL's HIDAPI paths, vendor tools)
+/// sees it too. The device is destroyed on drop.
+///
+/// Example:
+/// ```rust,no_run
+/// use uinput_rs::uhid::{self, UhidConfig, UhidDevice};
+///
+/// let keyboard = UhidDevice::create(&UhidConfig {
+///     name: "my-hid-keyboard".to_owned(),
+///     ..Default::default()
+/// })
+/// .unwrap();
+///
+/// // HID usage 0x04 is the A key.
+/// keyboard.send_report(&uhid::keyboard_report(0, &[0x04])).unwrap();
+/// keyboard.send_report(&uhid::keyboard_report(0, &[])).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct UhidDevice {
+    file: File,
+}
+
+impl UhidDevice {
+    /// Open /dev/uhid and create the device.
+    pub fn create(config: &UhidConfig) -> Result<Self> {
+        if config.descriptor.len() > UHID_DATA_MAX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "report descriptor is too long",
+            ));
+        }
+
+        let mut event = [0u8; EVENT_SIZE];
+        event[0..4].copy_from_slice(&UHID_CREATE2.to_ne_bytes());
+        put_str(&mut event[4..132], &config.name, "name")?;
+        put_str(&mut event[132..196], &config.phys, "phys")?;
+        put_str(&mut event[196..260], &config.uniq, "uniq")?;
+        event[260..262].copy_from_slice(&(config.descriptor.len() as u16).to_ne_bytes());
+        event[262..264].copy_from_slice(&config.bus.to_ne_bytes());
+        event[264..268].copy_from_slice(&config.vendor.to_ne_bytes());
+        event[268..272].copy_from_slice(&config.product.to_ne_bytes());
+        event[272..276].copy_from_slice(&config.version.to_ne_bytes());
+        event[276..280].copy_from_slice(&config.country.to_ne_bytes());
+        event[280..280 + config.descriptor.len()].copy_from_slice(&config.descriptor);
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/uhid")?;
+        let device = Self { file };
+        device.write_event(&event)?;
+        Ok(device)
+    }
+
+    fn write_event(&self, event: &[u8]) -> Result<()> {
+        let written = (&self.file).write(event)?;
+        if written == event.len() {
+            Ok(())
+        } else {
+            Err(io::ErrorKind::WriteZero.into())
+        }
+    }
+
+    /// Send an input report, laid out the way the report descriptor says.
+    /// Include the report ID as the first byte if the descriptor uses them.
+    pub fn send_report(&self, report: &[u8]) -> Result<()> {
+        if report.len() > UHID_DATA_MAX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "report is too long",
+            ));
+        }
+        let mut event = [0u8; 4 + 2 + UHID_DATA_MAX];
+        event[0..4].copy_from_slice(&UHID_INPUT2.to_ne_bytes());
+        event[4..6].copy_from_slice(&(report.len() as u16).to_ne_bytes());
+        event[6..6 + report.len()].copy_from_slice(report);
+        self.write_event(&event)
+    }
+}
+
+impl Drop for UhidDevice {
+    fn drop(&mut self) {
+        // Closing the fd destroys the device too, this just makes it explicit.
+        let _ = self.write_event(&UHID_DESTROY.to_ne_bytes());
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    io::Result,
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::Device;
+
+#[derive(Debug)]
+struct State {
+    last_pet: Instant,
+    stopped: bool,
+    fired: bool,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+/// Tears the devices down if the owner stops petting it. A thread waits
+/// for pets; when `timeout` passes without one it releases everything held
+/// on every device and destroys them, so a hung main loop doesn't leave
+/// ghost devices with keys stuck down. Dropping the watchdog stops it
+/// without touching the devices.
+///
+/// Example:
+/// ```rust
+/// use std::{sync::Arc, thread::sleep, time::Duration};
+/// use uinput_rs::{Device, MockBackend, UInputUserDevice, key_codes::KEY_A, key_events::KEY_A_EVENT, key_types::EV_KEY, watchdog::Watchdog};
+///
+/// let mock = MockBackend::new();
+/// let device = Arc::new(Device::with_backend(mock.clone(), &[KEY_A_EVENT], &UInputUserDevice::default()).unwrap());
+/// let watchdog = Watchdog::spawn(vec![device.clone()], Duration::from_millis(50)).unwrap();
+///
+/// device.emit(EV_KEY, KEY_A, 1).unwrap();
+/// device.sync().unwrap();
+/// watchdog.pet();
+///
+/// // The main loop hangs.
+/// sleep(Duration::from_millis(300));
+/// assert!(watchdog.fired());
+/// assert!(device.pressed_keys().is_empty());
+/// assert!(mock.destroyed());
+/// ```
+#[derive(Debug)]
+pub struct Watchdog {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    /// Start watching the devices.
+    pub fn spawn(devices: Vec<Arc<Device>>, timeout: Duration) -> Result<Self> {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                last_pet: Instant::now(),
+                stopped: false,
+                fired: false,
+            }),
+            wake: Condvar::new(),
+        });
+        let watcher = shared.clone();
+        let thread = thread::Builder::new()
+            .name("uinput-watchdog".into())
+            .spawn(move || watch(&watcher, &devices, timeout))?;
+        Ok(Self {
+            shared,
+            thread: Some(thread),
+        })
+    }
+
+    /// Tell the watchdog the owner is alive.
+    pub fn pet(&self) {
+        self.lock().last_pet = Instant::now();
+    }
+
+    /// Whether it timed out and tore the devices down.
+    pub fn fired(&self) -> bool {
+        self.lock().fired
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.lock().stopped = true;
+        self.shared.wake.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn watch(shared: &Shared, devices: &[Arc<Device>], timeout: Duration) {
+    let mut state = shared.state.lock().unwrap_or_else(|err| err.into_inner());
+    loop {
+        if state.stopped {
+            return;
+        }
+        let deadline = state.last_pet + timeout;
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        state = shared
+            .wake
+            .wait_timeout(state, deadline - now)
+            .unwrap_or_else(|err| err.into_inner())
+            .0;
+    }
+    #[cfg(feature = "log")]
+    log::error!(
+        "watchdog: no pet for {timeout:?}, destroying {} devices",
+        devices.len()
+    );
+    for device in devices {
+        device.teardown();
+    }
+    state.fired = true;
+}
@@ -0,0 +1,143 @@
+use std::{io::Result, sync::Mutex};
+
+use libc::input_event;
+
+use crate::{
+    Capabilities, SYN_REPORT,
+    device::new_event,
+    key_codes::{REL_HWHEEL, REL_HWHEEL_HI_RES, REL_WHEEL, REL_WHEEL_HI_RES},
+    key_types::{EV_REL, EV_SYN},
+};
+
+/// Hi-res units per notch.
+const NOTCH: i32 = 120;
+
+#[derive(Debug)]
+struct Axis {
+    legacy: u16,
+    hi_res: u16,
+    /// 120ths scrolled without a notch yet.
+    remainder: i32,
+    /// What the current frame has of each code.
+    frame_legacy: Option<i32>,
+    frame_hi_res: Option<i32>,
+}
+
+impl Axis {
+    fn new(legacy: u16, hi_res: u16) -> Self {
+        Self {
+            legacy,
+            hi_res,
+            remainder: 0,
+            frame_legacy: None,
+            frame_hi_res: None,
+        }
+    }
+
+    /// Note a wheel event, false if it's not for this axis.
+    fn observe(&mut self, event: &input_event) -> bool {
+        let slot = match event.code {
+            code if code == self.legacy => &mut self.frame_legacy,
+            code if code == self.hi_res => &mut self.frame_hi_res,
+            _ => return false,
+        };
+        *slot = Some(slot.unwrap_or(0) + event.value);
+        true
+    }
+
+    /// The event the frame is missing, if any. Resets the frame.
+    fn companion(&mut self) -> Option<input_event> {
+        match (self.frame_legacy.take(), self.frame_hi_res.take()) {
+            (Some(notches), None) => {
+                self.remainder = 0;
+                Some(new_event(EV_REL, self.hi_res, notches * NOTCH))
+            }
+            (None, Some(hi_res)) => {
+                // Like hid-input, a change of direction starts over.
+                if self.remainder.signum() * hi_res.signum() < 0 {
+                    self.remainder = 0;
+                }
+                self.remainder += hi_res;
+                let notches = self.remainder / NOTCH;
+                self.remainder -= notches * NOTCH;
+                (notches != 0).then(|| new_event(EV_REL, self.legacy, notches))
+            }
+            (Some(_), Some(_)) => {
+                self.remainder = 0;
+                None
+            }
+            (None, None) => None,
+        }
+    }
+}
+
+/// Fills in REL_WHEEL for frames that only have REL_WHEEL_HI_RES and the
+/// other way around, same for the horizontal wheel.
+#[derive(Debug)]
+pub(crate) struct WheelPairing {
+    axes: Mutex<Vec<Axis>>,
+}
+
+impl WheelPairing {
+    /// Pairing for the wheels that have both codes enabled, None when
+    /// there are none.
+    pub(crate) fn for_capabilities(capabilities: &Capabilities) -> Option<Self> {
+        let axes: Vec<_> = [
+            (REL_WHEEL, REL_WHEEL_HI_RES),
+            (REL_HWHEEL, REL_HWHEEL_HI_RES),
+        ]
+        .into_iter()
+        .filter(|&(legacy, hi_res)| {
+            capabilities.contains(EV_REL, legacy) && capabilities.contains(EV_REL, hi_res)
+        })
+        .map(|(legacy, hi_res)| Axis::new(legacy, hi_res))
+        .collect();
+        (!axes.is_empty()).then(|| Self {
+            axes: Mutex::new(axes),
+        })
+    }
+
+    /// Pass the events to write with the missing halves added in front of
+    /// each SYN_REPORT. Runs of frames that need nothing go through in one
+    /// piece, a frame that does is split before its SYN_REPORT so nothing
+    /// gets copied to the heap.
+    pub(crate) fn pair(
+        &self,
+        events: &[input_event],
+        mut write: impl FnMut(&[input_event]) -> Result<()>,
+    ) -> Result<()> {
+        let mut axes = self.axes.lock().unwrap_or_else(|err| err.into_inner());
+        let mut start = 0;
+        for (i, event) in events.iter().enumerate() {
+            if event.type_ == EV_REL {
+                axes.iter_mut().any(|axis| axis.observe(event));
+                continue;
+            }
+            if event.type_ != EV_SYN || event.code != SYN_REPORT {
+                continue;
+            }
+            // At most one companion per axis, then the SYN_REPORT itself.
+            let mut tail = [*event; 3];
+            let mut len = 0;
+            for axis in axes.iter_mut() {
+                if let Some(companion) = axis.companion() {
+                    tail[len] = companion;
+                    len += 1;
+                }
+            }
+            if len == 0 {
+                continue;
+            }
+            tail[len] = *event;
+            if start < i {
+                write(&events[start..i])?;
+            }
+            write(&tail[..=len])?;
+            start = i + 1;
+        }
+        if start < events.len() {
+            write(&events[start..])?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    io::Result,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use uinput_rs::{
+    Device, SYN_REPORT, UInputUserDevice, UinputBackend, input_event,
+    key_codes::{BTN_LEFT, REL_X, REL_Y},
+    key_events::{BTN_LEFT_EVENT, REL_X_EVENT, REL_Y_EVENT},
+    key_types::{EV_KEY, EV_REL, EV_SYN},
+};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Accepts everything and keeps nothing. MockBackend records every write,
+/// which allocates, so it would hide the device's own allocations.
+#[derive(Debug)]
+struct Discard;
+
+impl UinputBackend for Discard {
+    fn open() -> Result<Self> {
+        Ok(Self)
+    }
+
+    unsafe fn ioctl(&self, _request: u64, _arg: u64) -> Result<()> {
+        Ok(())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        Ok(buf.len())
+    }
+}
+
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    COUNTING.with(|counting| counting.set(true));
+    f();
+    COUNTING.with(|counting| counting.set(false));
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn event(type_: u16, code: u16, value: i32) -> input_event {
+    let mut event: input_event = unsafe { std::mem::zeroed() };
+    event.type_ = type_;
+    event.code = code;
+    event.value = value;
+    event
+}
+
+#[test]
+fn emit_path_does_not_allocate() {
+    let device = Device::with_backend(
+        Discard,
+        &[BTN_LEFT_EVENT, REL_X_EVENT, REL_Y_EVENT],
+        &UInputUserDevice::default(),
+    )
+    .unwrap();
+    let frame = [
+        event(EV_REL, REL_X, 1),
+        event(EV_REL, REL_Y, 1),
+        event(EV_SYN, SYN_REPORT, 0),
+    ];
+
+    let count = allocations(|| {
+        for _ in 0..100 {
+            device.emit(EV_REL, REL_X, 1).unwrap();
+            device.emit(EV_REL, REL_Y, -1).unwrap();
+            device.sync().unwrap();
+            device.emit_batch(&frame).unwrap();
+            device.emit(EV_KEY, BTN_LEFT, 1).unwrap();
+            device.sync().unwrap();
+            device.emit(EV_KEY, BTN_LEFT, 0).unwrap();
+            device.sync().unwrap();
+        }
+    });
+    assert_eq!(count, 0);
+}
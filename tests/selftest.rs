@@ -0,0 +1,10 @@
+use std::path::Path;
+
+#[test]
+fn loopback() {
+    if !Path::new("/dev/uinput").exists() {
+        eprintln!("Skipping, /dev/uinput doesn't exist");
+        return;
+    }
+    uinput_rs::selftest().unwrap();
+}